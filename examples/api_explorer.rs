@@ -1,3 +1,4 @@
+use chrono::NaiveDate;
 use sumup_rs::{payouts::PayoutListQuery, SumUpClient};
 
 #[tokio::main]
@@ -24,7 +25,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Payouts
     let payouts = client
-        .list_merchant_payouts(mc, &PayoutListQuery::new("2024-01-01", "2024-12-31"))
+        .list_merchant_payouts(
+            mc,
+            &PayoutListQuery::new(
+                NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+            ),
+        )
         .await?;
     println!("Payouts: {}", payouts.items.len());
 
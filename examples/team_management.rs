@@ -22,7 +22,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             mc,
             &CreateRoleRequest {
                 name: "Manager".to_string(),
-                permissions: vec!["read_transactions".to_string()],
+                permissions: vec!["read_transactions".into()],
             },
         )
         .await?;
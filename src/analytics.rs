@@ -0,0 +1,489 @@
+//! Sales analytics over transaction history.
+//!
+//! [`SalesAnalytics`] groups transactions into hour, day or week buckets in the
+//! merchant's local time and totals them per currency with exact [`Money`] arithmetic:
+//! gross sales, refunds, chargebacks, tips and VAT, the number of sales and the average
+//! ticket, with breakdowns by payment type, entry mode and card scheme.
+//!
+//! Bucket boundaries follow the time zone's daylight saving rules, so a day bucket is 23
+//! or 25 hours long on the days the clocks change.
+//!
+//! Enabled with the `analytics` cargo feature.
+
+use crate::{
+    Currency, Error, Merchant, Money, Result, Transaction, TransactionEventType, TransactionStatus,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+
+/// Length of an analytics bucket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BucketSize {
+    Hour,
+    Day,
+    /// ISO week, starting on Monday
+    Week,
+}
+
+/// Count and gross amount of the sales in one breakdown category
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakdown {
+    pub count: usize,
+    pub gross: Money,
+}
+
+/// Totals for one currency over a period
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SalesSummary {
+    pub currency: Currency,
+    /// Number of sales: successful, refunded and charged back transactions
+    pub count: usize,
+    /// Amount of all sales, including tips
+    pub gross: Money,
+    /// Amount refunded in the period, one count per refund
+    pub refunds: Money,
+    pub refund_count: usize,
+    /// Amount charged back in the period, one count per chargeback
+    pub chargebacks: Money,
+    pub chargeback_count: usize,
+    pub tips: Money,
+    pub vat: Money,
+    /// Sales by `payment_type`; transactions without one are not broken down
+    pub by_payment_type: BTreeMap<String, Breakdown>,
+    /// Sales by `entry_mode`; transactions without one are not broken down
+    pub by_entry_mode: BTreeMap<String, Breakdown>,
+    /// Sales by card scheme; transactions without one are not broken down
+    pub by_card_type: BTreeMap<String, Breakdown>,
+}
+
+impl SalesSummary {
+    fn new(currency: &Currency) -> Result<Self> {
+        let zero = Money::zero(currency.clone())?;
+        Ok(Self {
+            currency: currency.clone(),
+            count: 0,
+            gross: zero.clone(),
+            refunds: zero.clone(),
+            refund_count: 0,
+            chargebacks: zero.clone(),
+            chargeback_count: 0,
+            tips: zero.clone(),
+            vat: zero,
+            by_payment_type: BTreeMap::new(),
+            by_entry_mode: BTreeMap::new(),
+            by_card_type: BTreeMap::new(),
+        })
+    }
+
+    /// Gross sales less refunds and chargebacks
+    pub fn net(&self) -> Result<Money> {
+        Ok(self
+            .gross
+            .checked_sub(&self.refunds)?
+            .checked_sub(&self.chargebacks)?)
+    }
+
+    /// Gross divided by the number of sales, rounded to the currency's minor unit
+    pub fn average_ticket(&self) -> Result<Option<Money>> {
+        if self.count == 0 {
+            return Ok(None);
+        }
+        let average = self.gross.amount().0 / rust_decimal::Decimal::from(self.count as u64);
+        Ok(Some(Money::rounded(average, self.currency.clone())?))
+    }
+
+    fn add_sale(&mut self, transaction: &Transaction) -> Result<()> {
+        let gross = transaction.money()?;
+        self.count += 1;
+        self.gross = self.gross.checked_add(&gross)?;
+        self.tips = self.tips.checked_add(&transaction.tip()?)?;
+        if let Some(vat) = transaction.vat_amount {
+            self.vat = self
+                .vat
+                .checked_add(&Money::new(vat, transaction.currency.clone())?)?;
+        }
+        let single = Breakdown { count: 1, gross };
+        if let Some(payment_type) = &transaction.payment_type {
+            add_breakdown(&mut self.by_payment_type, payment_type, &single)?;
+        }
+        if let Some(entry_mode) = &transaction.entry_mode {
+            add_breakdown(&mut self.by_entry_mode, entry_mode, &single)?;
+        }
+        if let Some(card_type) = transaction.card_scheme() {
+            add_breakdown(&mut self.by_card_type, card_type.as_str(), &single)?;
+        }
+        Ok(())
+    }
+
+    /// Add the totals of another summary in the same currency
+    pub fn merge(&mut self, other: &SalesSummary) -> Result<()> {
+        self.count += other.count;
+        self.gross = self.gross.checked_add(&other.gross)?;
+        self.refund_count += other.refund_count;
+        self.refunds = self.refunds.checked_add(&other.refunds)?;
+        self.chargeback_count += other.chargeback_count;
+        self.chargebacks = self.chargebacks.checked_add(&other.chargebacks)?;
+        self.tips = self.tips.checked_add(&other.tips)?;
+        self.vat = self.vat.checked_add(&other.vat)?;
+        for (key, breakdown) in &other.by_payment_type {
+            add_breakdown(&mut self.by_payment_type, key, breakdown)?;
+        }
+        for (key, breakdown) in &other.by_entry_mode {
+            add_breakdown(&mut self.by_entry_mode, key, breakdown)?;
+        }
+        for (key, breakdown) in &other.by_card_type {
+            add_breakdown(&mut self.by_card_type, key, breakdown)?;
+        }
+        Ok(())
+    }
+}
+
+fn add_breakdown(
+    map: &mut BTreeMap<String, Breakdown>,
+    key: &str,
+    breakdown: &Breakdown,
+) -> Result<()> {
+    match map.get_mut(key) {
+        Some(existing) => {
+            existing.count += breakdown.count;
+            existing.gross = existing.gross.checked_add(&breakdown.gross)?;
+        }
+        None => {
+            map.insert(key.to_string(), breakdown.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Totals of one currency within one time bucket
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SalesBucket {
+    /// Start of the bucket in the report's time zone (inclusive)
+    pub start: DateTime<Tz>,
+    /// Start of the next bucket (exclusive)
+    pub end: DateTime<Tz>,
+    pub summary: SalesSummary,
+}
+
+/// Result of [`SalesAnalytics::aggregate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SalesReport {
+    pub timezone: Tz,
+    pub bucket_size: BucketSize,
+    /// Buckets that contain sales, refunds or chargebacks, ordered by start then currency
+    pub buckets: Vec<SalesBucket>,
+    /// Transactions that are not sales (failed, cancelled or pending)
+    pub skipped: usize,
+}
+
+impl SalesReport {
+    /// Totals over all buckets, one per currency, ordered by currency code
+    pub fn totals(&self) -> Result<Vec<SalesSummary>> {
+        let mut totals: BTreeMap<String, SalesSummary> = BTreeMap::new();
+        for bucket in &self.buckets {
+            let currency = &bucket.summary.currency;
+            match totals.get_mut(currency.as_str()) {
+                Some(total) => total.merge(&bucket.summary)?,
+                None => {
+                    totals.insert(currency.to_string(), bucket.summary.clone());
+                }
+            }
+        }
+        Ok(totals.into_values().collect())
+    }
+}
+
+/// Aggregates transactions into time buckets in a time zone.
+///
+/// Successful, refunded and charged back transactions count as sales; refunds and
+/// chargebacks are additionally totalled on their own from the transaction's refund and
+/// chargeback events, in the bucket of each event's time, so partial refunds count with
+/// their own amount. Transactions from the history carry no events; a refunded or charged
+/// back one then counts its full amount as of the sale's time (see
+/// [`Transaction::reversals`]). Transactions without a status are treated as successful.
+#[derive(Debug, Clone)]
+pub struct SalesAnalytics {
+    bucket_size: BucketSize,
+    timezone: Tz,
+}
+
+impl SalesAnalytics {
+    /// Bucket by `bucket_size` in UTC
+    pub fn new(bucket_size: BucketSize) -> Self {
+        Self {
+            bucket_size,
+            timezone: Tz::UTC,
+        }
+    }
+
+    pub fn timezone(mut self, timezone: Tz) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Use the merchant's IANA `timezone` (e.g. `"Europe/Berlin"`); UTC if it has none
+    pub fn merchant_timezone(self, merchant: &Merchant) -> Result<Self> {
+        match merchant.timezone.as_deref() {
+            Some(name) => {
+                let timezone = name.parse::<Tz>().map_err(|_| {
+                    Error::InvalidInput(format!("unknown merchant time zone '{}'", name))
+                })?;
+                Ok(self.timezone(timezone))
+            }
+            None => Ok(self),
+        }
+    }
+
+    pub fn aggregate<'a>(
+        &self,
+        transactions: impl IntoIterator<Item = &'a Transaction>,
+    ) -> Result<SalesReport> {
+        let mut buckets: BTreeMap<(DateTime<Utc>, String), SalesBucket> = BTreeMap::new();
+        let mut skipped = 0;
+
+        for transaction in transactions {
+            if !matches!(
+                transaction.status,
+                None | Some(TransactionStatus::Successful)
+                    | Some(TransactionStatus::Refunded)
+                    | Some(TransactionStatus::ChargeBack)
+            ) {
+                skipped += 1;
+                continue;
+            }
+
+            self.bucket(&mut buckets, transaction.timestamp, &transaction.currency)?
+                .add_sale(transaction)?;
+            for (amount, time) in transaction.reversals(TransactionEventType::Refund) {
+                let refund = Money::new(amount, transaction.currency.clone())?;
+                let summary = self.bucket(&mut buckets, time, &transaction.currency)?;
+                summary.refund_count += 1;
+                summary.refunds = summary.refunds.checked_add(&refund)?;
+            }
+            for (amount, time) in transaction.reversals(TransactionEventType::ChargeBack) {
+                let chargeback = Money::new(amount, transaction.currency.clone())?;
+                let summary = self.bucket(&mut buckets, time, &transaction.currency)?;
+                summary.chargeback_count += 1;
+                summary.chargebacks = summary.chargebacks.checked_add(&chargeback)?;
+            }
+        }
+
+        Ok(SalesReport {
+            timezone: self.timezone,
+            bucket_size: self.bucket_size,
+            buckets: buckets.into_values().collect(),
+            skipped,
+        })
+    }
+
+    /// Summary of the bucket containing `timestamp` in `currency`, created if missing
+    fn bucket<'b>(
+        &self,
+        buckets: &'b mut BTreeMap<(DateTime<Utc>, String), SalesBucket>,
+        timestamp: DateTime<Utc>,
+        currency: &Currency,
+    ) -> Result<&'b mut SalesSummary> {
+        let (start, end) = self.bucket_of(timestamp);
+        let key = (start.with_timezone(&Utc), currency.to_string());
+        let bucket = match buckets.entry(key) {
+            std::collections::btree_map::Entry::Occupied(e) => e.into_mut(),
+            std::collections::btree_map::Entry::Vacant(e) => e.insert(SalesBucket {
+                start,
+                end,
+                summary: SalesSummary::new(currency)?,
+            }),
+        };
+        Ok(&mut bucket.summary)
+    }
+
+    /// Start and end of the bucket containing `timestamp`
+    fn bucket_of(&self, timestamp: DateTime<Utc>) -> (DateTime<Tz>, DateTime<Tz>) {
+        let local = timestamp.with_timezone(&self.timezone);
+        match self.bucket_size {
+            BucketSize::Hour => {
+                let start = local
+                    - Duration::seconds(i64::from(local.minute() * 60 + local.second()))
+                    - Duration::nanoseconds(i64::from(local.nanosecond()));
+                (start, start + Duration::hours(1))
+            }
+            BucketSize::Day => {
+                let day = local.date_naive();
+                (self.midnight(day), self.midnight(day + Duration::days(1)))
+            }
+            BucketSize::Week => {
+                let day = local.date_naive()
+                    - Duration::days(i64::from(local.weekday().num_days_from_monday()));
+                (self.midnight(day), self.midnight(day + Duration::days(7)))
+            }
+        }
+    }
+
+    /// Start of a local day; zones that skip midnight start the day at the first
+    /// existing time
+    fn midnight(&self, day: NaiveDate) -> DateTime<Tz> {
+        let mut time = day.and_hms_opt(0, 0, 0).unwrap_or_default();
+        loop {
+            if let Some(start) = self.timezone.from_local_datetime(&time).earliest() {
+                return start;
+            }
+            time += Duration::minutes(15);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::money::eur;
+
+    fn transactions() -> Vec<Transaction> {
+        serde_json::from_str(
+            r#"[
+                {"id": "t1", "transaction_code": "TX1", "amount": 10.00, "tip_amount": 1.00,
+                 "vat_amount": 1.60, "currency": "EUR", "timestamp": "2024-03-30T22:30:00Z",
+                 "status": "SUCCESSFUL", "payment_type": "POS", "entry_mode": "CONTACTLESS",
+                 "card_type": "VISA"},
+                {"id": "t2", "transaction_code": "TX2", "amount": 20.00, "currency": "EUR",
+                 "timestamp": "2024-03-31T22:30:00Z", "status": "REFUNDED",
+                 "payment_type": "POS", "card": {"last_4_digits": "4242", "type": "MASTERCARD"}},
+                {"id": "t3", "transaction_code": "TX3", "amount": 5.00, "currency": "EUR",
+                 "timestamp": "2024-03-31T10:00:00Z", "status": "SUCCESSFUL",
+                 "payment_type": "ECOM", "card_type": "VISA"},
+                {"id": "t4", "transaction_code": "TX4", "amount": 7.00, "currency": "EUR",
+                 "timestamp": "2024-03-31T11:00:00Z", "status": "FAILED"},
+                {"id": "t5", "transaction_code": "TX5", "amount": 300, "currency": "GBP",
+                 "timestamp": "2024-03-31T12:00:00Z", "status": "SUCCESSFUL"}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_day_buckets_follow_local_time() {
+        let report = SalesAnalytics::new(BucketSize::Day)
+            .timezone(chrono_tz::Europe::Berlin)
+            .aggregate(&transactions())
+            .unwrap();
+        assert_eq!(report.skipped, 1);
+
+        // 22:30 UTC is still the 30th in Berlin (UTC+1) but 22:30 UTC on the 31st is
+        // already the 1st (UTC+2)
+        let days: Vec<_> = report
+            .buckets
+            .iter()
+            .map(|b| {
+                (
+                    b.start.date_naive().to_string(),
+                    b.summary.currency.to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            days,
+            vec![
+                ("2024-03-30".to_string(), "EUR".to_string()),
+                ("2024-03-31".to_string(), "EUR".to_string()),
+                ("2024-03-31".to_string(), "GBP".to_string()),
+                ("2024-04-01".to_string(), "EUR".to_string()),
+            ]
+        );
+
+        // 31 March is the switch to summer time: a 23 hour day
+        let dst_day = &report.buckets[1];
+        assert_eq!(dst_day.end - dst_day.start, Duration::hours(23));
+    }
+
+    #[test]
+    fn test_week_totals_and_breakdowns() {
+        let report = SalesAnalytics::new(BucketSize::Week)
+            .aggregate(&transactions())
+            .unwrap();
+        let totals = report.totals().unwrap();
+        let eur_totals = &totals[0];
+        assert_eq!(eur_totals.currency.as_str(), "EUR");
+        assert_eq!(eur_totals.count, 3);
+        assert_eq!(eur_totals.gross, eur(3500));
+        assert_eq!(eur_totals.refunds, eur(2000));
+        assert_eq!(eur_totals.net().unwrap(), eur(1500));
+        assert_eq!(eur_totals.tips, eur(100));
+        assert_eq!(eur_totals.vat, eur(160));
+        assert_eq!(eur_totals.average_ticket().unwrap(), Some(eur(1167)));
+        assert_eq!(eur_totals.by_payment_type["POS"].count, 2);
+        assert_eq!(eur_totals.by_payment_type["ECOM"].gross, eur(500));
+        assert_eq!(eur_totals.by_card_type["VISA"].count, 2);
+        assert_eq!(eur_totals.by_card_type["MASTERCARD"].gross, eur(2000));
+        assert_eq!(eur_totals.by_entry_mode.len(), 1);
+        assert_eq!(totals[1].gross, Money::new(300, "GBP").unwrap());
+
+        // all sales fall in the week starting Monday 25 March (UTC)
+        assert!(report
+            .buckets
+            .iter()
+            .all(|b| b.start.date_naive() == NaiveDate::from_ymd_opt(2024, 3, 25).unwrap()));
+    }
+
+    #[test]
+    fn test_refund_events_count_in_their_own_bucket() {
+        let transactions: Vec<Transaction> = serde_json::from_str(
+            r#"[
+                {"id": "t1", "transaction_code": "TX1", "amount": 50.00, "currency": "EUR",
+                 "timestamp": "2024-04-02T09:00:00Z", "status": "SUCCESSFUL",
+                 "events": [
+                    {"type": "REFUND", "status": "REFUNDED", "amount": 12.50,
+                     "timestamp": "2024-04-04T15:00:00Z"},
+                    {"type": "REFUND", "status": "FAILED", "amount": 5.00,
+                     "timestamp": "2024-04-04T16:00:00Z"},
+                    {"type": "CHARGE_BACK", "amount": 20.00,
+                     "timestamp": "2024-04-05T08:00:00Z"}
+                 ]}
+            ]"#,
+        )
+        .unwrap();
+        let report = SalesAnalytics::new(BucketSize::Day)
+            .aggregate(&transactions)
+            .unwrap();
+        let days: Vec<_> = report
+            .buckets
+            .iter()
+            .map(|b| {
+                (
+                    b.start.date_naive().to_string(),
+                    b.summary.count,
+                    b.summary.refunds.clone(),
+                    b.summary.chargebacks.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            days,
+            vec![
+                ("2024-04-02".to_string(), 1, eur(0), eur(0)),
+                ("2024-04-04".to_string(), 0, eur(1250), eur(0)),
+                ("2024-04-05".to_string(), 0, eur(0), eur(2000)),
+            ]
+        );
+        let totals = &report.totals().unwrap()[0];
+        assert_eq!(totals.refund_count, 1);
+        assert_eq!(totals.net().unwrap(), eur(1750));
+    }
+
+    #[test]
+    fn test_merchant_timezone() {
+        let merchant: Merchant = serde_json::from_value(serde_json::json!({
+            "merchant_code": "M123",
+            "timezone": "Europe/Berlin"
+        }))
+        .unwrap();
+        let analytics = SalesAnalytics::new(BucketSize::Hour)
+            .merchant_timezone(&merchant)
+            .unwrap();
+        assert_eq!(analytics.timezone, chrono_tz::Europe::Berlin);
+
+        let mut unknown = merchant.clone();
+        unknown.timezone = Some("Mars/Olympus".into());
+        assert!(SalesAnalytics::new(BucketSize::Hour)
+            .merchant_timezone(&unknown)
+            .is_err());
+    }
+}
@@ -0,0 +1,700 @@
+//! Recurring billing on top of saved payment instruments.
+//!
+//! A [`Subscription`] pairs a customer's saved card token with a billing [`Schedule`].
+//! The [`SubscriptionRunner`] finds subscriptions that are due, creates a checkout for
+//! each one and processes it with the saved token. Failed charges are retried according
+//! to a [`DunningPolicy`] before the subscription is marked as unpaid.
+//!
+//! State is persisted through the [`SubscriptionStore`] trait and "today" is read from a
+//! [`Clock`], so the whole flow can be exercised in tests with a [`ManualClock`].
+//!
+//! Enabled with the `billing` cargo feature.
+
+pub use crate::clock::{Clock, ManualClock, SystemClock};
+use crate::{
+    Amount, Checkout, CheckoutId, CheckoutStatus, CreateCheckoutRequest, Currency, CustomerId,
+    Error, MerchantCode, ProcessCheckoutRequest, ProcessCheckoutResponse, Result, SumUpClient,
+};
+use chrono::{Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Unit of a billing interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BillingInterval {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+/// When and how much a subscription is charged.
+///
+/// Billing dates are always computed from the anchor rather than from the previous
+/// billing date, so a subscription anchored on the 31st is charged on the last day of
+/// shorter months and returns to the 31st afterwards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    pub interval: BillingInterval,
+    /// Number of intervals between charges (e.g. 3 with `Month` for quarterly billing)
+    pub interval_count: u32,
+    /// Date the subscription starts
+    pub anchor: NaiveDate,
+    pub amount: Amount,
+    pub currency: Currency,
+    /// Free days before the first charge
+    #[serde(default)]
+    pub trial_days: u32,
+}
+
+impl Schedule {
+    pub fn new(
+        interval: BillingInterval,
+        anchor: NaiveDate,
+        amount: impl Into<Amount>,
+        currency: impl Into<Currency>,
+    ) -> Self {
+        Self {
+            interval,
+            interval_count: 1,
+            anchor,
+            amount: amount.into(),
+            currency: currency.into(),
+            trial_days: 0,
+        }
+    }
+
+    /// Monthly schedule starting at `anchor`
+    pub fn monthly(
+        anchor: NaiveDate,
+        amount: impl Into<Amount>,
+        currency: impl Into<Currency>,
+    ) -> Self {
+        Self::new(BillingInterval::Month, anchor, amount, currency)
+    }
+
+    /// Yearly schedule starting at `anchor`
+    pub fn yearly(
+        anchor: NaiveDate,
+        amount: impl Into<Amount>,
+        currency: impl Into<Currency>,
+    ) -> Self {
+        Self::new(BillingInterval::Year, anchor, amount, currency)
+    }
+
+    pub fn every(mut self, count: u32) -> Self {
+        self.interval_count = count;
+        self
+    }
+
+    pub fn trial_days(mut self, days: u32) -> Self {
+        self.trial_days = days;
+        self
+    }
+
+    /// Check the schedule can actually be billed.
+    pub fn validate(&self) -> Result<()> {
+        if self.interval_count == 0 {
+            return Err(Error::InvalidInput(
+                "interval_count must be at least 1".into(),
+            ));
+        }
+        if !self.amount.is_positive() {
+            return Err(Error::InvalidInput(format!(
+                "subscription amount must be positive, got {}",
+                self.amount
+            )));
+        }
+        Ok(())
+    }
+
+    /// Date of the first charge (the anchor, shifted by the trial period)
+    pub fn first_billing_date(&self) -> NaiveDate {
+        self.anchor + Days::new(u64::from(self.trial_days))
+    }
+
+    /// Date on which billing period `period` (starting at 0) is due.
+    ///
+    /// Returns `None` if the date is out of range.
+    pub fn billing_date(&self, period: u32) -> Option<NaiveDate> {
+        let start = self.first_billing_date();
+        let steps = period.checked_mul(self.interval_count)?;
+        match self.interval {
+            BillingInterval::Day => start.checked_add_days(Days::new(u64::from(steps))),
+            BillingInterval::Week => start.checked_add_days(Days::new(u64::from(steps) * 7)),
+            BillingInterval::Month => start.checked_add_months(Months::new(steps)),
+            BillingInterval::Year => start.checked_add_months(Months::new(steps.checked_mul(12)?)),
+        }
+    }
+}
+
+/// Lifecycle state of a subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionStatus {
+    /// In the free trial, first charge not yet attempted
+    Trialing,
+    /// Last charge succeeded
+    Active,
+    /// Last charge failed and a retry is scheduled
+    PastDue,
+    /// All dunning retries failed; no further charges are attempted
+    Unpaid,
+    /// Cancelled by the merchant
+    Cancelled,
+    /// The schedule has no further billing dates in range
+    Ended,
+}
+
+impl std::fmt::Display for SubscriptionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Trialing => write!(f, "trialing"),
+            Self::Active => write!(f, "active"),
+            Self::PastDue => write!(f, "past_due"),
+            Self::Unpaid => write!(f, "unpaid"),
+            Self::Cancelled => write!(f, "cancelled"),
+            Self::Ended => write!(f, "ended"),
+        }
+    }
+}
+
+/// A recurring charge against a customer's saved payment instrument
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subscription {
+    /// Caller-assigned identifier, used to build checkout references
+    pub id: String,
+    pub merchant_code: MerchantCode,
+    pub customer_id: CustomerId,
+    /// Payment instrument token (see `list_customer_payment_instruments`)
+    pub payment_token: String,
+    pub schedule: Schedule,
+    pub status: SubscriptionStatus,
+    /// Number of billing periods successfully charged so far
+    pub periods_billed: u32,
+    /// Date of the next charge attempt (billing date or dunning retry)
+    pub next_charge_date: NaiveDate,
+    /// Failed attempts for the current billing period
+    pub failed_attempts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_checkout_id: Option<CheckoutId>,
+    /// Checkout of the attempt in progress, saved before it is processed so an
+    /// interrupted run resumes it instead of charging again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_checkout_id: Option<CheckoutId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl Subscription {
+    pub fn new(
+        id: impl Into<String>,
+        merchant_code: impl Into<MerchantCode>,
+        customer_id: impl Into<CustomerId>,
+        payment_token: impl Into<String>,
+        schedule: Schedule,
+    ) -> Result<Self> {
+        schedule.validate()?;
+        let status = if schedule.trial_days > 0 {
+            SubscriptionStatus::Trialing
+        } else {
+            SubscriptionStatus::Active
+        };
+        Ok(Self {
+            id: id.into(),
+            merchant_code: merchant_code.into(),
+            customer_id: customer_id.into(),
+            payment_token: payment_token.into(),
+            next_charge_date: schedule.first_billing_date(),
+            schedule,
+            status,
+            periods_billed: 0,
+            failed_attempts: 0,
+            description: None,
+            last_checkout_id: None,
+            pending_checkout_id: None,
+            last_error: None,
+        })
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Whether the subscription should be charged on `date`
+    pub fn is_due(&self, date: NaiveDate) -> bool {
+        matches!(
+            self.status,
+            SubscriptionStatus::Trialing | SubscriptionStatus::Active | SubscriptionStatus::PastDue
+        ) && self.next_charge_date <= date
+    }
+
+    /// Stop charging this subscription
+    pub fn cancel(&mut self) {
+        self.status = SubscriptionStatus::Cancelled;
+    }
+
+    /// Checkout reference for the current attempt; unique per period and retry
+    fn checkout_reference(&self) -> String {
+        format!(
+            "sub-{}-{}-{}",
+            self.id, self.periods_billed, self.failed_attempts
+        )
+    }
+
+    /// Record a paid period and move on to the next billing date.
+    ///
+    /// The payment is always recorded; a subscription whose next billing date is out
+    /// of range becomes [`SubscriptionStatus::Ended`] instead of failing the run.
+    fn record_success(&mut self, checkout_id: CheckoutId) {
+        let period = self.periods_billed + 1;
+        self.periods_billed = period;
+        self.failed_attempts = 0;
+        self.last_checkout_id = Some(checkout_id);
+        self.pending_checkout_id = None;
+        match self.schedule.billing_date(period) {
+            Some(next) => {
+                self.next_charge_date = next;
+                self.status = SubscriptionStatus::Active;
+                self.last_error = None;
+            }
+            None => {
+                self.status = SubscriptionStatus::Ended;
+                self.last_error = Some(format!(
+                    "billing date for period {} is out of range",
+                    period
+                ));
+            }
+        }
+    }
+
+    fn record_failure(
+        &mut self,
+        today: NaiveDate,
+        policy: &DunningPolicy,
+        reason: String,
+    ) -> Option<NaiveDate> {
+        self.failed_attempts += 1;
+        self.last_error = Some(reason);
+        match policy.retry_delay(self.failed_attempts) {
+            Some(delay) => {
+                self.status = SubscriptionStatus::PastDue;
+                self.next_charge_date = today + Days::new(u64::from(delay));
+                Some(self.next_charge_date)
+            }
+            None => {
+                self.status = SubscriptionStatus::Unpaid;
+                None
+            }
+        }
+    }
+}
+
+/// Retry schedule for failed charges.
+///
+/// Each entry is the number of days to wait after the n-th consecutive failure.
+/// Once the list is exhausted the subscription becomes [`SubscriptionStatus::Unpaid`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DunningPolicy {
+    pub retry_after_days: Vec<u32>,
+}
+
+impl DunningPolicy {
+    pub fn new(retry_after_days: Vec<u32>) -> Self {
+        Self { retry_after_days }
+    }
+
+    /// Never retry; the first failure marks the subscription unpaid
+    pub fn none() -> Self {
+        Self::new(Vec::new())
+    }
+
+    /// Days to wait after the given (1-based) failed attempt, if another retry is allowed
+    pub fn retry_delay(&self, failed_attempts: u32) -> Option<u32> {
+        let index = usize::try_from(failed_attempts.checked_sub(1)?).ok()?;
+        self.retry_after_days.get(index).copied()
+    }
+}
+
+impl Default for DunningPolicy {
+    /// Retry after 1, 3 and 7 days
+    fn default() -> Self {
+        Self::new(vec![1, 3, 7])
+    }
+}
+
+/// Persistence for subscriptions.
+///
+/// Implementations should map their own failures to [`Error::Storage`].
+pub trait SubscriptionStore {
+    /// Subscriptions that should be charged on `date` (see [`Subscription::is_due`])
+    fn due(&self, date: NaiveDate) -> Result<Vec<Subscription>>;
+
+    fn get(&self, id: &str) -> Result<Option<Subscription>>;
+
+    /// Insert or replace a subscription
+    fn save(&self, subscription: &Subscription) -> Result<()>;
+}
+
+impl<S: SubscriptionStore + ?Sized> SubscriptionStore for &S {
+    fn due(&self, date: NaiveDate) -> Result<Vec<Subscription>> {
+        (**self).due(date)
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Subscription>> {
+        (**self).get(id)
+    }
+
+    fn save(&self, subscription: &Subscription) -> Result<()> {
+        (**self).save(subscription)
+    }
+}
+
+/// In-process store, useful for tests and single-instance deployments
+#[derive(Debug, Default)]
+pub struct InMemoryStore {
+    subscriptions: Mutex<HashMap<String, Subscription>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All stored subscriptions, in no particular order
+    pub fn all(&self) -> Vec<Subscription> {
+        self.lock().values().cloned().collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Subscription>> {
+        self.subscriptions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl SubscriptionStore for InMemoryStore {
+    fn due(&self, date: NaiveDate) -> Result<Vec<Subscription>> {
+        let mut due: Vec<Subscription> = self
+            .lock()
+            .values()
+            .filter(|s| s.is_due(date))
+            .cloned()
+            .collect();
+        due.sort_by(|a, b| {
+            a.next_charge_date
+                .cmp(&b.next_charge_date)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(due)
+    }
+
+    fn get(&self, id: &str) -> Result<Option<Subscription>> {
+        Ok(self.lock().get(id).cloned())
+    }
+
+    fn save(&self, subscription: &Subscription) -> Result<()> {
+        self.lock()
+            .insert(subscription.id.clone(), subscription.clone());
+        Ok(())
+    }
+}
+
+/// Result of a single charge attempt
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChargeOutcome {
+    /// The checkout was paid and the subscription moved to the next period
+    Paid { checkout_id: CheckoutId },
+    /// The charge failed; `retry_on` is `None` once dunning is exhausted
+    Failed {
+        checkout_id: Option<CheckoutId>,
+        reason: String,
+        retry_on: Option<NaiveDate>,
+    },
+}
+
+/// Charge attempt for one subscription during a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChargeResult {
+    pub subscription_id: String,
+    pub amount: Amount,
+    pub currency: Currency,
+    pub outcome: ChargeOutcome,
+}
+
+/// Summary of a [`SubscriptionRunner::run_due`] pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunReport {
+    pub date: NaiveDate,
+    pub results: Vec<ChargeResult>,
+}
+
+impl RunReport {
+    pub fn paid(&self) -> impl Iterator<Item = &ChargeResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, ChargeOutcome::Paid { .. }))
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &ChargeResult> {
+        self.results
+            .iter()
+            .filter(|r| matches!(r.outcome, ChargeOutcome::Failed { .. }))
+    }
+}
+
+/// Charges due subscriptions with their saved payment tokens.
+///
+/// The checkout of each attempt is saved to the store before it is processed. If a run
+/// is interrupted after that, the next run retrieves the checkout and resumes it (or
+/// records its result) rather than creating a second one for the same period.
+///
+/// Each due subscription is charged at most once per [`run_due`](Self::run_due) call;
+/// a subscription that is several periods behind catches up over successive runs.
+pub struct SubscriptionRunner<'a, S, C = SystemClock> {
+    client: &'a SumUpClient,
+    store: S,
+    clock: C,
+    dunning: DunningPolicy,
+}
+
+impl<'a, S: SubscriptionStore> SubscriptionRunner<'a, S, SystemClock> {
+    /// Create a runner using the system clock
+    pub fn new(client: &'a SumUpClient, store: S) -> Self {
+        Self::with_clock(client, store, SystemClock)
+    }
+}
+
+impl<'a, S: SubscriptionStore, C: Clock> SubscriptionRunner<'a, S, C> {
+    pub fn with_clock(client: &'a SumUpClient, store: S, clock: C) -> Self {
+        Self {
+            client,
+            store,
+            clock,
+            dunning: DunningPolicy::default(),
+        }
+    }
+
+    pub fn dunning(mut self, policy: DunningPolicy) -> Self {
+        self.dunning = policy;
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Charge every subscription that is due today.
+    ///
+    /// Declined payments and API errors are recorded on the subscription and reported,
+    /// not returned. Transport, rate-limit, authentication and storage errors abort the
+    /// run; subscriptions charged before the error have already been saved.
+    pub async fn run_due(&self) -> Result<RunReport> {
+        let today = self.clock.today();
+        let mut report = RunReport {
+            date: today,
+            results: Vec::new(),
+        };
+
+        for mut subscription in self.store.due(today)? {
+            let outcome = self.charge(&mut subscription, today).await?;
+            self.store.save(&subscription)?;
+            report.results.push(ChargeResult {
+                subscription_id: subscription.id.clone(),
+                amount: subscription.schedule.amount,
+                currency: subscription.schedule.currency.clone(),
+                outcome,
+            });
+        }
+
+        Ok(report)
+    }
+
+    async fn charge(
+        &self,
+        subscription: &mut Subscription,
+        today: NaiveDate,
+    ) -> Result<ChargeOutcome> {
+        let checkout = match subscription.pending_checkout_id.clone() {
+            // An earlier run stopped after creating this checkout: check where it got to
+            Some(id) => match self.client.retrieve_checkout(&id).await {
+                Ok(checkout) => checkout,
+                Err(e @ Error::ApiError { .. }) => {
+                    return Ok(self.fail(subscription, today, Some(id), e.to_string()));
+                }
+                Err(e) => return Err(e),
+            },
+            None => match self.create_checkout(subscription).await {
+                Ok(checkout) => {
+                    subscription.pending_checkout_id = Some(checkout.id.clone());
+                    self.store.save(subscription)?;
+                    checkout
+                }
+                Err(e @ Error::ApiError { .. }) => {
+                    return Ok(self.fail(subscription, today, None, e.to_string()));
+                }
+                Err(e) => return Err(e),
+            },
+        };
+
+        match checkout.status {
+            CheckoutStatus::Paid => {
+                subscription.record_success(checkout.id.clone());
+                return Ok(ChargeOutcome::Paid {
+                    checkout_id: checkout.id,
+                });
+            }
+            CheckoutStatus::Pending => {}
+            status => {
+                let reason = format!("checkout finished with status {}", status);
+                return Ok(self.fail(subscription, today, Some(checkout.id), reason));
+            }
+        }
+
+        let process = ProcessCheckoutRequest::token(subscription.payment_token.clone())
+            .customer_id(subscription.customer_id.clone());
+        let outcome = match self.client.process_checkout(&checkout.id, &process).await {
+            Ok(ProcessCheckoutResponse::Success(processed))
+                if processed.status == CheckoutStatus::Paid =>
+            {
+                subscription.record_success(checkout.id.clone());
+                ChargeOutcome::Paid {
+                    checkout_id: checkout.id,
+                }
+            }
+            Ok(ProcessCheckoutResponse::Success(processed)) => {
+                let reason = format!("checkout finished with status {}", processed.status);
+                self.fail(subscription, today, Some(checkout.id), reason)
+            }
+            Ok(ProcessCheckoutResponse::Accepted(_)) => {
+                let reason = "payment requires customer authentication".to_string();
+                self.fail(subscription, today, Some(checkout.id), reason)
+            }
+            Err(e @ Error::ApiError { .. }) => {
+                self.fail(subscription, today, Some(checkout.id), e.to_string())
+            }
+            Err(e) => return Err(e),
+        };
+        Ok(outcome)
+    }
+
+    /// Create the checkout for the current attempt.
+    ///
+    /// If the API rejects the reference as a duplicate, a checkout was created by a run
+    /// that failed before saving it; that checkout is looked up and returned instead.
+    async fn create_checkout(&self, subscription: &Subscription) -> Result<Checkout> {
+        let reference = subscription.checkout_reference();
+        let mut request = CreateCheckoutRequest::new(
+            reference.clone(),
+            subscription.schedule.amount,
+            &subscription.schedule.currency,
+            &subscription.merchant_code,
+        )
+        .customer_id(subscription.customer_id.clone());
+        if let Some(ref description) = subscription.description {
+            request = request.description(description.clone());
+        }
+
+        match self.client.create_checkout(&request).await {
+            Err(Error::ApiError { status: 409, body }) => {
+                let existing = self.client.list_checkouts(Some(&reference)).await?;
+                existing
+                    .into_iter()
+                    .find(|c| c.checkout_reference.as_deref() == Some(reference.as_str()))
+                    .ok_or(Error::ApiError { status: 409, body })
+            }
+            result => result,
+        }
+    }
+
+    fn fail(
+        &self,
+        subscription: &mut Subscription,
+        today: NaiveDate,
+        checkout_id: Option<CheckoutId>,
+        reason: String,
+    ) -> ChargeOutcome {
+        if let Some(ref id) = checkout_id {
+            subscription.last_checkout_id = Some(id.clone());
+        }
+        subscription.pending_checkout_id = None;
+        let retry_on = subscription.record_failure(today, &self.dunning, reason.clone());
+        ChargeOutcome::Failed {
+            checkout_id,
+            reason,
+            retry_on,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_monthly_billing_dates_clamp_to_month_end() {
+        let schedule = Schedule::monthly(date(2024, 1, 31), Amount::from_cents(999), "EUR");
+        assert_eq!(schedule.billing_date(0), Some(date(2024, 1, 31)));
+        assert_eq!(schedule.billing_date(1), Some(date(2024, 2, 29)));
+        assert_eq!(schedule.billing_date(2), Some(date(2024, 3, 31)));
+        assert_eq!(schedule.billing_date(3), Some(date(2024, 4, 30)));
+    }
+
+    #[test]
+    fn test_trial_shifts_billing_dates() {
+        let schedule = Schedule::new(
+            BillingInterval::Week,
+            date(2024, 1, 1),
+            Amount::from_cents(500),
+            "EUR",
+        )
+        .every(2)
+        .trial_days(14);
+        assert_eq!(schedule.first_billing_date(), date(2024, 1, 15));
+        assert_eq!(schedule.billing_date(1), Some(date(2024, 1, 29)));
+    }
+
+    #[test]
+    fn test_dunning_policy_delays() {
+        let policy = DunningPolicy::default();
+        assert_eq!(policy.retry_delay(1), Some(1));
+        assert_eq!(policy.retry_delay(3), Some(7));
+        assert_eq!(policy.retry_delay(4), None);
+        assert_eq!(DunningPolicy::none().retry_delay(1), None);
+    }
+
+    #[test]
+    fn test_invalid_schedule_is_rejected() {
+        let schedule = Schedule::monthly(date(2024, 1, 1), Amount::from_cents(0), "EUR");
+        assert!(Subscription::new("s1", "M123", "cust", "tok", schedule).is_err());
+    }
+
+    #[test]
+    fn test_success_past_last_billing_date_ends_subscription() {
+        let schedule = Schedule::new(
+            BillingInterval::Day,
+            NaiveDate::MAX,
+            Amount::from_cents(500),
+            "EUR",
+        );
+        let mut subscription = Subscription::new("s1", "M123", "cust", "tok", schedule).unwrap();
+        subscription.pending_checkout_id = Some("chk_1".into());
+        subscription.record_success("chk_1".into());
+        assert_eq!(subscription.status, SubscriptionStatus::Ended);
+        assert_eq!(subscription.periods_billed, 1);
+        assert_eq!(subscription.last_checkout_id, Some("chk_1".into()));
+        assert_eq!(subscription.pending_checkout_id, None);
+        assert!(!subscription.is_due(NaiveDate::MAX));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
-    Amount, AvailablePaymentMethodsResponse, Checkout, CheckoutListQuery, CreateCheckoutRequest,
-    Currency, DeletedCheckout, ProcessCheckoutRequest, ProcessCheckoutResponse, Result, SumUpClient,
+    Amount, ApplePaySessionRequest, AvailablePaymentMethodsResponse, Checkout, CheckoutId, CheckoutListQuery, CreateCheckoutRequest,
+    Currency, DeletedCheckout, Error, MerchantCode, PaymentType, ProcessCheckoutRequest, ProcessCheckoutResponse,
+    Result, SumUpClient,
 };
 
 impl SumUpClient {
@@ -36,26 +37,29 @@ impl SumUpClient {
     }
 
     /// Retrieves a checkout by ID.
-    pub async fn retrieve_checkout(&self, checkout_id: impl AsRef<str>) -> Result<Checkout> {
-        let url = self.build_url(&format!("/v0.1/checkouts/{}", checkout_id.as_ref()))?;
+    pub async fn retrieve_checkout(&self, checkout_id: impl Into<CheckoutId>) -> Result<Checkout> {
+        let checkout_id: CheckoutId = checkout_id.into();
+        let url = self.build_url(&format!("/v0.1/checkouts/{}", checkout_id.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
 
     /// Processes a checkout (charges the payment instrument).
     /// Returns Success for immediate completion, or Accepted for 3DS redirect.
-    pub async fn process_checkout(&self, checkout_id: impl AsRef<str>, body: &ProcessCheckoutRequest) -> Result<ProcessCheckoutResponse> {
-        let url = self.build_url(&format!("/v0.1/checkouts/{}", checkout_id.as_ref()))?;
+    pub async fn process_checkout(&self, checkout_id: impl Into<CheckoutId>, body: &ProcessCheckoutRequest) -> Result<ProcessCheckoutResponse> {
+        let checkout_id: CheckoutId = checkout_id.into();
+        let url = self.build_url(&format!("/v0.1/checkouts/{}", checkout_id.as_str()))?;
         let response = self.http_client.put(url).bearer_auth(self.api_key_str()).json(body).send().await?;
 
         let status = response.status().as_u16();
         match status {
             200 | 202 => {
+                let path = response.url().path().to_string();
                 let text = response.text().await.unwrap_or_default();
                 if text.contains("next_step") {
-                    Ok(ProcessCheckoutResponse::Accepted(serde_json::from_str(&text)?))
+                    Ok(ProcessCheckoutResponse::Accepted(self.decode(text.as_bytes(), &path)?))
                 } else {
-                    Ok(ProcessCheckoutResponse::Success(Box::new(serde_json::from_str(&text)?)))
+                    Ok(ProcessCheckoutResponse::Success(Box::new(self.decode(text.as_bytes(), &path)?)))
                 }
             }
             _ => self.handle_error(response).await,
@@ -63,15 +67,17 @@ impl SumUpClient {
     }
 
     /// Deactivates a checkout.
-    pub async fn deactivate_checkout(&self, checkout_id: impl AsRef<str>) -> Result<DeletedCheckout> {
-        let url = self.build_url(&format!("/v0.1/checkouts/{}", checkout_id.as_ref()))?;
+    pub async fn deactivate_checkout(&self, checkout_id: impl Into<CheckoutId>) -> Result<DeletedCheckout> {
+        let checkout_id: CheckoutId = checkout_id.into();
+        let url = self.build_url(&format!("/v0.1/checkouts/{}", checkout_id.as_str()))?;
         let response = self.http_client.delete(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
 
     /// Gets available payment methods for a merchant.
-    pub async fn get_available_payment_methods(&self, merchant_code: impl AsRef<str>, amount: Option<Amount>, currency: Option<&Currency>) -> Result<AvailablePaymentMethodsResponse> {
-        let mut url = self.build_url(&format!("/v0.1/merchants/{}/payment-methods", merchant_code.as_ref()))?;
+    pub async fn get_available_payment_methods(&self, merchant_code: impl Into<MerchantCode>, amount: Option<Amount>, currency: Option<&Currency>) -> Result<AvailablePaymentMethodsResponse> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let mut url = self.build_url(&format!("/v0.1/merchants/{}/payment-methods", merchant_code.as_str()))?;
         {
             let mut pairs = url.query_pairs_mut();
             if let Some(v) = amount { pairs.append_pair("amount", &v.to_string()); }
@@ -80,6 +86,30 @@ impl SumUpClient {
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
+
+    /// Checks that a payment method is offered for this merchant, amount and currency.
+    /// Returns `Error::InvalidInput` if it is not.
+    pub async fn ensure_payment_method_available(&self, merchant_code: impl Into<MerchantCode>, payment_type: PaymentType, amount: Option<Amount>, currency: Option<&Currency>) -> Result<()> {
+        self.get_available_payment_methods(merchant_code, amount, currency).await?.ensure_supported(payment_type)
+    }
+
+    /// Validates a process request locally and against the merchant's available payment
+    /// methods for the checkout's amount and currency, then processes it.
+    pub async fn process_checkout_checked(&self, checkout: &Checkout, body: &ProcessCheckoutRequest) -> Result<ProcessCheckoutResponse> {
+        body.validate()?;
+        let merchant_code = checkout.merchant_code.as_ref().ok_or_else(|| Error::InvalidInput("checkout has no merchant_code".into()))?;
+        self.ensure_payment_method_available(merchant_code, body.payment_type.clone(), Some(checkout.amount), Some(&checkout.currency)).await?;
+        self.process_checkout(&checkout.id, body).await
+    }
+
+    /// Creates an Apple Pay merchant session for a checkout.
+    /// Pass the returned object to `ApplePaySession.completeMerchantValidation()`.
+    pub async fn create_apple_pay_session(&self, checkout_id: impl Into<CheckoutId>, body: &ApplePaySessionRequest) -> Result<serde_json::Value> {
+        let checkout_id: CheckoutId = checkout_id.into();
+        let url = self.build_url(&format!("/v0.2/checkouts/{}/apple-pay-session", checkout_id.as_str()))?;
+        let response = self.http_client.put(url).bearer_auth(self.api_key_str()).json(body).send().await?;
+        self.handle_response(response).await
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,70 @@
+//! Time sources for the schedulers and trackers.
+//!
+//! Components that act on dates read "now" from a [`Clock`], so tests can drive them with
+//! a [`ManualClock`] instead of waiting for real time to pass.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use std::sync::Mutex;
+
+/// Source of the current time
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+
+    fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+}
+
+/// Clock backed by the system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Clock that only moves when told to, for tests and backfills
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Start at midnight UTC on the given date
+    pub fn at_date(date: NaiveDate) -> Self {
+        Self::new(date.and_time(chrono::NaiveTime::MIN).and_utc())
+    }
+
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    pub fn advance(&self, by: chrono::Duration) {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        *now += by;
+    }
+
+    pub fn advance_days(&self, days: i64) {
+        self.advance(chrono::Duration::days(days));
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
@@ -0,0 +1,657 @@
+//! CSV import of payout reports and CSV export of payouts, transaction history and
+//! ledger journals.
+//!
+//! Amounts are read and written as exact decimals (never through `f64`), so a value such
+//! as `0.10` survives a round trip through a spreadsheet export unchanged.
+//!
+//! Free-text cells (descriptions, references, unmodelled payout fields and the text
+//! fields of transactions) that start with `=`, `+`, `-` or `@` are written with a
+//! leading `'`, so a spreadsheet shows them as text instead of running them as formulas.
+//! [`read_payouts_csv`] removes the prefix again.
+
+use crate::models::payout::parse_payout_date;
+use crate::{
+    Amount, Currency, Journal, MerchantCode, Payout, PayoutId, PayoutStatus, PayoutType, Result,
+    SumUpClient, Transaction, UnknownFields,
+};
+use rust_decimal::Decimal;
+use std::collections::BTreeSet;
+use std::io::{Read, Write};
+
+/// Errors reading or writing CSV
+#[derive(thiserror::Error, Debug)]
+pub enum CsvError {
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("CSV is missing the required column '{0}'")]
+    MissingColumn(&'static str),
+
+    #[error("CSV row {row}, column '{column}': invalid value '{value}'")]
+    InvalidField {
+        /// 1-based data row, not counting the header
+        row: u64,
+        column: &'static str,
+        value: String,
+    },
+}
+
+/// Columns written for each payout, and read back by [`PayoutCsvParser`]
+///
+/// [`write_payouts_csv`] follows them with one column per [`Payout::extra`] key.
+pub const PAYOUT_CSV_HEADERS: [&str; 11] = [
+    "id",
+    "date",
+    "type",
+    "status",
+    "amount",
+    "fee",
+    "currency",
+    "transaction_code",
+    "reference",
+    "description",
+    "merchant_code",
+];
+
+/// Columns written for each transaction
+pub const TRANSACTION_CSV_HEADERS: [&str; 14] = [
+    "id",
+    "transaction_code",
+    "timestamp",
+    "status",
+    "payment_type",
+    "amount",
+    "tip_amount",
+    "vat_amount",
+    "currency",
+    "merchant_code",
+    "entry_mode",
+    "auth_code",
+    "installments_count",
+    "internal_id",
+];
+
+/// Columns written for each journal line, one row per debit or credit
+pub const JOURNAL_CSV_HEADERS: [&str; 8] = [
+    "entry_id",
+    "date",
+    "account",
+    "debit",
+    "credit",
+    "currency",
+    "description",
+    "reference",
+];
+
+/// Position of each known payout column in a CSV header
+#[derive(Debug, Clone)]
+struct PayoutColumns {
+    id: Option<usize>,
+    date: usize,
+    payout_type: usize,
+    status: usize,
+    amount: usize,
+    fee: Option<usize>,
+    currency: usize,
+    transaction_code: Option<usize>,
+    reference: Option<usize>,
+    description: Option<usize>,
+    merchant_code: Option<usize>,
+    /// Unrecognised columns, kept in [`Payout::extra`]
+    others: Vec<(usize, String)>,
+}
+
+/// Lower-case a header and map known aliases to the JSON field names
+fn normalize_header(header: &str) -> String {
+    let header = header
+        .trim_start_matches('\u{feff}')
+        .trim()
+        .to_ascii_lowercase()
+        .replace([' ', '-'], "_");
+    match header.as_str() {
+        "payout_id" => "id".into(),
+        "payout_date" => "date".into(),
+        "payout_type" => "type".into(),
+        "payout_status" => "status".into(),
+        "fee_amount" => "fee".into(),
+        "payout_reference" => "reference".into(),
+        _ => header,
+    }
+}
+
+impl PayoutColumns {
+    fn from_headers(headers: &csv::StringRecord) -> std::result::Result<Self, CsvError> {
+        let names: Vec<String> = headers.iter().map(normalize_header).collect();
+        let find = |name: &str| names.iter().position(|n| n == name);
+        let require = |name: &'static str| find(name).ok_or(CsvError::MissingColumn(name));
+
+        let mut columns = Self {
+            id: find("id"),
+            date: require("date")?,
+            payout_type: require("type")?,
+            status: require("status")?,
+            amount: require("amount")?,
+            fee: find("fee"),
+            currency: require("currency")?,
+            transaction_code: find("transaction_code"),
+            reference: find("reference"),
+            description: find("description"),
+            merchant_code: find("merchant_code"),
+            others: Vec::new(),
+        };
+        let known = [
+            columns.id,
+            Some(columns.date),
+            Some(columns.payout_type),
+            Some(columns.status),
+            Some(columns.amount),
+            columns.fee,
+            Some(columns.currency),
+            columns.transaction_code,
+            columns.reference,
+            columns.description,
+            columns.merchant_code,
+        ];
+        columns.others = names
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| !known.contains(&Some(*i)))
+            .collect();
+        Ok(columns)
+    }
+
+    fn payout(
+        &self,
+        record: &csv::StringRecord,
+        row: u64,
+    ) -> std::result::Result<Payout, CsvError> {
+        let field = |index: usize| record.get(index).unwrap_or("").trim();
+        let optional = |index: Option<usize>| {
+            index
+                .map(field)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let invalid = |column: &'static str, value: &str| CsvError::InvalidField {
+            row,
+            column,
+            value: value.to_string(),
+        };
+        let decimal = |column: &'static str, index: usize| {
+            let value = field(index);
+            value
+                .parse::<Decimal>()
+                .map(Amount)
+                .map_err(|_| invalid(column, value))
+        };
+        let required = |column: &'static str, index: usize| {
+            let value = field(index);
+            if value.is_empty() {
+                Err(invalid(column, value))
+            } else {
+                Ok(value)
+            }
+        };
+
+        let date = field(self.date);
+        let date = parse_payout_date(date).ok_or_else(|| invalid("date", date))?;
+        let fee = match self.fee.map(field) {
+            Some(value) if !value.is_empty() => decimal("fee", self.fee.unwrap_or_default())?,
+            _ => Amount::default(),
+        };
+        let extra: serde_json::Map<String, serde_json::Value> = self
+            .others
+            .iter()
+            .map(|(index, name)| (name.clone(), unescape_formula(field(*index)).into()))
+            .collect();
+
+        Ok(Payout {
+            id: optional(self.id).map(PayoutId),
+            amount: decimal("amount", self.amount)?,
+            fee,
+            currency: Currency::new(required("currency", self.currency)?),
+            date,
+            status: required("status", self.status)?
+                .parse::<PayoutStatus>()
+                .unwrap_or_else(|e| match e {}),
+            payout_type: required("type", self.payout_type)?
+                .parse::<PayoutType>()
+                .unwrap_or_else(|e| match e {}),
+            transaction_code: optional(self.transaction_code),
+            reference: optional(self.reference).map(|v| unescape_formula(&v).to_string()),
+            description: optional(self.description).map(|v| unescape_formula(&v).to_string()),
+            merchant_code: optional(self.merchant_code).map(MerchantCode),
+            bank_account: None,
+            extra: UnknownFields::from(extra),
+        })
+    }
+}
+
+/// Incremental parser for a payout CSV report arriving in chunks.
+///
+/// Columns are matched by header name (case-insensitive, in any order); unrecognised
+/// columns end up in [`Payout::extra`]. Only complete rows are parsed, so memory use is
+/// bounded by the chunk size rather than the size of the report.
+#[derive(Debug, Default)]
+pub struct PayoutCsvParser {
+    columns: Option<PayoutColumns>,
+    buffer: Vec<u8>,
+    /// How much of `buffer` has been scanned for row boundaries
+    scanned: usize,
+    in_quotes: bool,
+    rows: u64,
+}
+
+impl PayoutCsvParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a chunk of the report and return the payouts of the rows it completed
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<Payout>> {
+        self.buffer.extend_from_slice(chunk);
+        let mut complete = None;
+        for (offset, byte) in self.buffer[self.scanned..].iter().enumerate() {
+            match byte {
+                b'"' => self.in_quotes = !self.in_quotes,
+                b'\n' if !self.in_quotes => complete = Some(self.scanned + offset + 1),
+                _ => {}
+            }
+        }
+        self.scanned = self.buffer.len();
+        match complete {
+            Some(end) => {
+                let rows: Vec<u8> = self.buffer.drain(..end).collect();
+                self.scanned -= end;
+                self.parse_rows(&rows)
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Parse whatever is left once the report has been fully received
+    pub fn finish(mut self) -> Result<Vec<Payout>> {
+        let rest = std::mem::take(&mut self.buffer);
+        self.parse_rows(&rest)
+    }
+
+    fn parse_rows(&mut self, rows: &[u8]) -> Result<Vec<Payout>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(rows);
+        let mut payouts = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(CsvError::from)?;
+            if record.iter().all(|field| field.trim().is_empty()) {
+                continue;
+            }
+            match &self.columns {
+                None => self.columns = Some(PayoutColumns::from_headers(&record)?),
+                Some(columns) => {
+                    self.rows += 1;
+                    payouts.push(columns.payout(&record, self.rows)?);
+                }
+            }
+        }
+        Ok(payouts)
+    }
+}
+
+/// Read a payout CSV report from any reader (e.g. a downloaded file)
+pub fn read_payouts_csv(mut reader: impl Read) -> Result<Vec<Payout>> {
+    let mut parser = PayoutCsvParser::new();
+    let mut payouts = Vec::new();
+    let mut chunk = [0u8; 8192];
+    loop {
+        let read = reader
+            .read(&mut chunk)
+            .map_err(|e| CsvError::from(csv::Error::from(e)))?;
+        if read == 0 {
+            break;
+        }
+        payouts.extend(parser.push(&chunk[..read])?);
+    }
+    payouts.extend(parser.finish()?);
+    Ok(payouts)
+}
+
+/// Characters that make a spreadsheet read a cell as a formula
+const FORMULA_PREFIXES: [char; 4] = ['=', '+', '-', '@'];
+
+/// Prefix a free-text cell that a spreadsheet would run as a formula with `'`, which
+/// spreadsheets show as text
+fn escape_formula(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.starts_with(FORMULA_PREFIXES) {
+        format!("'{}", value).into()
+    } else {
+        value.into()
+    }
+}
+
+/// Undo [`escape_formula`]
+fn unescape_formula(value: &str) -> &str {
+    match value.strip_prefix('\'') {
+        Some(rest) if rest.starts_with(FORMULA_PREFIXES) => rest,
+        _ => value,
+    }
+}
+
+fn optional_amount(amount: Option<Amount>) -> String {
+    amount.map(|a| a.0.to_string()).unwrap_or_default()
+}
+
+fn extra_field(value: Option<&serde_json::Value>) -> String {
+    match value {
+        None | Some(serde_json::Value::Null) => String::new(),
+        Some(serde_json::Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Write payouts as CSV with [`PAYOUT_CSV_HEADERS`], readable by [`read_payouts_csv`]
+///
+/// Every key found in any payout's [`Payout::extra`] gets its own column after the
+/// fixed ones, so unmodelled fields survive a round trip; non-string values are written
+/// as JSON text and read back as strings. [`Payout::bank_account`] is not written.
+pub fn write_payouts_csv<'a>(
+    writer: impl Write,
+    payouts: impl IntoIterator<Item = &'a Payout>,
+) -> Result<()> {
+    let payouts: Vec<&Payout> = payouts.into_iter().collect();
+    let extra_columns: BTreeSet<&str> = payouts
+        .iter()
+        .flat_map(|payout| payout.extra.iter().map(|(name, _)| name.as_str()))
+        .filter(|name| !PAYOUT_CSV_HEADERS.contains(name))
+        .collect();
+
+    let mut writer = csv::Writer::from_writer(writer);
+    writer
+        .write_record(PAYOUT_CSV_HEADERS.iter().chain(&extra_columns))
+        .map_err(CsvError::from)?;
+    for payout in payouts {
+        let reference = escape_formula(payout.reference.as_deref().unwrap_or(""));
+        let description = escape_formula(payout.description.as_deref().unwrap_or(""));
+        let fixed = [
+            payout.id.as_ref().map(|id| id.as_str()).unwrap_or(""),
+            &payout.date.to_string(),
+            payout.payout_type.as_str(),
+            payout.status.as_str(),
+            &payout.amount.0.to_string(),
+            &payout.fee.0.to_string(),
+            payout.currency.as_str(),
+            payout.transaction_code.as_deref().unwrap_or(""),
+            &reference,
+            &description,
+            payout
+                .merchant_code
+                .as_ref()
+                .map(|m| m.as_str())
+                .unwrap_or(""),
+        ];
+        let extra: Vec<String> = extra_columns
+            .iter()
+            .map(|name| escape_formula(&extra_field(payout.extra.get(name))).into_owned())
+            .collect();
+        writer
+            .write_record(
+                fixed
+                    .iter()
+                    .copied()
+                    .chain(extra.iter().map(String::as_str)),
+            )
+            .map_err(CsvError::from)?;
+    }
+    writer
+        .flush()
+        .map_err(|e| CsvError::from(csv::Error::from(e)))?;
+    Ok(())
+}
+
+/// Write transaction history as CSV with [`TRANSACTION_CSV_HEADERS`]
+pub fn write_transactions_csv<'a>(
+    writer: impl Write,
+    transactions: impl IntoIterator<Item = &'a Transaction>,
+) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer
+        .write_record(TRANSACTION_CSV_HEADERS)
+        .map_err(CsvError::from)?;
+    for transaction in transactions {
+        writer
+            .write_record([
+                transaction.id.as_str(),
+                &transaction.transaction_code,
+                &transaction.timestamp.to_rfc3339(),
+                &transaction
+                    .status
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+                &escape_formula(transaction.payment_type.as_deref().unwrap_or("")),
+                &transaction.amount.0.to_string(),
+                &optional_amount(transaction.tip_amount),
+                &optional_amount(transaction.vat_amount),
+                transaction.currency.as_str(),
+                transaction
+                    .merchant_code
+                    .as_ref()
+                    .map(|m| m.as_str())
+                    .unwrap_or(""),
+                &escape_formula(transaction.entry_mode.as_deref().unwrap_or("")),
+                &escape_formula(transaction.auth_code.as_deref().unwrap_or("")),
+                &transaction
+                    .installments_count
+                    .map(|n| n.to_string())
+                    .unwrap_or_default(),
+                &escape_formula(transaction.internal_id.as_deref().unwrap_or("")),
+            ])
+            .map_err(CsvError::from)?;
+    }
+    writer
+        .flush()
+        .map_err(|e| CsvError::from(csv::Error::from(e)))?;
+    Ok(())
+}
+
+/// Write a ledger journal as CSV with [`JOURNAL_CSV_HEADERS`].
+///
+/// Each line of an entry becomes a row; the side without a value is left empty, which
+/// is what most bookkeeping imports expect.
+pub fn write_journal_csv(writer: impl Write, journal: &Journal) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer
+        .write_record(JOURNAL_CSV_HEADERS)
+        .map_err(CsvError::from)?;
+    let side = |amount: Amount| {
+        if amount.is_zero() {
+            String::new()
+        } else {
+            amount.0.to_string()
+        }
+    };
+    for entry in &journal.entries {
+        for line in &entry.lines {
+            writer
+                .write_record([
+                    entry.id.as_str(),
+                    &entry.date.to_string(),
+                    &line.account,
+                    &side(line.debit),
+                    &side(line.credit),
+                    entry.currency.as_str(),
+                    &escape_formula(&entry.description),
+                    &escape_formula(entry.reference.as_deref().unwrap_or("")),
+                ])
+                .map_err(CsvError::from)?;
+        }
+    }
+    writer
+        .flush()
+        .map_err(|e| CsvError::from(csv::Error::from(e)))?;
+    Ok(())
+}
+
+impl SumUpClient {
+    /// Streams a merchant's payouts as a CSV report, calling `on_payout` for each row as
+    /// it arrives.
+    pub async fn for_each_merchant_payout_csv(
+        &self,
+        merchant_code: impl Into<MerchantCode>,
+        query: &crate::payouts::PayoutListQuery,
+        mut on_payout: impl FnMut(Payout) -> Result<()>,
+    ) -> Result<()> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        query.validate()?;
+        let query = query.clone().format(crate::payouts::PayoutFormat::Csv);
+        let url = self.build_url(&format!(
+            "/v1.0/merchants/{}/payouts",
+            merchant_code.as_str()
+        ))?;
+        let mut response = self
+            .http_client
+            .get(url)
+            .bearer_auth(self.api_key_str())
+            .query(&query)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return self.handle_error(response).await;
+        }
+
+        let mut parser = PayoutCsvParser::new();
+        while let Some(chunk) = response.chunk().await? {
+            parser
+                .push(&chunk)?
+                .into_iter()
+                .try_for_each(&mut on_payout)?;
+        }
+        parser.finish()?.into_iter().try_for_each(&mut on_payout)
+    }
+
+    /// Lists a merchant's payouts from the CSV report format.
+    pub async fn list_merchant_payouts_csv(
+        &self,
+        merchant_code: impl Into<MerchantCode>,
+        query: &crate::payouts::PayoutListQuery,
+    ) -> Result<Vec<Payout>> {
+        let mut payouts = Vec::new();
+        self.for_each_merchant_payout_csv(merchant_code, query, |payout| {
+            payouts.push(payout);
+            Ok(())
+        })
+        .await?;
+        Ok(payouts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str =
+        "\u{feff}Payout Date,Type,Status,Amount,Fee,Currency,Transaction Code,Reference,Note\n\
+        2024-03-04,PAYOUT,SUCCESSFUL,98.10,1.90,EUR,TX1,PO-1,\"first, with comma\"\n\
+        2024-03-04,REFUND_DEDUCTION,SUCCESSFUL,-20.00,0,EUR,TX0,PO-1,\"multi\nline\"\n";
+
+    #[test]
+    fn test_parse_by_header_name_in_any_chunking() {
+        let whole = read_payouts_csv(REPORT.as_bytes()).unwrap();
+        assert_eq!(whole.len(), 2);
+        assert_eq!(whole[0].amount.0, Decimal::new(9810, 2));
+        assert_eq!(whole[0].fee.0.to_string(), "1.90");
+        assert_eq!(whole[1].payout_type, PayoutType::RefundDeduction);
+        assert_eq!(whole[1].extra.get("note").unwrap(), "multi\nline");
+
+        // one byte at a time splits rows and quoted fields at every position
+        let mut parser = PayoutCsvParser::new();
+        let mut payouts = Vec::new();
+        for byte in REPORT.as_bytes() {
+            payouts.extend(parser.push(std::slice::from_ref(byte)).unwrap());
+        }
+        payouts.extend(parser.finish().unwrap());
+        assert_eq!(payouts.len(), 2);
+        assert_eq!(payouts[0].reference.as_deref(), Some("PO-1"));
+        assert_eq!(payouts[0].extra.get("note").unwrap(), "first, with comma");
+    }
+
+    #[test]
+    fn test_missing_column_and_invalid_amount() {
+        let missing = read_payouts_csv("date,type,status,currency\n".as_bytes());
+        assert!(matches!(
+            missing,
+            Err(crate::Error::Csv(CsvError::MissingColumn("amount")))
+        ));
+
+        let invalid = read_payouts_csv(
+            "date,type,status,amount,currency\n2024-03-04,PAYOUT,SUCCESSFUL,1.2.3,EUR\n".as_bytes(),
+        );
+        assert!(matches!(
+            invalid,
+            Err(crate::Error::Csv(CsvError::InvalidField {
+                row: 1,
+                column: "amount",
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_payout_round_trip() {
+        let payouts = read_payouts_csv(REPORT.as_bytes()).unwrap();
+        let mut out = Vec::new();
+        write_payouts_csv(&mut out, &payouts).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("id,date,type,status,amount,fee,currency"));
+        assert!(text.contains(",98.10,1.90,EUR,"));
+
+        let again = read_payouts_csv(text.as_bytes()).unwrap();
+        assert_eq!(again.len(), 2);
+        assert_eq!(again[1].amount, payouts[1].amount);
+        assert_eq!(again[0].extra.get("note"), payouts[0].extra.get("note"));
+        assert_eq!(again[1].extra.get("note").unwrap(), "multi\nline");
+    }
+
+    #[test]
+    fn test_payout_round_trip_keeps_description() {
+        let csv = "date,type,status,amount,currency,description\n\
+            2024-03-04,PAYOUT,SUCCESSFUL,5.00,EUR,\"Card sales, 4 March\"\n";
+        let payouts = read_payouts_csv(csv.as_bytes()).unwrap();
+        assert_eq!(
+            payouts[0].description.as_deref(),
+            Some("Card sales, 4 March")
+        );
+
+        let mut out = Vec::new();
+        write_payouts_csv(&mut out, &payouts).unwrap();
+        let again = read_payouts_csv(out.as_slice()).unwrap();
+        assert_eq!(again[0].description, payouts[0].description);
+        assert!(again[0].extra.is_empty());
+    }
+
+    #[test]
+    fn test_rfc3339_dates_are_read_like_json() {
+        let csv = "date,type,status,amount,currency\n\
+            2024-03-04T06:00:00Z,PAYOUT,SUCCESSFUL,5.00,EUR\n";
+        let payouts = read_payouts_csv(csv.as_bytes()).unwrap();
+        assert_eq!(payouts[0].date.to_string(), "2024-03-04");
+    }
+
+    #[test]
+    fn test_formula_cells_are_escaped_and_read_back() {
+        let csv = "date,type,status,amount,currency,description,note\n\
+            2024-03-04,PAYOUT,SUCCESSFUL,-5.00,EUR,=HYPERLINK(\"x\"),@SUM(A1)\n";
+        let payouts = read_payouts_csv(csv.as_bytes()).unwrap();
+
+        let mut out = Vec::new();
+        write_payouts_csv(&mut out, &payouts).unwrap();
+        let written = String::from_utf8(out.clone()).unwrap();
+        assert!(written.contains(",-5.00,"));
+        assert!(written.contains("'=HYPERLINK"));
+        assert!(written.contains(",'@SUM(A1)"));
+
+        let again = read_payouts_csv(out.as_slice()).unwrap();
+        assert_eq!(again[0].description.as_deref(), Some("=HYPERLINK(\"x\")"));
+        assert_eq!(again[0].extra.get("note"), Some(&"@SUM(A1)".into()));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    CreateCustomerRequest, Customer, PaymentInstrument, Result, SumUpClient, UpdateCustomerRequest,
+    CreateCustomerRequest, Customer, CustomerId, PaymentInstrument, Result, SumUpClient, UpdateCustomerRequest,
 };
 
 impl SumUpClient {
@@ -11,29 +11,33 @@ impl SumUpClient {
     }
 
     /// Retrieves an identified saved customer resource.
-    pub async fn retrieve_customer(&self, customer_id: impl AsRef<str>) -> Result<Customer> {
-        let url = self.build_url(&format!("/v0.1/customers/{}", customer_id.as_ref()))?;
+    pub async fn retrieve_customer(&self, customer_id: impl Into<CustomerId>) -> Result<Customer> {
+        let customer_id: CustomerId = customer_id.into();
+        let url = self.build_url(&format!("/v0.1/customers/{}", customer_id.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
 
     /// Updates an identified saved customer resource's personal details.
-    pub async fn update_customer(&self, customer_id: impl AsRef<str>, body: &UpdateCustomerRequest) -> Result<Customer> {
-        let url = self.build_url(&format!("/v0.1/customers/{}", customer_id.as_ref()))?;
+    pub async fn update_customer(&self, customer_id: impl Into<CustomerId>, body: &UpdateCustomerRequest) -> Result<Customer> {
+        let customer_id: CustomerId = customer_id.into();
+        let url = self.build_url(&format!("/v0.1/customers/{}", customer_id.as_str()))?;
         let response = self.http_client.put(url).bearer_auth(self.api_key_str()).json(body).send().await?;
         self.handle_response(response).await
     }
 
     /// Lists all payment instruments for a customer.
-    pub async fn list_customer_payment_instruments(&self, customer_id: impl AsRef<str>) -> Result<Vec<PaymentInstrument>> {
-        let url = self.build_url(&format!("/v0.1/customers/{}/payment-instruments", customer_id.as_ref()))?;
+    pub async fn list_customer_payment_instruments(&self, customer_id: impl Into<CustomerId>) -> Result<Vec<PaymentInstrument>> {
+        let customer_id: CustomerId = customer_id.into();
+        let url = self.build_url(&format!("/v0.1/customers/{}/payment-instruments", customer_id.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
 
     /// Deactivates a payment instrument for a customer.
-    pub async fn deactivate_customer_payment_instrument(&self, customer_id: impl AsRef<str>, token: impl AsRef<str>) -> Result<()> {
-        let url = self.build_url(&format!("/v0.1/customers/{}/payment-instruments/{}", customer_id.as_ref(), token.as_ref()))?;
+    pub async fn deactivate_customer_payment_instrument(&self, customer_id: impl Into<CustomerId>, token: impl AsRef<str>) -> Result<()> {
+        let customer_id: CustomerId = customer_id.into();
+        let url = self.build_url(&format!("/v0.1/customers/{}/payment-instruments/{}", customer_id.as_str(), token.as_ref()))?;
         let response = self.http_client.delete(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_empty_response(response).await
     }
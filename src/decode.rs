@@ -0,0 +1,142 @@
+//! Decoding of API responses and forward-compatible enums.
+//!
+//! Every enum the API sends has an `Unknown(String)` variant that keeps values added by
+//! SumUp after this crate was released, so a new status never breaks decoding of a whole
+//! response. The client reports such values according to its [`DecodePolicy`].
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+/// How the client treats enum values it does not know
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecodePolicy {
+    /// Keep unknown values in the `Unknown` variant and report them to the warning hook
+    #[default]
+    Lenient,
+    /// Fail the request with [`Error::UnknownValue`](crate::Error::UnknownValue)
+    Strict,
+}
+
+/// An enum value from an API response that this version of the crate does not know
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownValue {
+    /// Name of the enum type, e.g. `"CheckoutStatus"`
+    pub type_name: &'static str,
+    /// The value as sent by the API
+    pub value: String,
+    /// Path of the request whose response contained the value
+    pub path: String,
+}
+
+impl std::fmt::Display for UnknownValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown {} value '{}' in response from {}",
+            self.type_name, self.value, self.path
+        )
+    }
+}
+
+/// Callback invoked for every unknown enum value the client decodes
+pub type UnknownValueHook = Arc<dyn Fn(&UnknownValue) + Send + Sync>;
+
+thread_local! {
+    static UNKNOWN_VALUES: RefCell<Option<Vec<(&'static str, String)>>> = const { RefCell::new(None) };
+}
+
+/// Note an unknown enum value if a client decode is in progress on this thread
+pub(crate) fn record_unknown(type_name: &'static str, value: &str) {
+    UNKNOWN_VALUES.with(|values| {
+        if let Some(values) = values.borrow_mut().as_mut() {
+            values.push((type_name, value.to_string()));
+        }
+    });
+}
+
+/// Run `decode`, returning its result and the unknown enum values it encountered
+pub(crate) fn collect_unknown<T>(decode: impl FnOnce() -> T) -> (T, Vec<(&'static str, String)>) {
+    let outer = UNKNOWN_VALUES.with(|values| values.borrow_mut().replace(Vec::new()));
+    let result = decode();
+    let collected =
+        UNKNOWN_VALUES.with(|values| std::mem::replace(&mut *values.borrow_mut(), outer));
+    (result, collected.unwrap_or_default())
+}
+
+/// Define an API enum whose wire values are listed explicitly and which keeps unknown
+/// values in an `Unknown(String)` variant.
+///
+/// The wire value `"unknown"` (in any case) maps to `Unknown` without being reported,
+/// since several SumUp enums document it as a regular value.
+macro_rules! api_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $( $(#[$vmeta:meta])* $variant:ident = $value:literal, )+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $( $(#[$vmeta])* $variant, )+
+            /// A value this version of the crate does not know, as sent by the API
+            Unknown(String),
+        }
+
+        impl $name {
+            /// Value as sent by the API
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $( Self::$variant => $value, )+
+                    Self::Unknown(raw) => raw,
+                }
+            }
+
+            /// Whether this is a value the crate does not know
+            pub fn is_unknown(&self) -> bool {
+                matches!(self, Self::Unknown(_))
+            }
+
+            fn from_known(value: &str) -> Option<Self> {
+                match value {
+                    $( $value => Some(Self::$variant), )+
+                    _ => None,
+                }
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = std::convert::Infallible;
+
+            fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+                Ok(Self::from_known(value).unwrap_or_else(|| Self::Unknown(value.to_string())))
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                Ok(Self::from_known(&value).unwrap_or_else(|| {
+                    if !value.eq_ignore_ascii_case("unknown") {
+                        $crate::decode::record_unknown(stringify!($name), &value);
+                    }
+                    Self::Unknown(value)
+                }))
+            }
+        }
+    };
+}
+
+pub(crate) use api_enum;
@@ -0,0 +1,667 @@
+//! Chargeback and dispute tracking.
+//!
+//! The SumUp API reports a chargeback only as the `CHARGE_BACK` status of a transaction
+//! (and, on transaction details, as `CHARGE_BACK` events). The [`DisputeTracker`] turns
+//! those into local [`Dispute`] records with a response deadline and an evidence
+//! checklist, and reports what needs attention as [`DisputeNotification`]s: new
+//! disputes, deadlines coming up, deadlines missed and resolutions.
+//!
+//! Disputes are persisted through the [`DisputeStore`] trait and "now" is read from a
+//! [`Clock`], like the billing scheduler.
+//!
+//! Enabled with the `disputes` cargo feature.
+
+use crate::clock::{Clock, SystemClock};
+use crate::transactions::TransactionHistoryQuery;
+use crate::{
+    Amount, Currency, Error, MerchantCode, Result, SumUpClient, Transaction, TransactionEvent,
+    TransactionEventType, TransactionId, TransactionStatus,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Where a dispute stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeStatus {
+    /// Waiting for the merchant to respond
+    Open,
+    /// Evidence was sent, waiting for the card scheme's decision
+    EvidenceSubmitted,
+    /// Decided in the merchant's favour
+    Won,
+    /// Decided in the card holder's favour
+    Lost,
+    /// The merchant accepted the chargeback without contesting it
+    Accepted,
+}
+
+impl DisputeStatus {
+    /// Whether the dispute has been decided or accepted
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Self::Won | Self::Lost | Self::Accepted)
+    }
+}
+
+/// Progress of one piece of evidence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvidenceStatus {
+    /// Still to be gathered
+    Requested,
+    /// Gathered but not yet sent
+    Collected,
+    Submitted,
+}
+
+/// An item on a dispute's evidence checklist, e.g. a receipt or delivery confirmation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Evidence {
+    pub name: String,
+    pub status: EvidenceStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A chargeback on one transaction, tracked locally
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dispute {
+    /// Code of the charged back transaction, which identifies the dispute
+    pub transaction_code: String,
+    pub transaction_id: TransactionId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merchant_code: Option<MerchantCode>,
+    /// Amount charged back: the sum of the chargeback events that did not fail, or the
+    /// transaction amount when the API returned none
+    pub amount: Amount,
+    pub currency: Currency,
+    /// Time of the first chargeback event, or when the chargeback was first seen
+    pub opened_at: DateTime<Utc>,
+    /// Deadline for submitting evidence
+    pub respond_by: DateTime<Utc>,
+    pub status: DisputeStatus,
+    #[serde(default)]
+    pub evidence: Vec<Evidence>,
+    /// Chargeback events from the transaction details, when the API returned them
+    #[serde(default)]
+    pub events: Vec<TransactionEvent>,
+    /// Whether the deadline reminder was sent
+    #[serde(default)]
+    pub reminder_sent: bool,
+    /// Whether the missed deadline was reported
+    #[serde(default)]
+    pub overdue_reported: bool,
+}
+
+impl Dispute {
+    /// Whether the merchant still has to respond
+    pub fn awaiting_response(&self) -> bool {
+        self.status == DisputeStatus::Open
+    }
+
+    /// Time left until the deadline, negative once it has passed
+    pub fn time_left(&self, now: DateTime<Utc>) -> Duration {
+        self.respond_by - now
+    }
+
+    /// Whether there is evidence and all of it has been gathered or sent
+    pub fn evidence_ready(&self) -> bool {
+        !self.evidence.is_empty()
+            && self
+                .evidence
+                .iter()
+                .all(|e| e.status != EvidenceStatus::Requested)
+    }
+
+    fn evidence_mut(&mut self, name: &str) -> Result<&mut Evidence> {
+        let code = &self.transaction_code;
+        self.evidence
+            .iter_mut()
+            .find(|e| e.name == name)
+            .ok_or_else(|| {
+                Error::InvalidInput(format!("dispute {} has no evidence '{}'", code, name))
+            })
+    }
+}
+
+/// What a [`DisputeNotification`] is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisputeNotificationKind {
+    /// A chargeback was detected
+    Opened,
+    /// The deadline is within the tracker's reminder period
+    DeadlineApproaching,
+    /// The deadline passed without evidence being submitted
+    DeadlineMissed,
+    /// The dispute was won, lost or accepted
+    Resolved,
+}
+
+/// Something the risk team should know about a dispute
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisputeNotification {
+    pub kind: DisputeNotificationKind,
+    /// The dispute as saved after the change
+    pub dispute: Dispute,
+}
+
+/// Callback invoked for every dispute notification
+pub type DisputeHook = Arc<dyn Fn(&DisputeNotification) + Send + Sync>;
+
+/// Where the tracker keeps its disputes, keyed by transaction code.
+///
+/// Every sync looks up each charged back transaction with [`get`](Self::get), so the
+/// store must keep closed disputes too; dropping them would open a new dispute for the
+/// same chargeback. Failures should be reported as [`Error::Storage`].
+pub trait DisputeStore {
+    /// The dispute for a charged back transaction, open or closed
+    fn get(&self, transaction_code: &str) -> Result<Option<Dispute>>;
+
+    /// Disputes that are not closed, soonest deadline first; used for deadline checks
+    fn open(&self) -> Result<Vec<Dispute>>;
+
+    /// Insert the dispute or replace the one with the same transaction code
+    fn save(&self, dispute: &Dispute) -> Result<()>;
+}
+
+impl<S: DisputeStore + ?Sized> DisputeStore for &S {
+    fn get(&self, transaction_code: &str) -> Result<Option<Dispute>> {
+        (**self).get(transaction_code)
+    }
+
+    fn open(&self) -> Result<Vec<Dispute>> {
+        (**self).open()
+    }
+
+    fn save(&self, dispute: &Dispute) -> Result<()> {
+        (**self).save(dispute)
+    }
+}
+
+/// Disputes held in memory, lost when the process exits.
+///
+/// Enough for tests and for a tracker that re-syncs from the transaction history on
+/// start; evidence notes and resolutions are not recoverable that way.
+#[derive(Debug, Default)]
+pub struct InMemoryDisputeStore {
+    disputes: Mutex<HashMap<String, Dispute>>,
+}
+
+impl InMemoryDisputeStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All stored disputes, in no particular order
+    pub fn all(&self) -> Vec<Dispute> {
+        self.lock().values().cloned().collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Dispute>> {
+        self.disputes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl DisputeStore for InMemoryDisputeStore {
+    fn get(&self, transaction_code: &str) -> Result<Option<Dispute>> {
+        Ok(self.lock().get(transaction_code).cloned())
+    }
+
+    fn open(&self) -> Result<Vec<Dispute>> {
+        let mut open: Vec<Dispute> = self
+            .lock()
+            .values()
+            .filter(|d| !d.status.is_closed())
+            .cloned()
+            .collect();
+        open.sort_by(|a, b| {
+            a.respond_by
+                .cmp(&b.respond_by)
+                .then_with(|| a.transaction_code.cmp(&b.transaction_code))
+        });
+        Ok(open)
+    }
+
+    fn save(&self, dispute: &Dispute) -> Result<()> {
+        self.lock()
+            .insert(dispute.transaction_code.clone(), dispute.clone());
+        Ok(())
+    }
+}
+
+/// Detects chargebacks and tracks the resulting disputes.
+///
+/// SumUp does not expose the card scheme's response deadline, so each dispute gets
+/// [`response_window`](Self::response_window) from when it was opened (7 days by
+/// default); a reminder is raised [`remind_before`](Self::remind_before) the deadline
+/// (2 days by default). Every notification is passed to the
+/// [`on_notification`](Self::on_notification) callback and returned to the caller.
+pub struct DisputeTracker<S, C = SystemClock> {
+    store: S,
+    clock: C,
+    response_window: Duration,
+    remind_before: Duration,
+    hook: Option<DisputeHook>,
+}
+
+impl<S: DisputeStore> DisputeTracker<S, SystemClock> {
+    /// Create a tracker using the system clock
+    pub fn new(store: S) -> Self {
+        Self::with_clock(store, SystemClock)
+    }
+}
+
+impl<S: DisputeStore, C: Clock> DisputeTracker<S, C> {
+    pub fn with_clock(store: S, clock: C) -> Self {
+        Self {
+            store,
+            clock,
+            response_window: Duration::days(7),
+            remind_before: Duration::days(2),
+            hook: None,
+        }
+    }
+
+    pub fn response_window(mut self, window: Duration) -> Self {
+        self.response_window = window;
+        self
+    }
+
+    pub fn remind_before(mut self, before: Duration) -> Self {
+        self.remind_before = before;
+        self
+    }
+
+    pub fn on_notification(
+        mut self,
+        hook: impl Fn(&DisputeNotification) + Send + Sync + 'static,
+    ) -> Self {
+        self.hook = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Open a dispute for every charged back transaction not tracked yet.
+    ///
+    /// A transaction is charged back when its status is `CHARGE_BACK` or it carries a
+    /// `CHARGE_BACK` event. Chargeback events of already tracked disputes are refreshed.
+    pub fn observe<'a>(
+        &self,
+        transactions: impl IntoIterator<Item = &'a Transaction>,
+    ) -> Result<Vec<DisputeNotification>> {
+        let mut notifications = Vec::new();
+        for transaction in transactions {
+            let events: Vec<TransactionEvent> = transaction.chargeback_events().cloned().collect();
+            if transaction.status != Some(TransactionStatus::ChargeBack) && events.is_empty() {
+                continue;
+            }
+
+            match self.store.get(&transaction.transaction_code)? {
+                Some(mut dispute) => {
+                    if !events.is_empty() && dispute.events != events {
+                        dispute.events = events;
+                        dispute.amount = chargeback_amount(transaction);
+                        self.store.save(&dispute)?;
+                    }
+                }
+                None => {
+                    let opened_at = events
+                        .iter()
+                        .filter_map(|e| e.timestamp)
+                        .min()
+                        .unwrap_or_else(|| self.clock.now());
+                    let dispute = Dispute {
+                        transaction_code: transaction.transaction_code.clone(),
+                        transaction_id: transaction.id.clone(),
+                        merchant_code: transaction.merchant_code.clone(),
+                        amount: chargeback_amount(transaction),
+                        currency: transaction.currency.clone(),
+                        opened_at,
+                        respond_by: opened_at + self.response_window,
+                        status: DisputeStatus::Open,
+                        evidence: Vec::new(),
+                        events,
+                        reminder_sent: false,
+                        overdue_reported: false,
+                    };
+                    notifications.push(self.notify(DisputeNotificationKind::Opened, dispute)?);
+                }
+            }
+        }
+        Ok(notifications)
+    }
+
+    /// Raise reminders for deadlines coming up and report deadlines missed, once each
+    pub fn check_deadlines(&self) -> Result<Vec<DisputeNotification>> {
+        let now = self.clock.now();
+        let mut notifications = Vec::new();
+        for mut dispute in self.store.open()? {
+            if !dispute.awaiting_response() {
+                continue;
+            }
+            if now > dispute.respond_by {
+                if !dispute.overdue_reported {
+                    dispute.overdue_reported = true;
+                    notifications
+                        .push(self.notify(DisputeNotificationKind::DeadlineMissed, dispute)?);
+                }
+            } else if dispute.time_left(now) <= self.remind_before && !dispute.reminder_sent {
+                dispute.reminder_sent = true;
+                notifications
+                    .push(self.notify(DisputeNotificationKind::DeadlineApproaching, dispute)?);
+            }
+        }
+        Ok(notifications)
+    }
+
+    /// Fetch transaction history matching `query`, open disputes for new chargebacks
+    /// and check deadlines.
+    ///
+    /// The details of newly charged back transactions are retrieved to pick up their
+    /// chargeback events.
+    pub async fn sync(
+        &self,
+        client: &SumUpClient,
+        merchant_code: impl Into<MerchantCode>,
+        query: &TransactionHistoryQuery,
+    ) -> Result<Vec<DisputeNotification>> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let mut transactions = client
+            .list_transactions_history_all(&merchant_code, query, None)
+            .await?;
+        for transaction in transactions.iter_mut() {
+            if transaction.status == Some(TransactionStatus::ChargeBack)
+                && self.store.get(&transaction.transaction_code)?.is_none()
+            {
+                *transaction = client
+                    .retrieve_transaction_by_id(&merchant_code, &transaction.id)
+                    .await?;
+            }
+        }
+
+        let mut notifications = self.observe(&transactions)?;
+        notifications.extend(self.check_deadlines()?);
+        Ok(notifications)
+    }
+
+    /// Add an item to a dispute's evidence checklist
+    pub fn request_evidence(&self, transaction_code: &str, name: &str) -> Result<Dispute> {
+        let now = self.clock.now();
+        self.update(transaction_code, |dispute| {
+            ensure_not_closed(dispute)?;
+            if dispute.evidence.iter().any(|e| e.name == name) {
+                return Err(Error::InvalidInput(format!(
+                    "dispute {} already has evidence '{}'",
+                    transaction_code, name
+                )));
+            }
+            dispute.evidence.push(Evidence {
+                name: name.to_string(),
+                status: EvidenceStatus::Requested,
+                note: None,
+                updated_at: now,
+            });
+            Ok(())
+        })
+    }
+
+    /// Mark an evidence item as gathered, with an optional note (e.g. where it is filed)
+    pub fn collect_evidence(
+        &self,
+        transaction_code: &str,
+        name: &str,
+        note: Option<&str>,
+    ) -> Result<Dispute> {
+        let now = self.clock.now();
+        self.update(transaction_code, |dispute| {
+            ensure_not_closed(dispute)?;
+            let evidence = dispute.evidence_mut(name)?;
+            evidence.status = EvidenceStatus::Collected;
+            evidence.note = note.map(str::to_string).or(evidence.note.take());
+            evidence.updated_at = now;
+            Ok(())
+        })
+    }
+
+    /// Record that the gathered evidence was sent; all requested items must be collected
+    pub fn submit_evidence(&self, transaction_code: &str) -> Result<Dispute> {
+        let now = self.clock.now();
+        self.update(transaction_code, |dispute| {
+            if !dispute.awaiting_response() {
+                return Err(Error::InvalidInput(format!(
+                    "dispute {} is not awaiting a response",
+                    transaction_code
+                )));
+            }
+            if !dispute.evidence_ready() {
+                return Err(Error::InvalidInput(format!(
+                    "dispute {} has evidence that is still to be collected",
+                    transaction_code
+                )));
+            }
+            for evidence in &mut dispute.evidence {
+                evidence.status = EvidenceStatus::Submitted;
+                evidence.updated_at = now;
+            }
+            dispute.status = DisputeStatus::EvidenceSubmitted;
+            Ok(())
+        })
+    }
+
+    /// Close a dispute as won, lost or accepted; a closed dispute cannot be resolved again
+    pub fn resolve(&self, transaction_code: &str, outcome: DisputeStatus) -> Result<Dispute> {
+        if !outcome.is_closed() {
+            return Err(Error::InvalidInput(format!(
+                "{:?} is not a dispute outcome",
+                outcome
+            )));
+        }
+        let dispute = self.update(transaction_code, |dispute| {
+            ensure_not_closed(dispute)?;
+            dispute.status = outcome;
+            Ok(())
+        })?;
+        self.emit(&DisputeNotification {
+            kind: DisputeNotificationKind::Resolved,
+            dispute: dispute.clone(),
+        });
+        Ok(dispute)
+    }
+
+    fn update(
+        &self,
+        transaction_code: &str,
+        change: impl FnOnce(&mut Dispute) -> Result<()>,
+    ) -> Result<Dispute> {
+        let mut dispute = self.store.get(transaction_code)?.ok_or_else(|| {
+            Error::InvalidInput(format!("no dispute for transaction {}", transaction_code))
+        })?;
+        change(&mut dispute)?;
+        self.store.save(&dispute)?;
+        Ok(dispute)
+    }
+
+    fn notify(
+        &self,
+        kind: DisputeNotificationKind,
+        dispute: Dispute,
+    ) -> Result<DisputeNotification> {
+        self.store.save(&dispute)?;
+        let notification = DisputeNotification { kind, dispute };
+        self.emit(&notification);
+        Ok(notification)
+    }
+
+    fn emit(&self, notification: &DisputeNotification) {
+        if let Some(hook) = &self.hook {
+            hook(notification);
+        }
+    }
+}
+
+/// Sum of the transaction's chargebacks, see [`Transaction::reversals`]; the transaction
+/// amount if there are none
+fn chargeback_amount(transaction: &Transaction) -> Amount {
+    let chargebacks = transaction.reversals(TransactionEventType::ChargeBack);
+    if chargebacks.is_empty() {
+        return transaction.amount;
+    }
+    Amount(chargebacks.iter().map(|(amount, _)| amount.0).sum())
+}
+
+fn ensure_not_closed(dispute: &Dispute) -> Result<()> {
+    if dispute.status.is_closed() {
+        return Err(Error::InvalidInput(format!(
+            "dispute {} is already closed as {:?}",
+            dispute.transaction_code, dispute.status
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+
+    fn transaction(code: &str, status: &str) -> Transaction {
+        serde_json::from_value(serde_json::json!({
+            "id": format!("id-{code}"),
+            "transaction_code": code,
+            "amount": 25.00,
+            "currency": "EUR",
+            "timestamp": "2024-03-01T10:00:00Z",
+            "status": status
+        }))
+        .unwrap()
+    }
+
+    fn tracker(clock: &ManualClock) -> DisputeTracker<InMemoryDisputeStore, &ManualClock> {
+        DisputeTracker::with_clock(InMemoryDisputeStore::new(), clock)
+    }
+
+    #[test]
+    fn test_chargebacks_open_disputes_once() {
+        let clock = ManualClock::at_date(chrono::NaiveDate::from_ymd_opt(2024, 3, 10).unwrap());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let hook_seen = seen.clone();
+        let tracker = tracker(&clock).on_notification(move |n| {
+            hook_seen.lock().unwrap().push(n.kind);
+        });
+
+        let history = vec![
+            transaction("TX1", "SUCCESSFUL"),
+            transaction("TX2", "CHARGE_BACK"),
+        ];
+        let opened = tracker.observe(&history).unwrap();
+        assert_eq!(opened.len(), 1);
+        let dispute = &opened[0].dispute;
+        assert_eq!(dispute.transaction_code, "TX2");
+        assert_eq!(dispute.respond_by, clock.now() + Duration::days(7));
+        assert!(tracker.observe(&history).unwrap().is_empty());
+        assert_eq!(*seen.lock().unwrap(), vec![DisputeNotificationKind::Opened]);
+    }
+
+    #[test]
+    fn test_chargeback_event_sets_opening_time_and_amount() {
+        let clock = ManualClock::at_date(chrono::NaiveDate::from_ymd_opt(2024, 3, 10).unwrap());
+        let tracker = tracker(&clock);
+        let detailed: Transaction = serde_json::from_value(serde_json::json!({
+            "id": "id-TX3", "transaction_code": "TX3", "amount": 25.00, "currency": "EUR",
+            "timestamp": "2024-03-01T10:00:00Z", "status": "SUCCESSFUL",
+            "events": [
+                {"type": "PAYOUT", "status": "PAID_OUT", "amount": 24.50},
+                {"type": "CHARGE_BACK", "status": "SUCCESSFUL", "amount": 10.00,
+                 "timestamp": "2024-03-08T09:00:00Z"}
+            ]
+        }))
+        .unwrap();
+
+        let opened = tracker.observe([&detailed]).unwrap();
+        let dispute = &opened[0].dispute;
+        assert_eq!(dispute.events.len(), 1);
+        // a partial chargeback disputes only its own amount
+        assert_eq!(dispute.amount, Amount::from_cents(1000));
+        assert_eq!(dispute.opened_at.to_rfc3339(), "2024-03-08T09:00:00+00:00");
+        assert_eq!(dispute.respond_by.to_rfc3339(), "2024-03-15T09:00:00+00:00");
+    }
+
+    #[test]
+    fn test_deadline_reminder_and_miss_are_reported_once() {
+        let clock = ManualClock::at_date(chrono::NaiveDate::from_ymd_opt(2024, 3, 10).unwrap());
+        let tracker = tracker(&clock);
+        tracker
+            .observe([&transaction("TX2", "CHARGE_BACK")])
+            .unwrap();
+
+        assert!(tracker.check_deadlines().unwrap().is_empty());
+        clock.advance_days(5);
+        let kinds: Vec<_> = tracker
+            .check_deadlines()
+            .unwrap()
+            .into_iter()
+            .map(|n| n.kind)
+            .collect();
+        assert_eq!(kinds, vec![DisputeNotificationKind::DeadlineApproaching]);
+        assert!(tracker.check_deadlines().unwrap().is_empty());
+
+        clock.advance_days(3);
+        let missed = tracker.check_deadlines().unwrap();
+        assert_eq!(missed[0].kind, DisputeNotificationKind::DeadlineMissed);
+        assert!(missed[0].dispute.overdue_reported);
+        assert!(tracker.check_deadlines().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_evidence_workflow() {
+        let clock = ManualClock::at_date(chrono::NaiveDate::from_ymd_opt(2024, 3, 10).unwrap());
+        let tracker = tracker(&clock);
+        tracker
+            .observe([&transaction("TX2", "CHARGE_BACK")])
+            .unwrap();
+
+        tracker.request_evidence("TX2", "receipt").unwrap();
+        tracker.request_evidence("TX2", "delivery").unwrap();
+        assert!(tracker.request_evidence("TX2", "receipt").is_err());
+        tracker
+            .collect_evidence("TX2", "receipt", Some("drive/receipts/TX2.pdf"))
+            .unwrap();
+        assert!(tracker.submit_evidence("TX2").is_err());
+
+        tracker.collect_evidence("TX2", "delivery", None).unwrap();
+        let submitted = tracker.submit_evidence("TX2").unwrap();
+        assert_eq!(submitted.status, DisputeStatus::EvidenceSubmitted);
+        assert!(submitted
+            .evidence
+            .iter()
+            .all(|e| e.status == EvidenceStatus::Submitted));
+
+        // submitted disputes no longer raise deadline notifications
+        clock.advance_days(10);
+        assert!(tracker.check_deadlines().unwrap().is_empty());
+
+        assert!(tracker.resolve("TX2", DisputeStatus::Open).is_err());
+        let won = tracker.resolve("TX2", DisputeStatus::Won).unwrap();
+        assert!(won.status.is_closed());
+        assert!(tracker.store().open().unwrap().is_empty());
+
+        // a decided dispute keeps its outcome and evidence
+        assert!(tracker.resolve("TX2", DisputeStatus::Lost).is_err());
+        assert!(tracker.request_evidence("TX2", "invoice").is_err());
+        assert!(tracker.collect_evidence("TX2", "receipt", None).is_err());
+        assert_eq!(
+            tracker.store().get("TX2").unwrap().unwrap().status,
+            DisputeStatus::Won
+        );
+    }
+}
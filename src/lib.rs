@@ -11,7 +11,14 @@ pub use models::*;
 
 // Utility types
 pub mod utils;
-pub use utils::Nullable;
+pub use utils::{ExtraFields, Nullable, UnknownFields};
+
+// Response decoding and forward-compatible enums
+pub mod decode;
+pub use decode::{DecodePolicy, UnknownValue, UnknownValueHook};
+
+// Time sources for schedulers and trackers
+pub mod clock;
 
 // Declare modules for API endpoints
 pub mod checkouts;
@@ -19,7 +26,9 @@ pub mod customers;
 pub mod members;
 pub mod memberships;
 pub mod merchant;
+pub mod merchant_scope;
 pub mod oauth;
+pub mod organizations;
 pub mod payouts;
 pub mod readers;
 pub mod receipts;
@@ -28,6 +37,21 @@ pub mod subaccounts;
 pub mod transactions;
 pub mod webhooks;
 
+#[cfg(feature = "billing")]
+pub mod billing;
+
+#[cfg(feature = "csv")]
+pub mod csv_io;
+
+#[cfg(feature = "analytics")]
+pub mod analytics;
+
+#[cfg(feature = "disputes")]
+pub mod disputes;
+
+#[cfg(feature = "team-sync")]
+pub mod team_sync;
+
 // Re-export OAuth types
 pub use oauth::{OAuthClient, OAuthConfig, Scope, TokenResponse};
 
@@ -37,11 +61,19 @@ pub use subaccounts::{CreateOperatorRequest, Operator, UpdateOperatorRequest};
 // Re-export Webhook types
 pub use webhooks::{WebhookEvent, WebhookEventType, WebhookResponse};
 
+// Re-export the merchant-scoped handle
+pub use merchant_scope::MerchantScope;
+
 // Re-export query types for convenience
 pub use transactions::TransactionHistoryQuery;
 
 // --- Custom Error Type ---
+/// Errors returned by the client and its helpers
+///
+/// Non-exhaustive: some variants only exist with their feature enabled (`Csv` with `csv`,
+/// `Yaml` with `team-sync`), so matches need a wildcard arm to compile under any feature set.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
@@ -66,6 +98,29 @@ pub enum Error {
 
     #[error("Authentication failed: {0}")]
     Unauthorized(String),
+
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    #[error(transparent)]
+    CardValidation(#[from] CardValidationError),
+
+    #[error(transparent)]
+    Money(#[from] MoneyError),
+
+    #[error(transparent)]
+    AmountParse(#[from] AmountParseError),
+
+    #[error("{0}")]
+    UnknownValue(UnknownValue),
+
+    #[cfg(feature = "csv")]
+    #[error(transparent)]
+    Csv(#[from] csv_io::CsvError),
+
+    #[cfg(feature = "team-sync")]
+    #[error("YAML error: {0}")]
+    Yaml(#[from] serde_yaml::Error),
 }
 
 /// Structured representation of SumUp API error responses
@@ -93,6 +148,8 @@ pub struct SumUpClientBuilder {
     api_key: Option<SecretString>,
     base_url: Option<String>,
     timeout: Option<Duration>,
+    decode_policy: DecodePolicy,
+    on_unknown_value: Option<UnknownValueHook>,
 }
 
 impl SumUpClientBuilder {
@@ -118,6 +175,19 @@ impl SumUpClientBuilder {
         self
     }
 
+    /// Set how enum values unknown to this crate are handled (default: lenient)
+    pub fn decode_policy(mut self, policy: DecodePolicy) -> Self {
+        self.decode_policy = policy;
+        self
+    }
+
+    /// Call `hook` for every enum value in a response that this crate does not know,
+    /// e.g. to log a warning when SumUp adds a new status
+    pub fn on_unknown_value(mut self, hook: impl Fn(&UnknownValue) + Send + Sync + 'static) -> Self {
+        self.on_unknown_value = Some(std::sync::Arc::new(hook));
+        self
+    }
+
     /// Build the client
     pub fn build(self) -> Result<SumUpClient> {
         let api_key = self
@@ -139,6 +209,8 @@ impl SumUpClientBuilder {
             http_client,
             api_key,
             base_url: Url::parse(&base_url)?,
+            decode_policy: self.decode_policy,
+            on_unknown_value: self.on_unknown_value,
         })
     }
 }
@@ -148,6 +220,8 @@ pub struct SumUpClient {
     pub(crate) http_client: Client,
     pub(crate) api_key: SecretString,
     pub(crate) base_url: Url,
+    pub(crate) decode_policy: DecodePolicy,
+    pub(crate) on_unknown_value: Option<UnknownValueHook>,
 }
 
 impl SumUpClient {
@@ -190,12 +264,34 @@ impl SumUpClient {
         response: reqwest::Response,
     ) -> Result<T> {
         if response.status().is_success() {
-            Ok(response.json::<T>().await?)
+            let path = response.url().path().to_string();
+            let bytes = response.bytes().await?;
+            self.decode(&bytes, &path)
         } else {
             self.handle_error(response).await
         }
     }
 
+    /// Deserialize a response body, applying the client's [`DecodePolicy`] to unknown enum values.
+    pub(crate) fn decode<T: serde::de::DeserializeOwned>(&self, body: &[u8], path: &str) -> Result<T> {
+        let (decoded, unknown) = decode::collect_unknown(|| serde_json::from_slice::<T>(body));
+        let decoded = decoded?;
+        for (type_name, value) in unknown {
+            let unknown = UnknownValue {
+                type_name,
+                value,
+                path: path.to_string(),
+            };
+            if let Some(hook) = &self.on_unknown_value {
+                hook(&unknown);
+            }
+            if self.decode_policy == DecodePolicy::Strict {
+                return Err(Error::UnknownValue(unknown));
+            }
+        }
+        Ok(decoded)
+    }
+
     /// Handle response that returns no body (204 No Content, etc).
     pub(crate) async fn handle_empty_response(&self, response: reqwest::Response) -> Result<()> {
         if response.status().is_success() {
@@ -256,6 +352,7 @@ impl std::fmt::Debug for SumUpClient {
         f.debug_struct("SumUpClient")
             .field("base_url", &self.base_url)
             .field("api_key", &"[REDACTED]")
+            .field("decode_policy", &self.decode_policy)
             .finish()
     }
 }
@@ -1,37 +1,87 @@
-use crate::{CreateMemberRequest, Member, MemberListResponse, Result, SumUpClient, UpdateMemberRequest};
+use crate::{CreateMemberRequest, Member, MemberId, MemberListQuery, MemberListResponse, MerchantCode, Result, SumUpClient, UpdateMemberRequest};
+use std::collections::HashSet;
+
+/// Page size used by [`SumUpClient::list_all_members`] when the query sets none
+const DEFAULT_MEMBER_PAGE_SIZE: i32 = 100;
 
 impl SumUpClient {
     /// Lists all members for a merchant.
-    pub async fn list_members(&self, merchant_code: impl AsRef<str>) -> Result<MemberListResponse> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/members", merchant_code.as_ref()))?;
+    pub async fn list_members(&self, merchant_code: impl Into<MerchantCode>) -> Result<MemberListResponse> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let url = self.build_url(&format!("/v0.1/merchants/{}/members", merchant_code.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
 
+    /// Lists one page of members for a merchant.
+    pub async fn list_members_with_query(&self, merchant_code: impl Into<MerchantCode>, query: &MemberListQuery) -> Result<MemberListResponse> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let mut url = self.build_url(&format!("/v0.1/merchants/{}/members", merchant_code.as_str()))?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            if let Some(v) = query.limit { pairs.append_pair("limit", &v.to_string()); }
+            if let Some(v) = query.offset { pairs.append_pair("offset", &v.to_string()); }
+        }
+        let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Fetches every member of a merchant, following offset pagination until
+    /// `total_count` is reached, a page comes back short, or a page brings no new members.
+    pub async fn list_all_members(&self, merchant_code: impl Into<MerchantCode>) -> Result<Vec<Member>> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let mut query = MemberListQuery::new().limit(DEFAULT_MEMBER_PAGE_SIZE).offset(0);
+        let mut seen = HashSet::new();
+        let mut members = Vec::new();
+        loop {
+            let page = self.list_members_with_query(&merchant_code, &query).await?;
+            let fetched = page.items.len() as i32;
+            let before = members.len();
+            members.extend(page.items.into_iter().filter(|m| seen.insert(m.id.clone())));
+            let offset = query.offset.unwrap_or(0) + fetched;
+            let done = match page.total_count {
+                Some(total) => offset >= total,
+                None => fetched < DEFAULT_MEMBER_PAGE_SIZE,
+            };
+            if done || members.len() == before {
+                break;
+            }
+            query.offset = Some(offset);
+        }
+        Ok(members)
+    }
+
     /// Creates a new member for a merchant.
-    pub async fn create_member(&self, merchant_code: impl AsRef<str>, body: &CreateMemberRequest) -> Result<Member> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/members", merchant_code.as_ref()))?;
+    pub async fn create_member(&self, merchant_code: impl Into<MerchantCode>, body: &CreateMemberRequest) -> Result<Member> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let url = self.build_url(&format!("/v0.1/merchants/{}/members", merchant_code.as_str()))?;
         let response = self.http_client.post(url).bearer_auth(self.api_key_str()).json(body).send().await?;
         self.handle_response(response).await
     }
 
     /// Retrieves a member by ID.
-    pub async fn retrieve_member(&self, merchant_code: impl AsRef<str>, member_id: impl AsRef<str>) -> Result<Member> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/members/{}", merchant_code.as_ref(), member_id.as_ref()))?;
+    pub async fn retrieve_member(&self, merchant_code: impl Into<MerchantCode>, member_id: impl Into<MemberId>) -> Result<Member> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let member_id: MemberId = member_id.into();
+        let url = self.build_url(&format!("/v0.1/merchants/{}/members/{}", merchant_code.as_str(), member_id.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
 
     /// Updates a member.
-    pub async fn update_member(&self, merchant_code: impl AsRef<str>, member_id: impl AsRef<str>, body: &UpdateMemberRequest) -> Result<Member> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/members/{}", merchant_code.as_ref(), member_id.as_ref()))?;
+    pub async fn update_member(&self, merchant_code: impl Into<MerchantCode>, member_id: impl Into<MemberId>, body: &UpdateMemberRequest) -> Result<Member> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let member_id: MemberId = member_id.into();
+        let url = self.build_url(&format!("/v0.1/merchants/{}/members/{}", merchant_code.as_str(), member_id.as_str()))?;
         let response = self.http_client.put(url).bearer_auth(self.api_key_str()).json(body).send().await?;
         self.handle_response(response).await
     }
 
     /// Deletes a member.
-    pub async fn delete_member(&self, merchant_code: impl AsRef<str>, member_id: impl AsRef<str>) -> Result<()> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/members/{}", merchant_code.as_ref(), member_id.as_ref()))?;
+    pub async fn delete_member(&self, merchant_code: impl Into<MerchantCode>, member_id: impl Into<MemberId>) -> Result<()> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let member_id: MemberId = member_id.into();
+        let url = self.build_url(&format!("/v0.1/merchants/{}/members/{}", merchant_code.as_str(), member_id.as_str()))?;
         let response = self.http_client.delete(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_empty_response(response).await
     }
@@ -1,11 +1,59 @@
-use crate::{Membership, MembershipListResponse, Result, SumUpClient};
+use crate::{Membership, MembershipId, MembershipListQuery, MembershipListResponse, MembershipStatus, Result, SumUpClient, UpdateMembershipStatusRequest};
 
 impl SumUpClient {
     /// Lists all memberships for the authenticated user.
     pub async fn list_memberships(&self) -> Result<Vec<Membership>> {
-        let url = self.build_url("/v0.1/memberships")?;
-        let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
-        let resp: MembershipListResponse = self.handle_response(response).await?;
+        let resp = self.list_memberships_with_query(&MembershipListQuery::default()).await?;
         Ok(resp.items)
     }
+
+    /// Lists one page of memberships matching the query filters.
+    pub async fn list_memberships_with_query(&self, query: &MembershipListQuery) -> Result<MembershipListResponse> {
+        let mut url = self.build_url("/v0.1/memberships")?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            if let Some(ref v) = query.status { pairs.append_pair("status", v.as_str()); }
+            if let Some(ref v) = query.resource_type { pairs.append_pair("resource.type", v.as_str()); }
+            for role in &query.roles { pairs.append_pair("roles", role.as_str()); }
+            if let Some(v) = query.limit { pairs.append_pair("limit", &v.to_string()); }
+            if let Some(v) = query.offset { pairs.append_pair("offset", &v.to_string()); }
+        }
+        let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Fetches every membership matching the query filters, following offset pagination
+    /// from `query.offset` until `total_count` is reached or a page comes back empty.
+    pub async fn list_all_memberships(&self, query: &MembershipListQuery) -> Result<Vec<Membership>> {
+        let mut page = query.clone();
+        let mut memberships = Vec::new();
+        loop {
+            let resp = self.list_memberships_with_query(&page).await?;
+            let fetched = resp.items.len() as i32;
+            memberships.extend(resp.items);
+            let offset = page.offset.unwrap_or(0) + fetched;
+            if fetched == 0 || offset >= resp.total_count {
+                break;
+            }
+            page.offset = Some(offset);
+        }
+        Ok(memberships)
+    }
+
+    /// Accepts a pending membership invitation.
+    pub async fn accept_membership_invitation(&self, membership_id: impl Into<MembershipId>) -> Result<Membership> {
+        self.update_membership_status(membership_id.into(), MembershipStatus::Active).await
+    }
+
+    /// Declines a pending membership invitation.
+    pub async fn decline_membership_invitation(&self, membership_id: impl Into<MembershipId>) -> Result<Membership> {
+        self.update_membership_status(membership_id.into(), MembershipStatus::Declined).await
+    }
+
+    async fn update_membership_status(&self, membership_id: MembershipId, status: MembershipStatus) -> Result<Membership> {
+        let url = self.build_url(&format!("/v0.1/memberships/{}", membership_id.as_str()))?;
+        let body = UpdateMembershipStatusRequest { status };
+        let response = self.http_client.put(url).bearer_auth(self.api_key_str()).json(&body).send().await?;
+        self.handle_response(response).await
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Merchant, MerchantProfile, MerchantProfileDetails, Result, SumUpClient};
+use crate::{DoingBusinessAs, Merchant, MerchantBankAccount, MerchantCode, MerchantProfile, MerchantProfileDetails, MerchantSettings, PersonalProfile, Result, SumUpClient, UpdateDoingBusinessAsRequest, UpdateMerchantProfileRequest, UpdateMerchantSettingsRequest};
 
 impl SumUpClient {
     /// Retrieves the authenticated merchant's profile.
@@ -11,8 +11,9 @@ impl SumUpClient {
     }
 
     /// Retrieves a specific merchant's profile.
-    pub async fn get_merchant(&self, merchant_code: impl AsRef<str>) -> Result<Merchant> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}", merchant_code.as_ref()))?;
+    pub async fn get_merchant(&self, merchant_code: impl Into<MerchantCode>) -> Result<Merchant> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let url = self.build_url(&format!("/v0.1/merchants/{}", merchant_code.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
@@ -21,4 +22,69 @@ impl SumUpClient {
     pub async fn list_merchants(&self) -> Result<Vec<crate::Membership>> {
         self.list_memberships().await
     }
+
+    /// Retrieves the business profile of the authenticated merchant.
+    pub async fn get_business_profile(&self) -> Result<MerchantProfileDetails> {
+        let url = self.build_url("/v0.1/me/merchant-profile")?;
+        let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Updates the business profile; only the fields set on `body` are changed.
+    pub async fn update_business_profile(&self, body: &UpdateMerchantProfileRequest) -> Result<()> {
+        let url = self.build_url("/v0.1/me/merchant-profile")?;
+        let response = self.http_client.put(url).bearer_auth(self.api_key_str()).json(body).send().await?;
+        self.handle_empty_response(response).await
+    }
+
+    /// Retrieves the name and contact details shown to customers.
+    pub async fn get_doing_business_as(&self) -> Result<DoingBusinessAs> {
+        let url = self.build_url("/v0.1/me/merchant-profile/doing-business-as")?;
+        let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Updates the name and contact details shown to customers; only the fields set on `body` are changed.
+    pub async fn update_doing_business_as(&self, body: &UpdateDoingBusinessAsRequest) -> Result<()> {
+        let url = self.build_url("/v0.1/me/merchant-profile/doing-business-as")?;
+        let response = self.http_client.put(url).bearer_auth(self.api_key_str()).json(body).send().await?;
+        self.handle_empty_response(response).await
+    }
+
+    /// Lists the merchant's bank accounts, or only the primary one if `primary_only` is set.
+    ///
+    /// The API has no call to change bank accounts; payout accounts are verified and
+    /// changed in the SumUp dashboard.
+    pub async fn list_bank_accounts(&self, primary_only: bool) -> Result<Vec<MerchantBankAccount>> {
+        let mut url = self.build_url("/v0.1/me/merchant-profile/bank-accounts")?;
+        if primary_only {
+            url.query_pairs_mut().append_pair("primary", "true");
+        }
+        let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Retrieves the personal profile of the account owner.
+    ///
+    /// The profile is read-only through the API: the owner's identity details are
+    /// verified by SumUp and changed in the dashboard.
+    pub async fn get_personal_profile(&self) -> Result<PersonalProfile> {
+        let url = self.build_url("/v0.1/me/personal-profile")?;
+        let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Retrieves the merchant's account settings.
+    pub async fn get_merchant_settings(&self) -> Result<MerchantSettings> {
+        let url = self.build_url("/v0.1/me/merchant-profile/settings")?;
+        let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Updates the merchant's account settings; only the fields set on `body` are changed.
+    pub async fn update_merchant_settings(&self, body: &UpdateMerchantSettingsRequest) -> Result<()> {
+        let url = self.build_url("/v0.1/me/merchant-profile/settings")?;
+        let response = self.http_client.put(url).bearer_auth(self.api_key_str()).json(body).send().await?;
+        self.handle_empty_response(response).await
+    }
 }
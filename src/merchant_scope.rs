@@ -0,0 +1,346 @@
+//! Merchant-scoped access to the API.
+//!
+//! [`SumUpClient::merchant`] returns a [`MerchantScope`] that carries the merchant code, so
+//! reader, member, role, payout, receipt and transaction calls don't have to repeat it.
+//! [`SumUpClient::for_each_merchant`] runs an operation against every merchant reachable
+//! through a set of memberships, with a bound on how many run at once.
+
+use crate::payouts::PayoutListQuery;
+use crate::receipts::ReceiptRetrieveQuery;
+use crate::transactions::TransactionHistoryQuery;
+use crate::{
+    CheckoutId, ClientTransactionId, CreateMemberRequest, CreateReaderCheckoutRequest,
+    CreateReaderRequest, CreateRoleRequest, Member, MemberId, MemberListResponse, Membership,
+    MembershipStatus, MerchantCode, Money, Payout, PayoutId, PayoutListResponse, Reader,
+    ReaderCheckoutResponse, ReaderId, ReaderListResponse, Receipt, ReceiptId, ReconciliationReport,
+    Result, Role, RoleId, RoleListResponse, SumUpClient, Transaction, TransactionHistoryResponse,
+    TransactionId, UpdateMemberRequest, UpdateReaderRequest, UpdateRoleRequest,
+};
+use futures_util::stream::{self, StreamExt};
+use std::collections::HashSet;
+use std::future::Future;
+
+/// A [`SumUpClient`] bound to a single merchant code.
+///
+/// Cheap to create and clone; every method forwards to the corresponding client method
+/// with the scoped merchant code filled in.
+#[derive(Clone)]
+pub struct MerchantScope<'a> {
+    client: &'a SumUpClient,
+    merchant_code: MerchantCode,
+}
+
+impl<'a> MerchantScope<'a> {
+    /// The merchant code this scope is bound to.
+    pub fn merchant_code(&self) -> &MerchantCode {
+        &self.merchant_code
+    }
+
+    /// The underlying client, for calls that are not merchant-scoped.
+    pub fn client(&self) -> &'a SumUpClient {
+        self.client
+    }
+
+    // --- Readers ---
+
+    /// Lists readers for the merchant.
+    pub async fn list_readers(&self) -> Result<ReaderListResponse> {
+        self.client.list_merchant_readers(&self.merchant_code).await
+    }
+
+    /// Creates a reader for the merchant.
+    pub async fn create_reader(&self, body: &CreateReaderRequest) -> Result<Reader> {
+        self.client
+            .create_merchant_reader(&self.merchant_code, body)
+            .await
+    }
+
+    /// Retrieves a reader of the merchant.
+    pub async fn retrieve_reader(&self, reader_id: impl Into<ReaderId>) -> Result<Reader> {
+        self.client
+            .retrieve_merchant_reader(&self.merchant_code, reader_id)
+            .await
+    }
+
+    /// Updates a reader of the merchant.
+    pub async fn update_reader(
+        &self,
+        reader_id: impl Into<ReaderId>,
+        body: &UpdateReaderRequest,
+    ) -> Result<Reader> {
+        self.client
+            .update_merchant_reader(&self.merchant_code, reader_id, body)
+            .await
+    }
+
+    /// Deletes a reader of the merchant.
+    pub async fn delete_reader(&self, reader_id: impl Into<ReaderId>) -> Result<()> {
+        self.client
+            .delete_merchant_reader(&self.merchant_code, reader_id)
+            .await
+    }
+
+    /// Starts a checkout on one of the merchant's readers.
+    pub async fn create_reader_checkout(
+        &self,
+        reader_id: impl Into<ReaderId>,
+        body: &CreateReaderCheckoutRequest,
+    ) -> Result<ReaderCheckoutResponse> {
+        self.client
+            .create_merchant_reader_checkout(&self.merchant_code, reader_id, body)
+            .await
+    }
+
+    /// Terminates a checkout running on one of the merchant's readers.
+    pub async fn terminate_reader_checkout(
+        &self,
+        reader_id: impl Into<ReaderId>,
+        checkout_id: impl Into<CheckoutId>,
+    ) -> Result<()> {
+        self.client
+            .terminate_merchant_reader_checkout(&self.merchant_code, reader_id, checkout_id)
+            .await
+    }
+
+    // --- Members ---
+
+    /// Lists members of the merchant.
+    pub async fn list_members(&self) -> Result<MemberListResponse> {
+        self.client.list_members(&self.merchant_code).await
+    }
+
+    /// Creates a member of the merchant.
+    pub async fn create_member(&self, body: &CreateMemberRequest) -> Result<Member> {
+        self.client.create_member(&self.merchant_code, body).await
+    }
+
+    /// Retrieves a member of the merchant.
+    pub async fn retrieve_member(&self, member_id: impl Into<MemberId>) -> Result<Member> {
+        self.client
+            .retrieve_member(&self.merchant_code, member_id)
+            .await
+    }
+
+    /// Updates a member of the merchant.
+    pub async fn update_member(
+        &self,
+        member_id: impl Into<MemberId>,
+        body: &UpdateMemberRequest,
+    ) -> Result<Member> {
+        self.client
+            .update_member(&self.merchant_code, member_id, body)
+            .await
+    }
+
+    /// Deletes a member of the merchant.
+    pub async fn delete_member(&self, member_id: impl Into<MemberId>) -> Result<()> {
+        self.client
+            .delete_member(&self.merchant_code, member_id)
+            .await
+    }
+
+    // --- Roles ---
+
+    /// Lists roles of the merchant.
+    pub async fn list_roles(&self) -> Result<RoleListResponse> {
+        self.client.list_roles(&self.merchant_code).await
+    }
+
+    /// Creates a custom role for the merchant.
+    pub async fn create_role(&self, body: &CreateRoleRequest) -> Result<Role> {
+        self.client.create_role(&self.merchant_code, body).await
+    }
+
+    /// Retrieves a role of the merchant.
+    pub async fn retrieve_role(&self, role_id: impl Into<RoleId>) -> Result<Role> {
+        self.client
+            .retrieve_role(&self.merchant_code, role_id)
+            .await
+    }
+
+    /// Updates a role of the merchant.
+    pub async fn update_role(
+        &self,
+        role_id: impl Into<RoleId>,
+        body: &UpdateRoleRequest,
+    ) -> Result<Role> {
+        self.client
+            .update_role(&self.merchant_code, role_id, body)
+            .await
+    }
+
+    /// Deletes a role of the merchant.
+    pub async fn delete_role(&self, role_id: impl Into<RoleId>) -> Result<()> {
+        self.client.delete_role(&self.merchant_code, role_id).await
+    }
+
+    // --- Payouts ---
+
+    /// Lists payouts of the merchant.
+    pub async fn list_payouts(&self, query: &PayoutListQuery) -> Result<PayoutListResponse> {
+        self.client
+            .list_merchant_payouts(&self.merchant_code, query)
+            .await
+    }
+
+    /// Retrieves a payout of the merchant.
+    pub async fn retrieve_payout(&self, payout_id: impl Into<PayoutId>) -> Result<Payout> {
+        self.client
+            .retrieve_merchant_payout(&self.merchant_code, payout_id)
+            .await
+    }
+
+    /// Lists the transactions settled in the payout that `payout` belongs to.
+    pub async fn list_payout_transactions(&self, payout: &Payout) -> Result<Vec<Transaction>> {
+        self.client
+            .list_payout_transactions(&self.merchant_code, payout)
+            .await
+    }
+
+    /// Reconciles the merchant's payouts against its transaction history.
+    pub async fn reconcile_payouts(
+        &self,
+        payouts: &PayoutListQuery,
+        transactions: &TransactionHistoryQuery,
+    ) -> Result<ReconciliationReport> {
+        self.client
+            .reconcile_payouts(&self.merchant_code, payouts, transactions)
+            .await
+    }
+
+    // --- Receipts ---
+
+    /// Retrieves a receipt of the merchant, optionally for a specific transaction event (e.g. a refund).
+    pub async fn retrieve_receipt(
+        &self,
+        receipt_id: impl Into<ReceiptId>,
+        tx_event_id: Option<i32>,
+    ) -> Result<Receipt> {
+        let mut query = ReceiptRetrieveQuery::new(self.merchant_code.as_str());
+        query.tx_event_id = tx_event_id;
+        self.client
+            .retrieve_merchant_receipt(self.merchant_code.as_str(), receipt_id, &query)
+            .await
+    }
+
+    // --- Transactions ---
+
+    /// Lists one page of the merchant's transaction history.
+    pub async fn list_transactions_history(
+        &self,
+        query: &TransactionHistoryQuery,
+    ) -> Result<TransactionHistoryResponse> {
+        self.client
+            .list_transactions_history(&self.merchant_code, query)
+            .await
+    }
+
+    /// Lists the merchant's full transaction history matching `query`, following pagination.
+    pub async fn list_transactions_history_all(
+        &self,
+        query: &TransactionHistoryQuery,
+        max_pages: Option<usize>,
+    ) -> Result<Vec<Transaction>> {
+        self.client
+            .list_transactions_history_all(&self.merchant_code, query, max_pages)
+            .await
+    }
+
+    /// Retrieves a transaction of the merchant by ID.
+    pub async fn retrieve_transaction_by_id(
+        &self,
+        transaction_id: impl Into<TransactionId>,
+    ) -> Result<Transaction> {
+        self.client
+            .retrieve_transaction_by_id(&self.merchant_code, transaction_id)
+            .await
+    }
+
+    /// Retrieves a transaction of the merchant by transaction code.
+    pub async fn retrieve_transaction_by_code(
+        &self,
+        transaction_code: impl AsRef<str>,
+    ) -> Result<Transaction> {
+        self.client
+            .retrieve_transaction_by_code(&self.merchant_code, transaction_code)
+            .await
+    }
+
+    /// Retrieves a transaction of the merchant by client transaction id.
+    pub async fn retrieve_transaction_by_client_transaction_id(
+        &self,
+        client_transaction_id: impl Into<ClientTransactionId>,
+    ) -> Result<Transaction> {
+        self.client
+            .retrieve_transaction_by_client_transaction_id(
+                &self.merchant_code,
+                client_transaction_id,
+            )
+            .await
+    }
+
+    /// Refunds `money` of one of the merchant's transactions.
+    pub async fn refund_transaction_money(
+        &self,
+        transaction: &Transaction,
+        money: &Money,
+        reason: &str,
+    ) -> Result<Transaction> {
+        self.client
+            .refund_transaction_money(&self.merchant_code, transaction, money, reason)
+            .await
+    }
+}
+
+impl std::fmt::Debug for MerchantScope<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MerchantScope")
+            .field("merchant_code", &self.merchant_code)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SumUpClient {
+    /// Returns a handle scoped to `merchant_code`.
+    pub fn merchant(&self, merchant_code: impl Into<MerchantCode>) -> MerchantScope<'_> {
+        MerchantScope {
+            client: self,
+            merchant_code: merchant_code.into(),
+        }
+    }
+
+    /// Runs `op` once for every merchant reachable through `memberships`, with at most
+    /// `concurrency` operations in flight.
+    ///
+    /// Merchants are taken from [`Membership::merchant_code`] of active memberships; pending,
+    /// declined and revoked memberships and organization memberships are skipped, and a
+    /// merchant listed more than once is visited once. Results are returned
+    /// in membership order, paired with their merchant code, and one merchant failing does
+    /// not stop the others. A `concurrency` of zero is treated as one.
+    pub async fn for_each_merchant<'a, F, Fut, T>(
+        &'a self,
+        memberships: &[Membership],
+        concurrency: usize,
+        op: F,
+    ) -> Vec<(MerchantCode, Result<T>)>
+    where
+        F: Fn(MerchantScope<'a>) -> Fut,
+        Fut: Future<Output = Result<T>> + 'a,
+    {
+        let mut seen = HashSet::new();
+        let codes: Vec<MerchantCode> = memberships
+            .iter()
+            .filter(|m| m.status == MembershipStatus::Active)
+            .filter_map(Membership::merchant_code)
+            .filter(|code| seen.insert(code.clone()))
+            .collect();
+        stream::iter(codes)
+            .map(|code| {
+                let fut = op(self.merchant(&code));
+                async move { (code, fut.await) }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+}
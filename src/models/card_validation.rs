@@ -0,0 +1,410 @@
+use super::common::CardDetails;
+use super::enums::CardType;
+use chrono::{Datelike, NaiveDate, Utc};
+
+/// Card field that failed validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CardField {
+    Number,
+    ExpiryMonth,
+    ExpiryYear,
+    Cvv,
+    CardType,
+}
+
+impl std::fmt::Display for CardField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number => write!(f, "number"),
+            Self::ExpiryMonth => write!(f, "expiry_month"),
+            Self::ExpiryYear => write!(f, "expiry_year"),
+            Self::Cvv => write!(f, "cvv"),
+            Self::CardType => write!(f, "type"),
+        }
+    }
+}
+
+/// A single validation failure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardFieldError {
+    pub field: CardField,
+    pub message: String,
+}
+
+impl std::fmt::Display for CardFieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Every problem found by [`CardDetails::validate`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid card details: {}", .errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))]
+pub struct CardValidationError {
+    pub errors: Vec<CardFieldError>,
+}
+
+impl CardValidationError {
+    /// Whether the given field failed validation
+    pub fn has(&self, field: CardField) -> bool {
+        self.errors.iter().any(|e| e.field == field)
+    }
+}
+
+/// Check a card number with the Luhn (mod 10) algorithm.
+///
+/// Spaces and dashes are ignored; any other non-digit makes the number invalid.
+pub fn luhn_valid(number: &str) -> bool {
+    let digits = match card_digits(number) {
+        Some(d) if !d.is_empty() => d,
+        _ => return false,
+    };
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+/// Detect the card brand from the number's issuer identification prefix.
+pub fn detect_card_type(number: &str) -> Option<CardType> {
+    let digits = card_digits(number)?;
+    let prefix = |len: usize| -> Option<u32> {
+        if digits.len() < len {
+            return None;
+        }
+        Some(digits[..len].iter().fold(0, |acc, d| acc * 10 + d))
+    };
+    let in_range =
+        |len: usize, lo: u32, hi: u32| prefix(len).is_some_and(|p| (lo..=hi).contains(&p));
+    let any_of = |len: usize, values: &[u32]| prefix(len).is_some_and(|p| values.contains(&p));
+
+    // Brands carved out of other ranges go first
+    if any_of(
+        6,
+        &[
+            401178, 401179, 431274, 438935, 451416, 457393, 457631, 457632, 504175, 627780, 636297,
+            636368,
+        ],
+    ) || in_range(6, 506699, 506778)
+        || in_range(6, 509000, 509999)
+        || in_range(6, 650031, 650033)
+        || in_range(6, 650035, 650051)
+        || in_range(6, 650405, 650439)
+        || in_range(6, 650485, 650538)
+        || in_range(6, 650541, 650598)
+        || in_range(6, 650700, 650718)
+        || in_range(6, 650720, 650727)
+        || in_range(6, 650901, 650920)
+    {
+        return Some(CardType::Elo);
+    }
+    if any_of(6, &[606282]) || any_of(4, &[3841]) {
+        return Some(CardType::Hipercard);
+    }
+    if any_of(2, &[34, 37]) {
+        return Some(CardType::Amex);
+    }
+    if in_range(4, 3528, 3589) {
+        return Some(CardType::Jcb);
+    }
+    if in_range(3, 300, 305) || any_of(2, &[36, 38, 39]) {
+        return Some(CardType::Diners);
+    }
+    if any_of(4, &[6011])
+        || in_range(3, 644, 649)
+        || any_of(2, &[65])
+        || in_range(6, 622126, 622925)
+    {
+        return Some(CardType::Discover);
+    }
+    if any_of(2, &[62]) {
+        return Some(CardType::Cup);
+    }
+    if in_range(2, 51, 55) || in_range(4, 2221, 2720) {
+        return Some(CardType::Mastercard);
+    }
+    if any_of(2, &[50, 56, 57, 58, 67]) || any_of(4, &[6304, 6759, 6761, 6762, 6763]) {
+        return Some(CardType::Maestro);
+    }
+    if any_of(4, &[4026, 4508, 4844, 4913, 4917]) || any_of(6, &[417500]) {
+        return Some(CardType::VisaElectron);
+    }
+    if any_of(1, &[4]) {
+        return Some(CardType::Visa);
+    }
+    None
+}
+
+/// Valid card number lengths for a brand
+fn number_lengths(card_type: Option<&CardType>) -> &'static [usize] {
+    match card_type {
+        Some(CardType::Amex) => &[15],
+        Some(CardType::Diners) => &[14, 15, 16, 17, 18, 19],
+        Some(CardType::Visa) => &[13, 16, 19],
+        Some(CardType::VisaElectron) | Some(CardType::Mastercard) | Some(CardType::Elo) => &[16],
+        Some(CardType::Hipercard) => &[13, 16, 19],
+        Some(CardType::Maestro) => &[12, 13, 14, 15, 16, 17, 18, 19],
+        Some(CardType::Jcb) | Some(CardType::Discover) | Some(CardType::Cup) => &[16, 17, 18, 19],
+        _ => &[12, 13, 14, 15, 16, 17, 18, 19],
+    }
+}
+
+/// Valid CVV lengths for a brand
+fn cvv_lengths(card_type: Option<&CardType>) -> &'static [usize] {
+    match card_type {
+        Some(CardType::Amex) => &[4],
+        Some(_) => &[3],
+        None => &[3, 4],
+    }
+}
+
+/// Digits of a card number with spaces and dashes removed, or `None` if it
+/// contains anything else
+fn card_digits(number: &str) -> Option<Vec<u32>> {
+    number
+        .chars()
+        .filter(|c| *c != ' ' && *c != '-')
+        .map(|c| c.to_digit(10))
+        .collect()
+}
+
+impl CardDetails {
+    /// Brand detected from the card number, if recognised
+    pub fn detected_card_type(&self) -> Option<CardType> {
+        detect_card_type(self.number.expose_secret())
+    }
+
+    /// Fill in `card_type` from the card number if it is not set yet
+    pub fn with_detected_card_type(mut self) -> Self {
+        if self.card_type.is_none() {
+            self.card_type = self.detected_card_type();
+        }
+        self
+    }
+
+    /// Validate the card against today's date (UTC).
+    ///
+    /// This is opt-in: `process_checkout` does not call it, so the API stays the final
+    /// authority. Use it to catch typos before the round trip.
+    pub fn validate(&self) -> Result<(), CardValidationError> {
+        self.validate_at(Utc::now().date_naive())
+    }
+
+    /// Validate the card as of `today`, collecting every failing field.
+    pub fn validate_at(&self, today: NaiveDate) -> Result<(), CardValidationError> {
+        let mut errors = Vec::new();
+        let mut push = |field, message: String| errors.push(CardFieldError { field, message });
+
+        let detected = self.detected_card_type();
+        match card_digits(self.number.expose_secret()) {
+            None => push(CardField::Number, "must contain only digits".into()),
+            Some(digits) => {
+                if !number_lengths(detected.as_ref()).contains(&digits.len()) {
+                    push(
+                        CardField::Number,
+                        format!("invalid length {}", digits.len()),
+                    );
+                } else if !luhn_valid(self.number.expose_secret()) {
+                    push(CardField::Number, "failed checksum".into());
+                }
+            }
+        }
+
+        if let (Some(declared), Some(detected)) = (&self.card_type, &detected) {
+            if declared != detected && !declared.is_unknown() {
+                push(
+                    CardField::CardType,
+                    format!("declared {} but number is {}", declared, detected),
+                );
+            }
+        }
+
+        let month = self
+            .expiry_month
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|m| (1..=12).contains(m));
+        if month.is_none() {
+            push(CardField::ExpiryMonth, "must be between 01 and 12".into());
+        }
+        let year = parse_expiry_year(self.expiry_year.trim());
+        if year.is_none() {
+            push(
+                CardField::ExpiryYear,
+                "must be a two- or four-digit year".into(),
+            );
+        }
+        if let (Some(month), Some(year)) = (month, year) {
+            let current = (today.year(), today.month());
+            if (year, month) < current {
+                push(
+                    CardField::ExpiryYear,
+                    format!("card expired in {:02}/{}", month, year),
+                );
+            } else if year > today.year() + 20 {
+                push(
+                    CardField::ExpiryYear,
+                    "more than 20 years in the future".into(),
+                );
+            }
+        }
+
+        let cvv = self.cvv.expose_secret();
+        let cvv_ok = cvv.chars().all(|c| c.is_ascii_digit())
+            && cvv_lengths(self.card_type.as_ref().or(detected.as_ref())).contains(&cvv.len());
+        if !cvv_ok {
+            let expected = cvv_lengths(self.card_type.as_ref().or(detected.as_ref()))
+                .iter()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>()
+                .join(" or ");
+            push(CardField::Cvv, format!("must be {} digits", expected));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(CardValidationError { errors })
+        }
+    }
+}
+
+/// Accept four-digit years, and two-digit years as 20YY
+fn parse_expiry_year(year: &str) -> Option<i32> {
+    if !year.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    match year.len() {
+        4 => year.parse().ok(),
+        2 => year.parse::<i32>().ok().map(|y| 2000 + y),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()
+    }
+
+    #[test]
+    fn test_luhn() {
+        assert!(luhn_valid("4242424242424242"));
+        assert!(luhn_valid("4242 4242 4242 4242"));
+        assert!(!luhn_valid("4242424242424241"));
+        assert!(!luhn_valid("4242x42424242424"));
+        assert!(!luhn_valid(""));
+    }
+
+    #[test]
+    fn test_brand_detection() {
+        assert_eq!(detect_card_type("4242424242424242"), Some(CardType::Visa));
+        assert_eq!(
+            detect_card_type("5555555555554444"),
+            Some(CardType::Mastercard)
+        );
+        assert_eq!(
+            detect_card_type("2223003122003222"),
+            Some(CardType::Mastercard)
+        );
+        assert_eq!(detect_card_type("378282246310005"), Some(CardType::Amex));
+        assert_eq!(
+            detect_card_type("6011111111111117"),
+            Some(CardType::Discover)
+        );
+        assert_eq!(detect_card_type("3566002020360505"), Some(CardType::Jcb));
+        assert_eq!(detect_card_type("30569309025904"), Some(CardType::Diners));
+        assert_eq!(
+            detect_card_type("6759649826438453"),
+            Some(CardType::Maestro)
+        );
+        assert_eq!(
+            detect_card_type("6062825624254001"),
+            Some(CardType::Hipercard)
+        );
+        assert_eq!(detect_card_type("5067230000000000"), Some(CardType::Elo));
+        assert_eq!(detect_card_type("9999999999999999"), None);
+    }
+
+    #[test]
+    fn test_elo_bins_do_not_swallow_visa_ranges() {
+        assert_eq!(detect_card_type("4011110000000006"), Some(CardType::Visa));
+        assert_eq!(detect_card_type("4576000000000006"), Some(CardType::Visa));
+        assert_eq!(detect_card_type("4011780000000000"), Some(CardType::Elo));
+        assert_eq!(detect_card_type("4576310000000000"), Some(CardType::Elo));
+
+        let card =
+            CardDetails::new("4011110000000006", "12", "2027", "123").card_type(CardType::Visa);
+        assert!(card.validate_at(today()).is_ok());
+    }
+
+    #[test]
+    fn test_valid_card_passes() {
+        let card = CardDetails::new("4242424242424242", "12", "2027", "123");
+        assert!(card.validate_at(today()).is_ok());
+        assert_eq!(
+            card.with_detected_card_type().card_type,
+            Some(CardType::Visa)
+        );
+    }
+
+    #[test]
+    fn test_current_month_is_not_expired() {
+        let card = CardDetails::new("4242424242424242", "06", "2025", "123");
+        assert!(card.validate_at(today()).is_ok());
+    }
+
+    #[test]
+    fn test_reports_every_failing_field() {
+        let card = CardDetails::new("4242424242424241", "13", "2024", "12");
+        let err = card.validate_at(today()).unwrap_err();
+        assert!(err.has(CardField::Number));
+        assert!(err.has(CardField::ExpiryMonth));
+        assert!(err.has(CardField::Cvv));
+        assert!(!err.has(CardField::ExpiryYear));
+        assert!(err.to_string().contains("number: failed checksum"));
+    }
+
+    #[test]
+    fn test_expired_card() {
+        let card = CardDetails::new("4242424242424242", "05", "2025", "123");
+        let err = card.validate_at(today()).unwrap_err();
+        assert_eq!(err.errors.len(), 1);
+        assert_eq!(err.errors[0].field, CardField::ExpiryYear);
+    }
+
+    #[test]
+    fn test_amex_needs_four_digit_cvv() {
+        let card = CardDetails::new("378282246310005", "12", "2027", "123");
+        assert!(card.validate_at(today()).unwrap_err().has(CardField::Cvv));
+        let card = CardDetails::new("378282246310005", "12", "2027", "1234");
+        assert!(card.validate_at(today()).is_ok());
+    }
+
+    #[test]
+    fn test_declared_type_must_match_number() {
+        let card = CardDetails::new("4242424242424242", "12", "2027", "123")
+            .card_type(CardType::Mastercard);
+        assert!(card
+            .validate_at(today())
+            .unwrap_err()
+            .has(CardField::CardType));
+    }
+}
@@ -1,8 +1,16 @@
 use super::common::EmptyObject;
 use super::common::{CardDetails, Mandate, MandateRequest, PaymentInstrumentToken};
 use super::customer::PersonalDetails;
-use super::enums::{Amount, CheckoutId, CheckoutPurpose, CheckoutStatus, Currency, CustomerId, MerchantCode, PaymentType, TransactionId};
+use super::enums::{
+    Amount, CheckoutId, CheckoutPurpose, CheckoutStatus, Currency, CustomerId, MerchantCode,
+    PaymentType, TransactionId,
+};
+use super::money::Money;
+use super::tax::VatBreakdown;
 use super::transaction::Transaction;
+use super::wallet::{ApplePayToken, GooglePayToken};
+use crate::utils::{impl_extra_fields, UnknownFields};
+use crate::{Error, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -39,6 +47,24 @@ pub struct Checkout {
     pub redirect_url: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payment_instrument: Option<PaymentInstrumentToken>,
+    /// Boleto voucher details (Boleto payments only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boleto: Option<BoletoDetails>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
+}
+
+impl_extra_fields!(Checkout);
+
+/// Boleto voucher returned after processing a Boleto checkout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoletoDetails {
+    /// Barcode digits the customer can pay with in their banking app
+    pub barcode: String,
+    /// Link to the printable voucher PDF
+    pub url: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,6 +105,36 @@ impl CreateCheckoutRequest {
         }
     }
 
+    /// Create a request charging the gross total of a VAT breakdown
+    ///
+    /// The checkout endpoint does not take product lines, so only the total is sent.
+    pub fn from_vat_breakdown(
+        checkout_reference: impl Into<String>,
+        breakdown: &VatBreakdown,
+        merchant_code: impl Into<MerchantCode>,
+    ) -> Self {
+        Self::from_money(
+            checkout_reference,
+            breakdown.total.gross.clone(),
+            merchant_code,
+        )
+    }
+
+    /// Create a request charging a validated money value
+    pub fn from_money(
+        checkout_reference: impl Into<String>,
+        money: Money,
+        merchant_code: impl Into<MerchantCode>,
+    ) -> Self {
+        let (amount, currency) = money.into_parts();
+        Self::new(checkout_reference, amount, currency, merchant_code)
+    }
+
+    /// The amount and currency as a validated money value
+    pub fn money(&self) -> Result<Money> {
+        Ok(Money::new(self.amount, self.currency.clone())?)
+    }
+
     pub fn description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
         self
@@ -138,6 +194,19 @@ pub struct ProcessCheckoutRequest {
     /// Mandate for recurrent payments
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mandate: Option<MandateRequest>,
+    /// Six-digit code from the customer's banking app (BLIK payments only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blik_code: Option<String>,
+    /// BIC of the customer's bank (iDEAL payments only). Without it the customer
+    /// picks their bank on the iDEAL page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    /// Apple Pay payment token (Apple Pay payments only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apple_pay: Option<ApplePayToken>,
+    /// Google Pay payment data (Google Pay payments only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub google_pay: Option<GooglePayToken>,
 }
 
 impl Default for PaymentType {
@@ -163,6 +232,60 @@ impl ProcessCheckoutRequest {
         }
     }
 
+    /// Boleto payment (Brazil). The payer's name, email, tax ID (CPF/CNPJ) and
+    /// address are required to issue the voucher.
+    pub fn boleto(personal_details: PersonalDetails) -> Self {
+        Self {
+            payment_type: PaymentType::Boleto,
+            personal_details: Some(personal_details),
+            ..Default::default()
+        }
+    }
+
+    /// iDEAL payment (Netherlands). The customer is redirected to their bank; set
+    /// [`issuer`](Self::issuer) to skip the bank selection.
+    pub fn ideal() -> Self {
+        Self {
+            payment_type: PaymentType::Ideal,
+            ..Default::default()
+        }
+    }
+
+    /// Bancontact payment (Belgium). The customer is redirected to their bank.
+    pub fn bancontact() -> Self {
+        Self {
+            payment_type: PaymentType::Bancontact,
+            ..Default::default()
+        }
+    }
+
+    /// BLIK payment (Poland) using the code shown in the customer's banking app.
+    pub fn blik(code: impl Into<String>) -> Self {
+        Self {
+            payment_type: PaymentType::Blik,
+            blik_code: Some(code.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Apple Pay payment with the token from the `onpaymentauthorized` event
+    pub fn apple_pay(token: ApplePayToken) -> Self {
+        Self {
+            payment_type: PaymentType::ApplePay,
+            apple_pay: Some(token),
+            ..Default::default()
+        }
+    }
+
+    /// Google Pay payment with the payment data returned by `loadPaymentData`
+    pub fn google_pay(token: GooglePayToken) -> Self {
+        Self {
+            payment_type: PaymentType::GooglePay,
+            google_pay: Some(token),
+            ..Default::default()
+        }
+    }
+
     pub fn installments(mut self, count: i32) -> Self {
         self.installments = Some(count);
         self
@@ -172,6 +295,92 @@ impl ProcessCheckoutRequest {
         self.customer_id = Some(id.into());
         self
     }
+
+    pub fn personal_details(mut self, details: PersonalDetails) -> Self {
+        self.personal_details = Some(details);
+        self
+    }
+
+    /// Send an iDEAL customer straight to their bank, identified by BIC
+    pub fn issuer(mut self, bic: impl Into<String>) -> Self {
+        self.issuer = Some(bic.into());
+        self
+    }
+
+    /// Check that the fields required by the chosen payment method are present.
+    pub fn validate(&self) -> Result<()> {
+        match self.payment_type {
+            PaymentType::Card => {
+                if self.card.is_none() && self.token.is_none() {
+                    return Err(Error::InvalidInput(
+                        "card payments need card details or a token".into(),
+                    ));
+                }
+            }
+            PaymentType::Boleto => {
+                let details = self.personal_details.as_ref().ok_or_else(|| {
+                    Error::InvalidInput("boleto payments need personal_details".into())
+                })?;
+                let mut missing = Vec::new();
+                if details.first_name.is_none() {
+                    missing.push("first_name");
+                }
+                if details.last_name.is_none() {
+                    missing.push("last_name");
+                }
+                if details.email.is_none() {
+                    missing.push("email");
+                }
+                if details.tax_id.is_none() {
+                    missing.push("tax_id");
+                }
+                if details.address.is_none() {
+                    missing.push("address");
+                }
+                if !missing.is_empty() {
+                    return Err(Error::InvalidInput(format!(
+                        "boleto payments need personal_details.{}",
+                        missing.join(", personal_details.")
+                    )));
+                }
+            }
+            PaymentType::Blik => {
+                let valid = self
+                    .blik_code
+                    .as_deref()
+                    .is_some_and(|c| c.len() == 6 && c.bytes().all(|b| b.is_ascii_digit()));
+                if !valid {
+                    return Err(Error::InvalidInput(
+                        "BLIK payments need a six-digit blik_code".into(),
+                    ));
+                }
+            }
+            PaymentType::ApplePay => {
+                if !self
+                    .apple_pay
+                    .as_ref()
+                    .is_some_and(ApplePayToken::is_present)
+                {
+                    return Err(Error::InvalidInput(
+                        "Apple Pay payments need an apple_pay token".into(),
+                    ));
+                }
+            }
+            PaymentType::GooglePay => {
+                if !self
+                    .google_pay
+                    .as_ref()
+                    .is_some_and(GooglePayToken::is_present)
+                {
+                    return Err(Error::InvalidInput(
+                        "Google Pay payments need google_pay payment data".into(),
+                    ));
+                }
+            }
+            PaymentType::Ideal | PaymentType::Bancontact | PaymentType::Unknown(_) => {}
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -200,11 +409,40 @@ pub struct AvailablePaymentMethod {
     pub id: String,
 }
 
+impl AvailablePaymentMethod {
+    /// The payment type this method corresponds to (`Unknown` if not modelled)
+    pub fn payment_type(&self) -> PaymentType {
+        PaymentType::from_id(&self.id)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AvailablePaymentMethodsResponse {
     pub available_payment_methods: Vec<AvailablePaymentMethod>,
 }
 
+impl AvailablePaymentMethodsResponse {
+    /// Whether the given payment type is offered
+    pub fn supports(&self, payment_type: PaymentType) -> bool {
+        self.available_payment_methods
+            .iter()
+            .any(|m| m.id.eq_ignore_ascii_case(payment_type.as_str()))
+    }
+
+    /// Like [`supports`](Self::supports), but returns `Error::InvalidInput` if the
+    /// payment type is not offered
+    pub fn ensure_supported(&self, payment_type: PaymentType) -> Result<()> {
+        if self.supports(payment_type.clone()) {
+            Ok(())
+        } else {
+            Err(Error::InvalidInput(format!(
+                "payment method '{}' is not available for this merchant",
+                payment_type
+            )))
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ProcessCheckoutResponse {
@@ -214,6 +452,36 @@ pub enum ProcessCheckoutResponse {
     Accepted(CheckoutAccepted),
 }
 
+impl ProcessCheckoutResponse {
+    /// What needs to happen next for the customer to complete the payment.
+    pub fn action(&self) -> PaymentAction {
+        match self {
+            Self::Accepted(accepted) => PaymentAction::Redirect(accepted.next_step.clone()),
+            Self::Success(checkout) => match checkout.boleto {
+                Some(ref boleto) => PaymentAction::Boleto(boleto.clone()),
+                None if checkout.status == CheckoutStatus::Pending => {
+                    PaymentAction::AwaitingConfirmation
+                }
+                None => PaymentAction::None,
+            },
+        }
+    }
+}
+
+/// Follow-up required after processing a checkout
+#[derive(Debug, Clone)]
+pub enum PaymentAction {
+    /// Nothing to do; the checkout status is final
+    None,
+    /// Send the customer to this URL (iDEAL and Bancontact bank pages, 3DS)
+    Redirect(NextStep),
+    /// Show the Boleto voucher to the customer; the checkout is paid when they pay it
+    Boleto(BoletoDetails),
+    /// The customer must approve the payment (e.g. BLIK in their banking app);
+    /// poll the checkout or wait for a webhook
+    AwaitingConfirmation,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckoutAccepted {
     pub next_step: NextStep,
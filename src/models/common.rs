@@ -1,21 +1,165 @@
 use super::enums::{CardType, MandateStatus, MandateType};
+use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 
 // A helper for empty objects {}
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct EmptyObject {}
 
+/// Primary account number (PAN) of a payment card.
+///
+/// The digits are held in a [`SecretString`] that is zeroized on drop. `Debug` and
+/// `Display` only show the BIN (first six digits) and the last four; the full number
+/// is emitted solely by `Serialize`, i.e. in the request body sent to SumUp.
+#[derive(Clone)]
+pub struct CardNumber(SecretString);
+
+impl CardNumber {
+    pub fn new(number: impl Into<String>) -> Self {
+        Self(SecretString::new(number.into()))
+    }
+
+    /// Access the full card number. Avoid logging or storing the result.
+    pub fn expose_secret(&self) -> &str {
+        self.0.expose_secret()
+    }
+
+    /// Card number with everything except the BIN and last four digits masked,
+    /// e.g. `424242******4242`
+    pub fn masked(&self) -> String {
+        let digits: Vec<char> = self
+            .expose_secret()
+            .chars()
+            .filter(|c| c.is_ascii_digit())
+            .collect();
+        if digits.len() < 13 {
+            return "*".repeat(digits.len());
+        }
+        let (bin, rest) = digits.split_at(6);
+        let (middle, last4) = rest.split_at(rest.len() - 4);
+        format!(
+            "{}{}{}",
+            bin.iter().collect::<String>(),
+            "*".repeat(middle.len()),
+            last4.iter().collect::<String>()
+        )
+    }
+
+    /// Last four digits, as shown on receipts
+    pub fn last_4_digits(&self) -> String {
+        let digits: Vec<char> = self
+            .expose_secret()
+            .chars()
+            .filter(|c| c.is_ascii_digit())
+            .collect();
+        digits[digits.len().saturating_sub(4)..].iter().collect()
+    }
+}
+
+impl std::fmt::Debug for CardNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CardNumber({})", self.masked())
+    }
+}
+
+impl std::fmt::Display for CardNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.masked())
+    }
+}
+
+impl From<String> for CardNumber {
+    fn from(s: String) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<&str> for CardNumber {
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl Serialize for CardNumber {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.expose_secret())
+    }
+}
+
+impl<'de> Deserialize<'de> for CardNumber {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// Card verification code (CVV/CVC), zeroized on drop and never shown by `Debug`
+/// or `Display`. Only `Serialize` emits the value.
+#[derive(Clone)]
+pub struct CardSecurityCode(SecretString);
+
+impl CardSecurityCode {
+    pub fn new(code: impl Into<String>) -> Self {
+        Self(SecretString::new(code.into()))
+    }
+
+    /// Access the code. Avoid logging or storing the result.
+    pub fn expose_secret(&self) -> &str {
+        self.0.expose_secret()
+    }
+}
+
+impl std::fmt::Debug for CardSecurityCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CardSecurityCode([REDACTED])")
+    }
+}
+
+impl std::fmt::Display for CardSecurityCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "***")
+    }
+}
+
+impl From<String> for CardSecurityCode {
+    fn from(s: String) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<&str> for CardSecurityCode {
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl Serialize for CardSecurityCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.expose_secret())
+    }
+}
+
+impl<'de> Deserialize<'de> for CardSecurityCode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
 /// Card details for ProcessCheckout request (per OpenAPI spec)
+///
+/// The card number and CVV are stored as secrets: `{:?}` prints
+/// `CardNumber(424242******4242)` and `CardSecurityCode([REDACTED])`. Serializing the
+/// struct (as done when sending the request) includes the full values, so never log
+/// the serialized form.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CardDetails {
     /// Card number
-    pub number: String,
+    pub number: CardNumber,
     /// Expiry month (MM format)
     pub expiry_month: String,
     /// Expiry year (YYYY format)
     pub expiry_year: String,
     /// CVV security code
-    pub cvv: String,
+    pub cvv: CardSecurityCode,
     /// Cardholder name
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
@@ -36,10 +180,10 @@ impl CardDetails {
         cvv: impl Into<String>,
     ) -> Self {
         Self {
-            number: number.into(),
+            number: CardNumber::new(number),
             expiry_month: expiry_month.into(),
             expiry_year: expiry_year.into(),
-            cvv: cvv.into(),
+            cvv: CardSecurityCode::new(cvv),
             name: None,
             card_type: None,
             zip_code: None,
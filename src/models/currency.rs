@@ -0,0 +1,408 @@
+use super::enums::{CountryCode, Currency};
+use crate::{Error, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Minor-unit exponents of active ISO 4217 currencies, sorted by code.
+///
+/// COP and HUF keep their ISO exponent of 2 even though centavos and fillér are no
+/// longer in circulation, so `1000.50 HUF` is a valid amount.
+#[rustfmt::skip]
+const ISO_4217_MINOR_UNITS: &[(&str, u32)] = &[
+    ("AED", 2), ("AFN", 2), ("ALL", 2), ("AMD", 2), ("ANG", 2), ("AOA", 2), ("ARS", 2),
+    ("AUD", 2), ("AWG", 2), ("AZN", 2), ("BAM", 2), ("BBD", 2), ("BDT", 2), ("BGN", 2),
+    ("BHD", 3), ("BIF", 0), ("BMD", 2), ("BND", 2), ("BOB", 2), ("BOV", 2), ("BRL", 2),
+    ("BSD", 2), ("BTN", 2), ("BWP", 2), ("BYN", 2), ("BZD", 2), ("CAD", 2), ("CDF", 2),
+    ("CHE", 2), ("CHF", 2), ("CHW", 2), ("CLF", 4), ("CLP", 0), ("CNY", 2), ("COP", 2),
+    ("COU", 2), ("CRC", 2), ("CUP", 2), ("CVE", 2), ("CZK", 2), ("DJF", 0), ("DKK", 2),
+    ("DOP", 2), ("DZD", 2), ("EGP", 2), ("ERN", 2), ("ETB", 2), ("EUR", 2), ("FJD", 2),
+    ("FKP", 2), ("GBP", 2), ("GEL", 2), ("GHS", 2), ("GIP", 2), ("GMD", 2), ("GNF", 0),
+    ("GTQ", 2), ("GYD", 2), ("HKD", 2), ("HNL", 2), ("HTG", 2), ("HUF", 2), ("IDR", 2),
+    ("ILS", 2), ("INR", 2), ("IQD", 3), ("IRR", 2), ("ISK", 0), ("JMD", 2), ("JOD", 3),
+    ("JPY", 0), ("KES", 2), ("KGS", 2), ("KHR", 2), ("KMF", 0), ("KPW", 2), ("KRW", 0),
+    ("KWD", 3), ("KYD", 2), ("KZT", 2), ("LAK", 2), ("LBP", 2), ("LKR", 2), ("LRD", 2),
+    ("LSL", 2), ("LYD", 3), ("MAD", 2), ("MDL", 2), ("MGA", 2), ("MKD", 2), ("MMK", 2),
+    ("MNT", 2), ("MOP", 2), ("MRU", 2), ("MUR", 2), ("MVR", 2), ("MWK", 2), ("MXN", 2),
+    ("MXV", 2), ("MYR", 2), ("MZN", 2), ("NAD", 2), ("NGN", 2), ("NIO", 2), ("NOK", 2),
+    ("NPR", 2), ("NZD", 2), ("OMR", 3), ("PAB", 2), ("PEN", 2), ("PGK", 2), ("PHP", 2),
+    ("PKR", 2), ("PLN", 2), ("PYG", 0), ("QAR", 2), ("RON", 2), ("RSD", 2), ("RUB", 2),
+    ("RWF", 0), ("SAR", 2), ("SBD", 2), ("SCR", 2), ("SDG", 2), ("SEK", 2), ("SGD", 2),
+    ("SHP", 2), ("SLE", 2), ("SOS", 2), ("SRD", 2), ("SSP", 2), ("STN", 2), ("SVC", 2),
+    ("SYP", 2), ("SZL", 2), ("THB", 2), ("TJS", 2), ("TMT", 2), ("TND", 3), ("TOP", 2),
+    ("TRY", 2), ("TTD", 2), ("TWD", 2), ("TZS", 2), ("UAH", 2), ("UGX", 0), ("USD", 2),
+    ("USN", 2), ("UYI", 0), ("UYU", 2), ("UYW", 4), ("UZS", 2), ("VED", 2), ("VES", 2),
+    ("VND", 0), ("VUV", 0), ("WST", 2), ("XAF", 0), ("XCD", 2), ("XCG", 2), ("XOF", 0),
+    ("XPF", 0), ("YER", 2), ("ZAR", 2), ("ZMW", 2), ("ZWG", 2),
+];
+
+/// ISO 3166-1 alpha-2 country codes with the currency in general use there, sorted by code
+#[rustfmt::skip]
+const ISO_3166_COUNTRIES: &[(&str, Option<&str>)] = &[
+    ("AD", Some("EUR")), ("AE", Some("AED")), ("AF", Some("AFN")), ("AG", Some("XCD")),
+    ("AI", Some("XCD")), ("AL", Some("ALL")), ("AM", Some("AMD")), ("AO", Some("AOA")),
+    ("AQ", None), ("AR", Some("ARS")), ("AS", Some("USD")), ("AT", Some("EUR")),
+    ("AU", Some("AUD")), ("AW", Some("AWG")), ("AX", Some("EUR")), ("AZ", Some("AZN")),
+    ("BA", Some("BAM")), ("BB", Some("BBD")), ("BD", Some("BDT")), ("BE", Some("EUR")),
+    ("BF", Some("XOF")), ("BG", Some("EUR")), ("BH", Some("BHD")), ("BI", Some("BIF")),
+    ("BJ", Some("XOF")), ("BL", Some("EUR")), ("BM", Some("BMD")), ("BN", Some("BND")),
+    ("BO", Some("BOB")), ("BQ", Some("USD")), ("BR", Some("BRL")), ("BS", Some("BSD")),
+    ("BT", Some("BTN")), ("BV", Some("NOK")), ("BW", Some("BWP")), ("BY", Some("BYN")),
+    ("BZ", Some("BZD")), ("CA", Some("CAD")), ("CC", Some("AUD")), ("CD", Some("CDF")),
+    ("CF", Some("XAF")), ("CG", Some("XAF")), ("CH", Some("CHF")), ("CI", Some("XOF")),
+    ("CK", Some("NZD")), ("CL", Some("CLP")), ("CM", Some("XAF")), ("CN", Some("CNY")),
+    ("CO", Some("COP")), ("CR", Some("CRC")), ("CU", Some("CUP")), ("CV", Some("CVE")),
+    ("CW", Some("XCG")), ("CX", Some("AUD")), ("CY", Some("EUR")), ("CZ", Some("CZK")),
+    ("DE", Some("EUR")), ("DJ", Some("DJF")), ("DK", Some("DKK")), ("DM", Some("XCD")),
+    ("DO", Some("DOP")), ("DZ", Some("DZD")), ("EC", Some("USD")), ("EE", Some("EUR")),
+    ("EG", Some("EGP")), ("EH", Some("MAD")), ("ER", Some("ERN")), ("ES", Some("EUR")),
+    ("ET", Some("ETB")), ("FI", Some("EUR")), ("FJ", Some("FJD")), ("FK", Some("FKP")),
+    ("FM", Some("USD")), ("FO", Some("DKK")), ("FR", Some("EUR")), ("GA", Some("XAF")),
+    ("GB", Some("GBP")), ("GD", Some("XCD")), ("GE", Some("GEL")), ("GF", Some("EUR")),
+    ("GG", Some("GBP")), ("GH", Some("GHS")), ("GI", Some("GIP")), ("GL", Some("DKK")),
+    ("GM", Some("GMD")), ("GN", Some("GNF")), ("GP", Some("EUR")), ("GQ", Some("XAF")),
+    ("GR", Some("EUR")), ("GS", Some("GBP")), ("GT", Some("GTQ")), ("GU", Some("USD")),
+    ("GW", Some("XOF")), ("GY", Some("GYD")), ("HK", Some("HKD")), ("HM", Some("AUD")),
+    ("HN", Some("HNL")), ("HR", Some("EUR")), ("HT", Some("HTG")), ("HU", Some("HUF")),
+    ("ID", Some("IDR")), ("IE", Some("EUR")), ("IL", Some("ILS")), ("IM", Some("GBP")),
+    ("IN", Some("INR")), ("IO", Some("USD")), ("IQ", Some("IQD")), ("IR", Some("IRR")),
+    ("IS", Some("ISK")), ("IT", Some("EUR")), ("JE", Some("GBP")), ("JM", Some("JMD")),
+    ("JO", Some("JOD")), ("JP", Some("JPY")), ("KE", Some("KES")), ("KG", Some("KGS")),
+    ("KH", Some("KHR")), ("KI", Some("AUD")), ("KM", Some("KMF")), ("KN", Some("XCD")),
+    ("KP", Some("KPW")), ("KR", Some("KRW")), ("KW", Some("KWD")), ("KY", Some("KYD")),
+    ("KZ", Some("KZT")), ("LA", Some("LAK")), ("LB", Some("LBP")), ("LC", Some("XCD")),
+    ("LI", Some("CHF")), ("LK", Some("LKR")), ("LR", Some("LRD")), ("LS", Some("LSL")),
+    ("LT", Some("EUR")), ("LU", Some("EUR")), ("LV", Some("EUR")), ("LY", Some("LYD")),
+    ("MA", Some("MAD")), ("MC", Some("EUR")), ("MD", Some("MDL")), ("ME", Some("EUR")),
+    ("MF", Some("EUR")), ("MG", Some("MGA")), ("MH", Some("USD")), ("MK", Some("MKD")),
+    ("ML", Some("XOF")), ("MM", Some("MMK")), ("MN", Some("MNT")), ("MO", Some("MOP")),
+    ("MP", Some("USD")), ("MQ", Some("EUR")), ("MR", Some("MRU")), ("MS", Some("XCD")),
+    ("MT", Some("EUR")), ("MU", Some("MUR")), ("MV", Some("MVR")), ("MW", Some("MWK")),
+    ("MX", Some("MXN")), ("MY", Some("MYR")), ("MZ", Some("MZN")), ("NA", Some("NAD")),
+    ("NC", Some("XPF")), ("NE", Some("XOF")), ("NF", Some("AUD")), ("NG", Some("NGN")),
+    ("NI", Some("NIO")), ("NL", Some("EUR")), ("NO", Some("NOK")), ("NP", Some("NPR")),
+    ("NR", Some("AUD")), ("NU", Some("NZD")), ("NZ", Some("NZD")), ("OM", Some("OMR")),
+    ("PA", Some("PAB")), ("PE", Some("PEN")), ("PF", Some("XPF")), ("PG", Some("PGK")),
+    ("PH", Some("PHP")), ("PK", Some("PKR")), ("PL", Some("PLN")), ("PM", Some("EUR")),
+    ("PN", Some("NZD")), ("PR", Some("USD")), ("PS", Some("ILS")), ("PT", Some("EUR")),
+    ("PW", Some("USD")), ("PY", Some("PYG")), ("QA", Some("QAR")), ("RE", Some("EUR")),
+    ("RO", Some("RON")), ("RS", Some("RSD")), ("RU", Some("RUB")), ("RW", Some("RWF")),
+    ("SA", Some("SAR")), ("SB", Some("SBD")), ("SC", Some("SCR")), ("SD", Some("SDG")),
+    ("SE", Some("SEK")), ("SG", Some("SGD")), ("SH", Some("SHP")), ("SI", Some("EUR")),
+    ("SJ", Some("NOK")), ("SK", Some("EUR")), ("SL", Some("SLE")), ("SM", Some("EUR")),
+    ("SN", Some("XOF")), ("SO", Some("SOS")), ("SR", Some("SRD")), ("SS", Some("SSP")),
+    ("ST", Some("STN")), ("SV", Some("USD")), ("SX", Some("XCG")), ("SY", Some("SYP")),
+    ("SZ", Some("SZL")), ("TC", Some("USD")), ("TD", Some("XAF")), ("TF", Some("EUR")),
+    ("TG", Some("XOF")), ("TH", Some("THB")), ("TJ", Some("TJS")), ("TK", Some("NZD")),
+    ("TL", Some("USD")), ("TM", Some("TMT")), ("TN", Some("TND")), ("TO", Some("TOP")),
+    ("TR", Some("TRY")), ("TT", Some("TTD")), ("TV", Some("AUD")), ("TW", Some("TWD")),
+    ("TZ", Some("TZS")), ("UA", Some("UAH")), ("UG", Some("UGX")), ("UM", Some("USD")),
+    ("US", Some("USD")), ("UY", Some("UYU")), ("UZ", Some("UZS")), ("VA", Some("EUR")),
+    ("VC", Some("XCD")), ("VE", Some("VES")), ("VG", Some("USD")), ("VI", Some("USD")),
+    ("VN", Some("VND")), ("VU", Some("VUV")), ("WF", Some("XPF")), ("WS", Some("WST")),
+    ("YE", Some("YER")), ("YT", Some("EUR")), ("ZA", Some("ZAR")), ("ZM", Some("ZMW")),
+    ("ZW", Some("ZWG")),
+];
+
+/// Look up the minor-unit exponent of an ISO 4217 code (case-sensitive, upper case)
+pub(crate) fn iso_4217_minor_units(code: &str) -> Option<u32> {
+    ISO_4217_MINOR_UNITS
+        .binary_search_by(|(c, _)| (*c).cmp(code))
+        .ok()
+        .map(|i| ISO_4217_MINOR_UNITS[i].1)
+}
+
+/// Look up an ISO 3166-1 alpha-2 code (case-sensitive, upper case), returning the
+/// currency in general use there, if any
+pub(crate) fn iso_3166_country(code: &str) -> Option<Option<&'static str>> {
+    ISO_3166_COUNTRIES
+        .binary_search_by(|(c, _)| (*c).cmp(code))
+        .ok()
+        .map(|i| ISO_3166_COUNTRIES[i].1)
+}
+
+/// Validated ISO 4217 currency code.
+///
+/// The currencies SumUp merchant accounts can be held in have their own variants; any
+/// other valid ISO 4217 code is kept in `Other`. Unlike [`Currency`], which accepts any
+/// string so API responses always deserialize, building one from a string fails for
+/// codes that are not ISO 4217.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CurrencyCode {
+    Eur,
+    Gbp,
+    Usd,
+    Chf,
+    Pln,
+    /// Bulgarian lev, replaced by the euro on 1 January 2026; kept for older transactions
+    /// and payouts, but no longer [supported](Self::is_supported)
+    Bgn,
+    Czk,
+    Huf,
+    Ron,
+    Sek,
+    Nok,
+    Dkk,
+    Brl,
+    Clp,
+    Cop,
+    /// A valid ISO 4217 code without a dedicated variant
+    Other(String),
+}
+
+impl CurrencyCode {
+    /// Currencies SumUp merchant accounts can be held in
+    pub const SUPPORTED: [CurrencyCode; 14] = [
+        Self::Eur,
+        Self::Gbp,
+        Self::Usd,
+        Self::Chf,
+        Self::Pln,
+        Self::Czk,
+        Self::Huf,
+        Self::Ron,
+        Self::Sek,
+        Self::Nok,
+        Self::Dkk,
+        Self::Brl,
+        Self::Clp,
+        Self::Cop,
+    ];
+
+    /// Validate an ISO 4217 code (surrounding whitespace and lower case are accepted)
+    pub fn parse(code: &str) -> Result<Self> {
+        let code = code.trim().to_ascii_uppercase();
+        if iso_4217_minor_units(&code).is_none() {
+            return Err(Error::InvalidInput(format!(
+                "'{}' is not an ISO 4217 currency code",
+                code
+            )));
+        }
+        Ok(match code.as_str() {
+            "EUR" => Self::Eur,
+            "GBP" => Self::Gbp,
+            "USD" => Self::Usd,
+            "CHF" => Self::Chf,
+            "PLN" => Self::Pln,
+            "BGN" => Self::Bgn,
+            "CZK" => Self::Czk,
+            "HUF" => Self::Huf,
+            "RON" => Self::Ron,
+            "SEK" => Self::Sek,
+            "NOK" => Self::Nok,
+            "DKK" => Self::Dkk,
+            "BRL" => Self::Brl,
+            "CLP" => Self::Clp,
+            "COP" => Self::Cop,
+            _ => Self::Other(code),
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Eur => Currency::EUR,
+            Self::Gbp => Currency::GBP,
+            Self::Usd => Currency::USD,
+            Self::Chf => Currency::CHF,
+            Self::Pln => Currency::PLN,
+            Self::Bgn => Currency::BGN,
+            Self::Czk => Currency::CZK,
+            Self::Huf => Currency::HUF,
+            Self::Ron => Currency::RON,
+            Self::Sek => Currency::SEK,
+            Self::Nok => Currency::NOK,
+            Self::Dkk => Currency::DKK,
+            Self::Brl => Currency::BRL,
+            Self::Clp => Currency::CLP,
+            Self::Cop => Currency::COP,
+            Self::Other(code) => code,
+        }
+    }
+
+    /// Whether SumUp merchant accounts can be held in this currency
+    pub fn is_supported(&self) -> bool {
+        Self::SUPPORTED.contains(self)
+    }
+
+    /// Number of decimal places of the currency's minor unit
+    pub fn minor_units(&self) -> u32 {
+        iso_4217_minor_units(self.as_str()).unwrap_or(2)
+    }
+
+    /// The currency in general use in a country, if it has one
+    pub fn for_country(country: &CountryCode) -> Option<Self> {
+        country.default_currency()
+    }
+}
+
+impl std::fmt::Display for CurrencyCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for CurrencyCode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&str> for CurrencyCode {
+    type Error = Error;
+
+    fn try_from(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for CurrencyCode {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        Self::parse(&s)
+    }
+}
+
+impl TryFrom<&Currency> for CurrencyCode {
+    type Error = Error;
+
+    fn try_from(currency: &Currency) -> Result<Self> {
+        Self::parse(currency.as_str())
+    }
+}
+
+impl TryFrom<Currency> for CurrencyCode {
+    type Error = Error;
+
+    fn try_from(currency: Currency) -> Result<Self> {
+        Self::parse(currency.as_str())
+    }
+}
+
+impl From<CurrencyCode> for Currency {
+    fn from(code: CurrencyCode) -> Self {
+        Currency::new(code.as_str())
+    }
+}
+
+impl From<&CurrencyCode> for Currency {
+    fn from(code: &CurrencyCode) -> Self {
+        Currency::new(code.as_str())
+    }
+}
+
+impl Serialize for CurrencyCode {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for CurrencyCode {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let code = String::deserialize(deserializer)?;
+        Self::parse(&code).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_are_sorted() {
+        assert!(ISO_4217_MINOR_UNITS.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(ISO_3166_COUNTRIES.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(ISO_3166_COUNTRIES.len(), 249);
+    }
+
+    #[test]
+    fn test_known_exponents() {
+        for (code, exponent) in [
+            ("EUR", 2),
+            ("COP", 2),
+            ("HUF", 2),
+            ("JPY", 0),
+            ("CLP", 0),
+            ("KRW", 0),
+            ("BHD", 3),
+            ("KWD", 3),
+            ("CLF", 4),
+        ] {
+            assert_eq!(Currency::new(code).minor_units(), Some(exponent), "{code}");
+        }
+    }
+
+    #[test]
+    fn test_every_country_currency_is_iso_4217() {
+        for (country, currency) in ISO_3166_COUNTRIES {
+            if let Some(currency) = currency {
+                assert!(
+                    iso_4217_minor_units(currency).is_some(),
+                    "{}: {}",
+                    country,
+                    currency
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_currency_code() {
+        assert_eq!(CurrencyCode::parse(" eur ").unwrap(), CurrencyCode::Eur);
+        assert_eq!(
+            CurrencyCode::parse("JPY").unwrap(),
+            CurrencyCode::Other("JPY".into())
+        );
+        assert!(!CurrencyCode::parse("JPY").unwrap().is_supported());
+        assert!(CurrencyCode::parse("EURO").is_err());
+        assert!(CurrencyCode::try_from("XXX").is_err());
+        assert!(CurrencyCode::SUPPORTED
+            .iter()
+            .all(|c| c.is_supported() && CurrencyCode::parse(c.as_str()).unwrap() == *c));
+    }
+
+    #[test]
+    fn test_serde_validates() {
+        assert_eq!(
+            serde_json::to_string(&CurrencyCode::Clp).unwrap(),
+            "\"CLP\""
+        );
+        assert_eq!(
+            serde_json::from_str::<CurrencyCode>("\"GBP\"").unwrap(),
+            CurrencyCode::Gbp
+        );
+        assert!(serde_json::from_str::<CurrencyCode>("\"ZZZ\"").is_err());
+    }
+
+    #[test]
+    fn test_country_default_currency() {
+        let currency = |c: &str| CountryCode::parse(c).unwrap().default_currency();
+        assert_eq!(currency("de"), Some(CurrencyCode::Eur));
+        assert_eq!(currency("BR"), Some(CurrencyCode::Brl));
+        assert_eq!(currency("CL"), Some(CurrencyCode::Clp));
+        assert_eq!(currency("US"), Some(CurrencyCode::Usd));
+        // Bulgaria uses the euro since 2026, so the lev is no longer supported
+        assert_eq!(currency("BG"), Some(CurrencyCode::Eur));
+        assert!(!CurrencyCode::Bgn.is_supported());
+        assert_eq!(currency("AQ"), None);
+        assert!(CountryCode::parse("UK").is_err());
+        assert!(CountryCode::parse("DEU").is_err());
+        assert_eq!(CountryCode::try_from("fr").unwrap().as_str(), "FR");
+        assert!(CountryCode::try_from("XX".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_validated_currency() {
+        assert_eq!(Currency::parse("gbp").unwrap(), Currency::new("GBP"));
+        assert!(Currency::parse("GB").is_err());
+        assert!("EURO".parse::<Currency>().is_err());
+        assert!(Currency::new("EURO").validate().is_err());
+        assert!(Currency::new(Currency::EUR).validate().is_ok());
+    }
+}
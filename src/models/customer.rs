@@ -1,3 +1,4 @@
+use crate::utils::{impl_extra_fields, UnknownFields};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -6,8 +7,14 @@ pub struct Customer {
     pub customer_id: String,
     #[serde(default)]
     pub personal_details: Option<PersonalDetails>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
 }
 
+impl_extra_fields!(Customer, PaymentInstrument);
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PersonalDetails {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -83,4 +90,8 @@ pub struct PaymentInstrument {
     /// Creation timestamp
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
 }
@@ -1,14 +1,15 @@
+use crate::decode::api_enum;
 use serde::{Deserialize, Serialize};
 
-/// Checkout status (per OpenAPI spec)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum CheckoutStatus {
-    Pending,
-    Paid,
-    Failed,
-    Cancelled,
-    Expired,
+api_enum! {
+    /// Checkout status (per OpenAPI spec)
+    pub enum CheckoutStatus {
+        Pending = "PENDING",
+        Paid = "PAID",
+        Failed = "FAILED",
+        Cancelled = "CANCELLED",
+        Expired = "EXPIRED",
+    }
 }
 
 impl Default for CheckoutStatus {
@@ -17,136 +18,102 @@ impl Default for CheckoutStatus {
     }
 }
 
-impl std::fmt::Display for CheckoutStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Pending => write!(f, "PENDING"),
-            Self::Paid => write!(f, "PAID"),
-            Self::Failed => write!(f, "FAILED"),
-            Self::Cancelled => write!(f, "CANCELLED"),
-            Self::Expired => write!(f, "EXPIRED"),
-        }
+api_enum! {
+    /// Checkout purpose
+    pub enum CheckoutPurpose {
+        Checkout = "CHECKOUT",
+        SetupRecurringPayment = "SETUP_RECURRING_PAYMENT",
     }
 }
 
-/// Checkout purpose
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum CheckoutPurpose {
-    Checkout,
-    SetupRecurringPayment,
-}
-
-/// Payment type for processing checkouts (per OpenAPI ProcessCheckout spec)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum PaymentType {
-    Card,
-    Boleto,
-    Ideal,
-    Blik,
-    Bancontact,
-    #[serde(other)]
-    Other,
-}
-
-/// Payout status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum PayoutStatus {
-    Pending,
-    Processing,
-    Completed,
-    Failed,
+api_enum! {
+    /// Payment type for processing checkouts (per OpenAPI ProcessCheckout spec)
+    pub enum PaymentType {
+        Card = "card",
+        Boleto = "boleto",
+        Ideal = "ideal",
+        Blik = "blik",
+        Bancontact = "bancontact",
+        ApplePay = "apple_pay",
+        GooglePay = "google_pay",
+    }
 }
 
-impl std::fmt::Display for PayoutStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Pending => write!(f, "PENDING"),
-            Self::Processing => write!(f, "PROCESSING"),
-            Self::Completed => write!(f, "COMPLETED"),
-            Self::Failed => write!(f, "FAILED"),
-        }
+impl PaymentType {
+    /// Parse an API identifier case-insensitively, keeping unknown identifiers
+    pub fn from_id(id: &str) -> Self {
+        Self::from_known(&id.to_ascii_lowercase()).unwrap_or_else(|| Self::Unknown(id.to_string()))
     }
 }
 
-/// Card reader status (per OpenAPI spec - lowercase values)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum ReaderStatus {
-    /// The reader status is unknown
-    Unknown,
-    /// The reader is created and waits for the physical device to confirm the pairing
-    Processing,
-    /// The reader is paired with a merchant account and can be used with SumUp APIs
-    Paired,
-    /// The pairing is expired and no longer usable with the account
-    Expired,
+api_enum! {
+    /// Payout status (the financial API reports `SUCCESSFUL` or `FAILED`)
+    pub enum PayoutStatus {
+        Successful = "SUCCESSFUL",
+        Pending = "PENDING",
+        Processing = "PROCESSING",
+        Completed = "COMPLETED",
+        Failed = "FAILED",
+    }
 }
 
-impl std::fmt::Display for ReaderStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Unknown => write!(f, "unknown"),
-            Self::Processing => write!(f, "processing"),
-            Self::Paired => write!(f, "paired"),
-            Self::Expired => write!(f, "expired"),
-        }
+impl PayoutStatus {
+    /// Whether the money reached the merchant's bank account
+    pub fn is_paid_out(&self) -> bool {
+        matches!(self, Self::Successful | Self::Completed)
     }
 }
 
-/// Reader device model
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
-pub enum ReaderDeviceModel {
-    Solo,
-    VirtualSolo,
+api_enum! {
+    /// Card reader status (per OpenAPI spec - lowercase values).
+    ///
+    /// The documented `unknown` status is `Unknown("unknown")`.
+    pub enum ReaderStatus {
+        /// The reader is created and waits for the physical device to confirm the pairing
+        Processing = "processing",
+        /// The reader is paired with a merchant account and can be used with SumUp APIs
+        Paired = "paired",
+        /// The pairing is expired and no longer usable with the account
+        Expired = "expired",
+    }
 }
 
-/// Card type for reader checkout (credit vs debit)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum ReaderCardType {
-    Credit,
-    Debit,
+api_enum! {
+    /// Reader device model
+    pub enum ReaderDeviceModel {
+        Solo = "solo",
+        VirtualSolo = "virtual-solo",
+    }
 }
 
-/// Transaction status (per OpenAPI spec)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum TransactionStatus {
-    Successful,
-    Cancelled,
-    Failed,
-    Pending,
-    Refunded,
-    ChargeBack,
+api_enum! {
+    /// Card type for reader checkout (credit vs debit)
+    pub enum ReaderCardType {
+        Credit = "credit",
+        Debit = "debit",
+    }
 }
 
-impl std::fmt::Display for TransactionStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Successful => write!(f, "SUCCESSFUL"),
-            Self::Cancelled => write!(f, "CANCELLED"),
-            Self::Failed => write!(f, "FAILED"),
-            Self::Pending => write!(f, "PENDING"),
-            Self::Refunded => write!(f, "REFUNDED"),
-            Self::ChargeBack => write!(f, "CHARGE_BACK"),
-        }
+api_enum! {
+    /// Transaction status (per OpenAPI spec)
+    pub enum TransactionStatus {
+        Successful = "SUCCESSFUL",
+        Cancelled = "CANCELLED",
+        Failed = "FAILED",
+        Pending = "PENDING",
+        Refunded = "REFUNDED",
+        ChargeBack = "CHARGE_BACK",
     }
 }
 
-/// Membership status (per OpenAPI spec - lowercase values)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum MembershipStatus {
-    Active,
-    Pending,
-    Declined,
-    Revoked,
-    #[serde(other)]
-    Unknown,
+api_enum! {
+    /// Membership status (per OpenAPI spec - lowercase values)
+    pub enum MembershipStatus {
+        Active = "active",
+        Pending = "pending",
+        Declined = "declined",
+        Revoked = "revoked",
+    }
 }
 
 impl Default for MembershipStatus {
@@ -155,127 +122,64 @@ impl Default for MembershipStatus {
     }
 }
 
-impl std::fmt::Display for MembershipStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Active => write!(f, "active"),
-            Self::Pending => write!(f, "pending"),
-            Self::Declined => write!(f, "declined"),
-            Self::Revoked => write!(f, "revoked"),
-            Self::Unknown => write!(f, "unknown"),
-        }
+api_enum! {
+    /// Card type (Visa, Mastercard, etc.) - per OpenAPI spec
+    pub enum CardType {
+        Amex = "AMEX",
+        Cup = "CUP",
+        Diners = "DINERS",
+        Discover = "DISCOVER",
+        Elo = "ELO",
+        Elv = "ELV",
+        Hipercard = "HIPERCARD",
+        Jcb = "JCB",
+        Maestro = "MAESTRO",
+        Mastercard = "MASTERCARD",
+        Visa = "VISA",
+        VisaElectron = "VISA_ELECTRON",
+        VisaVpay = "VISA_VPAY",
     }
 }
 
-/// Card type (Visa, Mastercard, etc.) - per OpenAPI spec
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum CardType {
-    Amex,
-    #[serde(rename = "CUP")]
-    Cup,
-    Diners,
-    Discover,
-    #[serde(rename = "ELO")]
-    Elo,
-    #[serde(rename = "ELV")]
-    Elv,
-    Hipercard,
-    #[serde(rename = "JCB")]
-    Jcb,
-    Maestro,
-    Mastercard,
-    Visa,
-    VisaElectron,
-    VisaVpay,
-    #[serde(other)]
-    Unknown,
-}
-
-impl std::fmt::Display for CardType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Amex => write!(f, "AMEX"),
-            Self::Cup => write!(f, "CUP"),
-            Self::Diners => write!(f, "DINERS"),
-            Self::Discover => write!(f, "DISCOVER"),
-            Self::Elo => write!(f, "ELO"),
-            Self::Elv => write!(f, "ELV"),
-            Self::Hipercard => write!(f, "HIPERCARD"),
-            Self::Jcb => write!(f, "JCB"),
-            Self::Maestro => write!(f, "MAESTRO"),
-            Self::Mastercard => write!(f, "MASTERCARD"),
-            Self::Visa => write!(f, "VISA"),
-            Self::VisaElectron => write!(f, "VISA_ELECTRON"),
-            Self::VisaVpay => write!(f, "VISA_VPAY"),
-            Self::Unknown => write!(f, "UNKNOWN"),
-        }
+api_enum! {
+    /// Mandate type for recurring payments (per OpenAPI spec)
+    pub enum MandateType {
+        Recurrent = "recurrent",
+        Oneoff = "oneoff",
     }
 }
 
-/// Mandate type for recurring payments (per OpenAPI spec)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum MandateType {
-    Recurrent,
-    Oneoff,
-    #[serde(other)]
-    Other,
-}
-
-/// Mandate status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum MandateStatus {
-    Active,
-    Inactive,
-    Pending,
-    Cancelled,
-}
-
-/// Payment instrument type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum PaymentInstrumentType {
-    Card,
-    BankAccount,
-    #[serde(other)]
-    Other,
-}
-
-impl std::fmt::Display for PaymentInstrumentType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Card => write!(f, "card"),
-            Self::BankAccount => write!(f, "bank_account"),
-            Self::Other => write!(f, "other"),
-        }
+api_enum! {
+    /// Mandate status
+    pub enum MandateStatus {
+        Active = "ACTIVE",
+        Inactive = "INACTIVE",
+        Pending = "PENDING",
+        Cancelled = "CANCELLED",
     }
 }
 
-/// Receipt status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum ReceiptStatus {
-    Pending,
-    Sent,
-    Failed,
-    #[serde(other)]
-    Unknown,
+api_enum! {
+    /// Payment instrument type
+    pub enum PaymentInstrumentType {
+        Card = "card",
+        BankAccount = "bank_account",
+    }
 }
 
-impl std::fmt::Display for ReceiptStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Pending => write!(f, "PENDING"),
-            Self::Sent => write!(f, "SENT"),
-            Self::Failed => write!(f, "FAILED"),
-            Self::Unknown => write!(f, "UNKNOWN"),
-        }
+api_enum! {
+    /// Receipt status
+    pub enum ReceiptStatus {
+        Pending = "PENDING",
+        Sent = "SENT",
+        Failed = "FAILED",
     }
 }
 
 /// Currency code (ISO 4217)
+///
+/// Accepts any string so API responses always deserialize; use [`Currency::parse`] or
+/// [`CurrencyCode`](super::CurrencyCode) to reject invalid codes locally.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct Currency(pub String);
@@ -289,6 +193,22 @@ impl Currency {
         &self.0
     }
 
+    /// Create a currency from a validated ISO 4217 code (whitespace and lower case are accepted)
+    pub fn parse(code: &str) -> crate::Result<Self> {
+        super::CurrencyCode::parse(code).map(Self::from)
+    }
+
+    /// Check that this is a valid ISO 4217 code
+    pub fn validate(&self) -> crate::Result<super::CurrencyCode> {
+        super::CurrencyCode::try_from(self)
+    }
+
+    /// Number of decimal places of the currency's minor unit (e.g. 2 for EUR, 0 for CLP),
+    /// or `None` if the code is not a known ISO 4217 currency
+    pub fn minor_units(&self) -> Option<u32> {
+        super::currency::iso_4217_minor_units(&self.0)
+    }
+
     // Common currencies
     pub const EUR: &'static str = "EUR";
     pub const GBP: &'static str = "GBP";
@@ -307,18 +227,20 @@ impl Currency {
     pub const COP: &'static str = "COP";
 }
 
-impl Default for Currency {
-    fn default() -> Self {
-        Self(Self::EUR.to_string())
-    }
-}
-
 impl std::fmt::Display for Currency {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
 
+impl std::str::FromStr for Currency {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
 impl From<&str> for Currency {
     fn from(s: &str) -> Self {
         Self(s.to_string())
@@ -346,7 +268,8 @@ impl From<&Currency> for Currency {
 /// Monetary amount using exact decimal arithmetic.
 ///
 /// Uses `rust_decimal::Decimal` internally to avoid floating-point precision issues.
-/// Serializes as a decimal number (e.g., `10.50`) for API compatibility.
+/// Serializes as a JSON number (e.g., `10.50`) for API compatibility, written and parsed
+/// through serde_json's arbitrary-precision path so no digits are lost to `f64`.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Amount(pub rust_decimal::Decimal);
 
@@ -356,7 +279,9 @@ impl Amount {
         Self(value)
     }
 
-    /// Create an amount from cents (minor units)
+    /// Create an amount from cents, assuming two decimal places.
+    ///
+    /// For currencies with a different minor unit (CLP, KWD, ...) use [`Money::from_minor_units`](super::Money::from_minor_units).
     pub fn from_cents(cents: i64) -> Self {
         Self(rust_decimal::Decimal::new(cents, 2))
     }
@@ -371,7 +296,9 @@ impl Amount {
         self.0
     }
 
-    /// Convert to cents (minor units), rounding if necessary
+    /// Convert to cents, assuming two decimal places and rounding if necessary.
+    ///
+    /// For currencies with a different minor unit use [`Money::to_minor_units`](super::Money::to_minor_units).
     pub fn to_cents(&self) -> i64 {
         use rust_decimal::prelude::ToPrimitive;
         (self.0 * rust_decimal::Decimal::from(100))
@@ -407,22 +334,51 @@ impl Serialize for Amount {
     where
         S: serde::Serializer,
     {
-        // Serialize as a number for API compatibility
-        use rust_decimal::prelude::ToPrimitive;
-        self.0.to_f64().unwrap_or(0.0).serialize(serializer)
+        rust_decimal::serde::arbitrary_precision::serialize(&self.0, serializer)
     }
 }
 
 impl<'de> Deserialize<'de> for Amount {
+    /// Accepts JSON numbers (exactly, without an `f64` round trip) and decimal strings.
+    /// Non-finite floats from other formats are rejected.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        use rust_decimal::prelude::FromPrimitive;
-        let value = f64::deserialize(deserializer)?;
-        let decimal = rust_decimal::Decimal::from_f64(value)
-            .ok_or_else(|| serde::de::Error::custom("invalid decimal value"))?;
-        Ok(Amount(decimal))
+        rust_decimal::serde::arbitrary_precision::deserialize(deserializer).map(Amount)
+    }
+}
+
+impl TryFrom<f64> for Amount {
+    type Error = crate::Error;
+
+    /// Converts using the shortest decimal representation of the float, so `0.1`
+    /// becomes exactly `0.1`. NaN and infinite values are rejected.
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if !value.is_finite() {
+            return Err(crate::Error::InvalidInput(format!(
+                "amount must be finite, got {}",
+                value
+            )));
+        }
+        value
+            .to_string()
+            .parse::<rust_decimal::Decimal>()
+            .map(Amount)
+            .map_err(|e| {
+                crate::Error::InvalidInput(format!("amount {} out of range: {}", value, e))
+            })
+    }
+}
+
+impl std::str::FromStr for Amount {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .parse::<rust_decimal::Decimal>()
+            .map(Amount)
+            .map_err(|e| crate::Error::InvalidInput(format!("invalid amount '{}': {}", s, e)))
     }
 }
 
@@ -458,203 +414,246 @@ impl std::ops::Sub for Amount {
     }
 }
 
-/// Strongly typed checkout ID
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(transparent)]
-pub struct CheckoutId(pub String);
-
-impl CheckoutId {
-    pub fn new(id: impl Into<String>) -> Self {
-        Self(id.into())
-    }
-
-    pub fn as_str(&self) -> &str {
-        &self.0
-    }
-}
-
-impl std::fmt::Display for CheckoutId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
-impl From<String> for CheckoutId {
-    fn from(s: String) -> Self {
-        Self(s)
-    }
-}
+/// Declares a strongly typed string ID with the usual conversions
+macro_rules! string_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(pub String);
 
-impl From<&str> for CheckoutId {
-    fn from(s: &str) -> Self {
-        Self(s.to_string())
-    }
-}
+        impl $name {
+            pub fn new(id: impl Into<String>) -> Self {
+                Self(id.into())
+            }
 
-impl AsRef<str> for CheckoutId {
-    fn as_ref(&self) -> &str {
-        &self.0
-    }
-}
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
 
-/// Strongly typed merchant code
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(transparent)]
-pub struct MerchantCode(pub String);
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
 
-impl MerchantCode {
-    pub fn new(code: impl Into<String>) -> Self {
-        Self(code.into())
-    }
+        impl From<String> for $name {
+            fn from(s: String) -> Self {
+                Self(s)
+            }
+        }
 
-    pub fn as_str(&self) -> &str {
-        &self.0
-    }
-}
+        impl From<&String> for $name {
+            fn from(s: &String) -> Self {
+                Self(s.clone())
+            }
+        }
 
-impl std::fmt::Display for MerchantCode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
+        impl From<&str> for $name {
+            fn from(s: &str) -> Self {
+                Self(s.to_string())
+            }
+        }
 
-impl From<String> for MerchantCode {
-    fn from(s: String) -> Self {
-        Self(s)
-    }
-}
+        impl From<&$name> for $name {
+            fn from(id: &$name) -> Self {
+                id.clone()
+            }
+        }
 
-impl From<&str> for MerchantCode {
-    fn from(s: &str) -> Self {
-        Self(s.to_string())
-    }
-}
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
 
-impl From<&MerchantCode> for MerchantCode {
-    fn from(m: &MerchantCode) -> Self {
-        m.clone()
-    }
-}
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
 
-impl AsRef<str> for MerchantCode {
-    fn as_ref(&self) -> &str {
-        &self.0
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+string_id!(
+    /// Strongly typed checkout ID
+    CheckoutId
+);
+
+string_id!(
+    /// Strongly typed merchant code
+    MerchantCode
+);
+
+string_id!(
+    /// Strongly typed customer ID
+    CustomerId
+);
+
+string_id!(
+    /// Strongly typed transaction ID
+    TransactionId
+);
+
+string_id!(
+    /// Client transaction ID (a UUID) returned when creating a reader checkout
+    ClientTransactionId
+);
+
+string_id!(
+    /// Strongly typed reader ID (e.g. `rdr_3MSAFM23CK82VSTT4BN6RWSQ65`)
+    ReaderId
+);
+
+impl ReaderId {
+    pub const PREFIX: &'static str = "rdr_";
+
+    /// Create a reader ID, checking its `rdr_` prefix and alphanumeric body
+    pub fn parse(id: impl Into<String>) -> crate::Result<Self> {
+        let id = Self(id.into());
+        id.validate()?;
+        Ok(id)
+    }
+
+    /// Check the `rdr_` prefix and alphanumeric body
+    pub fn validate(&self) -> crate::Result<()> {
+        match self.0.strip_prefix(Self::PREFIX) {
+            Some(body) if !body.is_empty() && body.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                Ok(())
+            }
+            _ => Err(crate::Error::InvalidInput(format!(
+                "'{}' is not a reader ID (expected {}<alphanumeric>)",
+                self.0,
+                Self::PREFIX
+            ))),
+        }
     }
 }
 
-/// Strongly typed customer ID
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(transparent)]
-pub struct CustomerId(pub String);
+string_id!(
+    /// Strongly typed member ID
+    MemberId
+);
 
-impl CustomerId {
-    pub fn new(id: impl Into<String>) -> Self {
-        Self(id.into())
-    }
+string_id!(
+    /// Strongly typed role ID
+    RoleId
+);
 
-    pub fn as_str(&self) -> &str {
-        &self.0
-    }
-}
+string_id!(
+    /// Strongly typed membership ID
+    MembershipId
+);
 
-impl std::fmt::Display for CustomerId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
+string_id!(
+    /// Strongly typed organization ID
+    OrganizationId
+);
 
-impl From<String> for CustomerId {
-    fn from(s: String) -> Self {
-        Self(s)
-    }
-}
+string_id!(
+    /// ID of the resource a membership grants access to: a merchant code or an
+    /// organization ID, depending on the membership's [`ResourceType`](crate::ResourceType)
+    ResourceId
+);
 
-impl From<&str> for CustomerId {
-    fn from(s: &str) -> Self {
-        Self(s.to_string())
-    }
-}
+string_id!(
+    /// Strongly typed payout ID
+    PayoutId
+);
 
-impl From<&String> for CustomerId {
-    fn from(s: &String) -> Self {
-        Self(s.clone())
-    }
-}
+string_id!(
+    /// Strongly typed receipt ID (a transaction code or transaction ID)
+    ReceiptId
+);
 
-impl AsRef<str> for CustomerId {
-    fn as_ref(&self) -> &str {
-        &self.0
-    }
-}
+string_id!(
+    /// Strongly typed operator (sub-account) ID
+    OperatorId
+);
 
-/// Strongly typed transaction ID
+/// Country code (ISO 3166-1 alpha-2)
+///
+/// Accepts any string so API responses always deserialize; use [`CountryCode::parse`]
+/// or `TryFrom` to reject invalid codes locally.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
-pub struct TransactionId(pub String);
+pub struct CountryCode(pub String);
 
-impl TransactionId {
-    pub fn new(id: impl Into<String>) -> Self {
-        Self(id.into())
+impl CountryCode {
+    pub fn new(code: impl Into<String>) -> Self {
+        Self(code.into())
+    }
+
+    /// Create a country code from a validated ISO 3166-1 alpha-2 code (whitespace and
+    /// lower case are accepted)
+    pub fn parse(code: &str) -> crate::Result<Self> {
+        let code = code.trim().to_ascii_uppercase();
+        if super::currency::iso_3166_country(&code).is_none() {
+            return Err(crate::Error::InvalidInput(format!(
+                "'{}' is not an ISO 3166-1 alpha-2 country code",
+                code
+            )));
+        }
+        Ok(Self(code))
     }
 
     pub fn as_str(&self) -> &str {
         &self.0
     }
-}
 
-impl std::fmt::Display for TransactionId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+    /// Check that this is a valid ISO 3166-1 alpha-2 code
+    pub fn is_valid(&self) -> bool {
+        super::currency::iso_3166_country(&self.0).is_some()
     }
-}
 
-impl From<String> for TransactionId {
-    fn from(s: String) -> Self {
-        Self(s)
+    /// The currency in general use in this country, if the code is valid and the
+    /// country has one
+    pub fn default_currency(&self) -> Option<super::CurrencyCode> {
+        super::currency::iso_3166_country(&self.0)
+            .flatten()
+            .and_then(|code| super::CurrencyCode::parse(code).ok())
     }
 }
 
-impl From<&str> for TransactionId {
-    fn from(s: &str) -> Self {
-        Self(s.to_string())
+impl std::str::FromStr for CountryCode {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
     }
 }
 
-impl AsRef<str> for TransactionId {
-    fn as_ref(&self) -> &str {
-        &self.0
+impl std::fmt::Display for CountryCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
-/// Country code (ISO 3166-1 alpha-2)
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(transparent)]
-pub struct CountryCode(pub String);
-
-impl CountryCode {
-    pub fn new(code: impl Into<String>) -> Self {
-        Self(code.into())
-    }
+impl TryFrom<&str> for CountryCode {
+    type Error = crate::Error;
 
-    pub fn as_str(&self) -> &str {
-        &self.0
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::parse(s)
     }
 }
 
-impl std::fmt::Display for CountryCode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
+impl TryFrom<String> for CountryCode {
+    type Error = crate::Error;
 
-impl From<String> for CountryCode {
-    fn from(s: String) -> Self {
-        Self(s)
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(&s)
     }
 }
 
-impl From<&str> for CountryCode {
-    fn from(s: &str) -> Self {
-        Self(s.to_string())
+impl AsRef<str> for CountryCode {
+    fn as_ref(&self) -> &str {
+        &self.0
     }
 }
@@ -0,0 +1,494 @@
+use super::enums::{Amount, Currency, TransactionStatus};
+use super::payout::{Payout, PayoutType};
+use super::transaction::{Transaction, TransactionEventType};
+use crate::Result;
+use chrono::NaiveDate;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+/// Ledger accounts the exporter books to.
+///
+/// The defaults are descriptive names; set them to the account numbers of your chart of
+/// accounts before importing into a bookkeeping tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountMapping {
+    /// Revenue from sales, net of VAT
+    pub sales: String,
+    /// VAT payable on sales
+    pub vat: String,
+    /// Tips, booked to `sales` when unset
+    pub tips: Option<String>,
+    /// Card processing fees
+    pub fees: String,
+    /// Receivable from SumUp between a sale and its payout
+    pub clearing: String,
+    /// Bank account payouts are sent to
+    pub bank: String,
+    /// Refunds given to customers (contra revenue)
+    pub refunds: String,
+    /// Chargebacks lost to card holders
+    pub chargebacks: String,
+}
+
+impl Default for AccountMapping {
+    fn default() -> Self {
+        Self {
+            sales: "Sales".to_string(),
+            vat: "VAT payable".to_string(),
+            tips: None,
+            fees: "Card fees".to_string(),
+            clearing: "SumUp clearing".to_string(),
+            bank: "Bank".to_string(),
+            refunds: "Refunds".to_string(),
+            chargebacks: "Chargebacks".to_string(),
+        }
+    }
+}
+
+impl AccountMapping {
+    pub fn sales(mut self, account: impl Into<String>) -> Self {
+        self.sales = account.into();
+        self
+    }
+
+    pub fn vat(mut self, account: impl Into<String>) -> Self {
+        self.vat = account.into();
+        self
+    }
+
+    pub fn tips(mut self, account: impl Into<String>) -> Self {
+        self.tips = Some(account.into());
+        self
+    }
+
+    pub fn fees(mut self, account: impl Into<String>) -> Self {
+        self.fees = account.into();
+        self
+    }
+
+    pub fn clearing(mut self, account: impl Into<String>) -> Self {
+        self.clearing = account.into();
+        self
+    }
+
+    pub fn bank(mut self, account: impl Into<String>) -> Self {
+        self.bank = account.into();
+        self
+    }
+
+    pub fn refunds(mut self, account: impl Into<String>) -> Self {
+        self.refunds = account.into();
+        self
+    }
+
+    pub fn chargebacks(mut self, account: impl Into<String>) -> Self {
+        self.chargebacks = account.into();
+        self
+    }
+}
+
+/// One debit or credit line of a [`JournalEntry`]; exactly one of the sides is non-zero
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalLine {
+    pub account: String,
+    pub debit: Amount,
+    pub credit: Amount,
+}
+
+/// A balanced booking in one currency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Stable ID derived from the source record, e.g. `sale-TX1`
+    pub id: String,
+    pub date: NaiveDate,
+    pub currency: Currency,
+    pub description: String,
+    /// Transaction code or payout reference the entry was booked from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    pub lines: Vec<JournalLine>,
+}
+
+impl JournalEntry {
+    fn new(
+        id: String,
+        date: NaiveDate,
+        currency: &Currency,
+        description: impl Into<String>,
+        reference: Option<&str>,
+    ) -> Self {
+        Self {
+            id,
+            date,
+            currency: currency.clone(),
+            description: description.into(),
+            reference: reference.map(str::to_string),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Add a line; positive values are debits, negative values credits, zero is skipped
+    fn post(mut self, account: &str, value: Decimal) -> Self {
+        if !value.is_zero() {
+            let (debit, credit) = if value.is_sign_positive() {
+                (value, Decimal::ZERO)
+            } else {
+                (Decimal::ZERO, -value)
+            };
+            self.lines.push(JournalLine {
+                account: account.to_string(),
+                debit: Amount(debit),
+                credit: Amount(credit),
+            });
+        }
+        self
+    }
+
+    pub fn total_debit(&self) -> Amount {
+        Amount(self.lines.iter().map(|l| l.debit.0).sum())
+    }
+
+    pub fn total_credit(&self) -> Amount {
+        Amount(self.lines.iter().map(|l| l.credit.0).sum())
+    }
+
+    /// Whether debits equal credits
+    pub fn is_balanced(&self) -> bool {
+        self.total_debit() == self.total_credit()
+    }
+}
+
+/// Journal entries ready for import, ordered by date
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Journal {
+    pub entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Whether every entry is balanced
+    pub fn is_balanced(&self) -> bool {
+        self.entries.iter().all(JournalEntry::is_balanced)
+    }
+
+    /// Write the journal in the generic JSON format: `{"entries": [{"id", "date",
+    /// "currency", "description", "reference", "lines": [{"account", "debit", "credit"}]}]}`
+    /// with amounts as exact JSON numbers
+    pub fn write_json(&self, writer: impl std::io::Write) -> Result<()> {
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
+
+/// Converts transactions and payouts into double-entry journal entries.
+///
+/// Sales are booked against the SumUp clearing account, and payouts move the balance from
+/// clearing to the bank, so clearing nets to zero once every sale has been paid out:
+///
+/// | Source | Debit | Credit |
+/// |---|---|---|
+/// | Successful transaction | clearing (gross) | sales (net), VAT, tips |
+/// | Refund | refunds (net), VAT, tips | clearing (refunded amount) |
+/// | Chargeback | chargebacks (amount) | clearing (amount) |
+/// | `PAYOUT` entry | bank (amount), fees (fee) | clearing (amount + fee) |
+/// | Deduction entry | clearing, fees (fee) | bank |
+///
+/// Each refund and chargeback event on the transaction is booked as its own reversal,
+/// for the event's amount and dated at the event, so partial refunds of a transaction
+/// that stays `SUCCESSFUL` are booked too. VAT and tips are reversed in proportion to
+/// the refunded share. Transaction history does not include events; when none were
+/// returned, a `REFUNDED` or `CHARGE_BACK` transaction is reversed in full, dated at the
+/// sale. Other statuses are not booked.
+#[derive(Debug, Clone, Default)]
+pub struct LedgerExporter {
+    accounts: AccountMapping,
+}
+
+impl LedgerExporter {
+    pub fn new(accounts: AccountMapping) -> Self {
+        Self { accounts }
+    }
+
+    pub fn accounts(&self) -> &AccountMapping {
+        &self.accounts
+    }
+
+    /// Entries for one transaction: its sale, plus one reversal per refund or chargeback
+    pub fn transaction_entries(&self, transaction: &Transaction) -> Vec<JournalEntry> {
+        let code = transaction.transaction_code.as_str();
+        if !matches!(
+            transaction.status,
+            None | Some(TransactionStatus::Successful)
+                | Some(TransactionStatus::Refunded)
+                | Some(TransactionStatus::ChargeBack)
+        ) {
+            return Vec::new();
+        }
+
+        let accounts = &self.accounts;
+        let date = transaction.timestamp.date_naive();
+        let gross = transaction.amount.0;
+        let vat = transaction.vat_amount.unwrap_or_default().0;
+        let tip = transaction.tip_amount.unwrap_or_default().0;
+        let tips = accounts.tips.as_deref().unwrap_or(&accounts.sales);
+        let entry = |id: String, date: NaiveDate, description: String| {
+            JournalEntry::new(id, date, &transaction.currency, description, Some(code))
+        };
+
+        let mut entries = vec![
+            entry(format!("sale-{}", code), date, format!("Sale {}", code))
+                .post(&accounts.clearing, gross)
+                .post(&accounts.sales, -(gross - vat - tip))
+                .post(&accounts.vat, -vat)
+                .post(tips, -tip),
+        ];
+
+        let reversals = |event_type: TransactionEventType| {
+            transaction
+                .reversals(event_type)
+                .into_iter()
+                .map(|(amount, time)| (amount.0, time.date_naive()))
+                .enumerate()
+        };
+        let id = |kind: &str, n: usize| match n {
+            0 => format!("{}-{}", kind, code),
+            n => format!("{}-{}-{}", kind, code, n + 1),
+        };
+
+        // VAT and tips are reversed in proportion to the refunded share of the sale
+        let scale = transaction.currency.minor_units().unwrap_or(gross.scale());
+        let share = |part: Decimal, amount: Decimal| {
+            if amount == gross || gross.is_zero() {
+                part
+            } else {
+                (part * amount / gross)
+                    .round_dp_with_strategy(scale, RoundingStrategy::MidpointAwayFromZero)
+            }
+        };
+        for (n, (amount, date)) in reversals(TransactionEventType::Refund) {
+            let (vat, tip) = (share(vat, amount), share(tip, amount));
+            entries.push(
+                entry(id("refund", n), date, format!("Refund of {}", code))
+                    .post(&accounts.refunds, amount - vat - tip)
+                    .post(&accounts.vat, vat)
+                    .post(tips, tip)
+                    .post(&accounts.clearing, -amount),
+            );
+        }
+        for (n, (amount, date)) in reversals(TransactionEventType::ChargeBack) {
+            entries.push(
+                entry(id("chargeback", n), date, format!("Chargeback of {}", code))
+                    .post(&accounts.chargebacks, amount)
+                    .post(&accounts.clearing, -amount),
+            );
+        }
+        entries
+    }
+
+    /// Entry moving one payout entry from the clearing account to the bank
+    pub fn payout_entry(&self, payout: &Payout) -> JournalEntry {
+        let accounts = &self.accounts;
+        let source = payout
+            .transaction_code
+            .as_deref()
+            .unwrap_or(payout.payout_type.as_str());
+        let batch = payout
+            .reference
+            .as_deref()
+            .or(payout.id.as_ref().map(|id| id.as_str()))
+            .unwrap_or_default();
+        let description = match payout.payout_type {
+            PayoutType::Payout => format!("Payout of {}", source),
+            _ => format!("Payout deduction {} for {}", payout.payout_type, source),
+        };
+
+        JournalEntry::new(
+            format!("payout-{}-{}-{}", payout.date, batch, source),
+            payout.date,
+            &payout.currency,
+            description,
+            payout.reference.as_deref(),
+        )
+        .post(&accounts.bank, payout.amount.0)
+        .post(&accounts.fees, payout.fee.0)
+        .post(&accounts.clearing, -(payout.amount.0 + payout.fee.0))
+    }
+
+    /// Book transactions and payout entries into one journal ordered by date
+    pub fn export<'a>(
+        &self,
+        transactions: impl IntoIterator<Item = &'a Transaction>,
+        payouts: impl IntoIterator<Item = &'a Payout>,
+    ) -> Journal {
+        let mut entries: Vec<JournalEntry> = transactions
+            .into_iter()
+            .flat_map(|t| self.transaction_entries(t))
+            .chain(payouts.into_iter().map(|p| self.payout_entry(p)))
+            .collect();
+        // stable, so a sale stays ahead of its reversal
+        entries.sort_by_key(|e| e.date);
+        Journal { entries }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transactions() -> Vec<Transaction> {
+        serde_json::from_str(
+            r#"[
+                {"id": "t1", "transaction_code": "TX1", "amount": 121.00, "vat_amount": 19.00,
+                 "tip_amount": 2.00, "currency": "EUR", "timestamp": "2024-03-01T10:00:00Z",
+                 "status": "SUCCESSFUL"},
+                {"id": "t2", "transaction_code": "TX2", "amount": 11.90, "vat_amount": 1.90,
+                 "currency": "EUR", "timestamp": "2024-03-01T11:00:00Z", "status": "REFUNDED"},
+                {"id": "t3", "transaction_code": "TX3", "amount": 5.00, "currency": "EUR",
+                 "timestamp": "2024-03-01T12:00:00Z", "status": "FAILED"}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    fn payouts() -> Vec<Payout> {
+        serde_json::from_str(
+            r#"[
+                {"amount": 11.90, "currency": "EUR", "date": "2024-03-01", "status": "SUCCESSFUL",
+                 "type": "PAYOUT", "transaction_code": "TX2", "reference": "PO-0"},
+                {"amount": 118.70, "fee": 2.30, "currency": "EUR", "date": "2024-03-04",
+                 "status": "SUCCESSFUL", "type": "PAYOUT", "transaction_code": "TX1",
+                 "reference": "PO-1"},
+                {"amount": -11.90, "currency": "EUR", "date": "2024-03-04",
+                 "status": "SUCCESSFUL", "type": "REFUND_DEDUCTION", "transaction_code": "TX2",
+                 "reference": "PO-1"}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    fn line(entry: &JournalEntry, account: &str) -> (Decimal, Decimal) {
+        let line = entry.lines.iter().find(|l| l.account == account).unwrap();
+        (line.debit.0, line.credit.0)
+    }
+
+    #[test]
+    fn test_sale_and_refund_entries() {
+        let exporter = LedgerExporter::new(AccountMapping::default().sales("8400").tips("8410"));
+        let entries = exporter.transaction_entries(&transactions()[0]);
+        assert_eq!(entries.len(), 1);
+        let sale = &entries[0];
+        assert_eq!(sale.id, "sale-TX1");
+        assert_eq!(
+            line(sale, "SumUp clearing"),
+            (Decimal::new(12100, 2), Decimal::ZERO)
+        );
+        assert_eq!(line(sale, "8400"), (Decimal::ZERO, Decimal::new(10000, 2)));
+        assert_eq!(
+            line(sale, "VAT payable"),
+            (Decimal::ZERO, Decimal::new(1900, 2))
+        );
+        assert_eq!(line(sale, "8410"), (Decimal::ZERO, Decimal::new(200, 2)));
+        assert!(sale.is_balanced());
+
+        let refunded = exporter.transaction_entries(&transactions()[1]);
+        let ids: Vec<_> = refunded.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["sale-TX2", "refund-TX2"]);
+        assert_eq!(
+            line(&refunded[1], "Refunds"),
+            (Decimal::new(1000, 2), Decimal::ZERO)
+        );
+
+        assert!(exporter.transaction_entries(&transactions()[2]).is_empty());
+    }
+
+    #[test]
+    fn test_clearing_nets_to_zero_once_paid_out() {
+        let journal = LedgerExporter::default().export(&transactions(), &payouts());
+        assert!(journal.is_balanced());
+        assert_eq!(journal.entries.len(), 6);
+
+        let clearing: Decimal = journal
+            .entries
+            .iter()
+            .flat_map(|e| &e.lines)
+            .filter(|l| l.account == "SumUp clearing")
+            .map(|l| l.debit.0 - l.credit.0)
+            .sum();
+        assert_eq!(clearing, Decimal::ZERO);
+
+        let bank: Decimal = journal
+            .entries
+            .iter()
+            .flat_map(|e| &e.lines)
+            .filter(|l| l.account == "Bank")
+            .map(|l| l.debit.0 - l.credit.0)
+            .sum();
+        assert_eq!(bank, Decimal::new(11870, 2));
+    }
+
+    #[test]
+    fn test_partial_refunds_are_booked_per_event() {
+        let transaction: Transaction = serde_json::from_str(
+            r#"{"id": "t4", "transaction_code": "TX4", "amount": 119.00, "vat_amount": 19.00,
+                "currency": "EUR", "timestamp": "2024-03-01T10:00:00Z", "status": "SUCCESSFUL",
+                "events": [
+                    {"type": "REFUND", "status": "SUCCESSFUL", "amount": 23.80,
+                     "timestamp": "2024-03-05T09:00:00Z"},
+                    {"type": "REFUND", "status": "FAILED", "amount": 10.00,
+                     "timestamp": "2024-03-06T09:00:00Z"},
+                    {"type": "REFUND", "status": "SUCCESSFUL", "amount": 11.90,
+                     "timestamp": "2024-03-07T09:00:00Z"}
+                ]}"#,
+        )
+        .unwrap();
+        let payouts: Vec<Payout> = serde_json::from_str(
+            r#"[
+                {"amount": 119.00, "currency": "EUR", "date": "2024-03-04", "status": "SUCCESSFUL",
+                 "type": "PAYOUT", "transaction_code": "TX4", "reference": "PO-1"},
+                {"amount": -35.70, "currency": "EUR", "date": "2024-03-08",
+                 "status": "SUCCESSFUL", "type": "REFUND_DEDUCTION", "transaction_code": "TX4",
+                 "reference": "PO-2"}
+            ]"#,
+        )
+        .unwrap();
+
+        let exporter = LedgerExporter::default();
+        let entries = exporter.transaction_entries(&transaction);
+        let ids: Vec<_> = entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["sale-TX4", "refund-TX4", "refund-TX4-2"]);
+        assert_eq!(
+            entries[1].date,
+            NaiveDate::from_ymd_opt(2024, 3, 5).unwrap()
+        );
+        assert_eq!(
+            line(&entries[1], "Refunds"),
+            (Decimal::new(2000, 2), Decimal::ZERO)
+        );
+        assert_eq!(
+            line(&entries[1], "VAT payable"),
+            (Decimal::new(380, 2), Decimal::ZERO)
+        );
+        assert!(entries.iter().all(JournalEntry::is_balanced));
+
+        let journal = exporter.export([&transaction], &payouts);
+        let clearing: Decimal = journal
+            .entries
+            .iter()
+            .flat_map(|e| &e.lines)
+            .filter(|l| l.account == "SumUp clearing")
+            .map(|l| l.debit.0 - l.credit.0)
+            .sum();
+        assert_eq!(clearing, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_json_keeps_exact_amounts() {
+        let journal = LedgerExporter::default().export(&transactions()[..1], &[]);
+        let mut out = Vec::new();
+        journal.write_json(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("\"debit\": 121.00"));
+
+        let back: Journal = serde_json::from_str(&text).unwrap();
+        assert_eq!(back, journal);
+    }
+}
@@ -0,0 +1,489 @@
+use super::enums::{Amount, Currency};
+use super::money::{Money, MoneyError};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// No-break space, used by CLDR between amounts and symbols and as the Polish group separator
+const NBSP: char = '\u{a0}';
+
+/// Locales amounts can be formatted and parsed in.
+///
+/// Separators and symbol placement follow the CLDR data for each locale, so formatted
+/// amounts may contain no-break spaces (U+00A0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// German (Germany): `1.234,56 €`
+    DeDe,
+    /// Portuguese (Brazil): `R$ 1.234,56`
+    PtBr,
+    /// Polish (Poland): `12 345,67 zł` (four-digit amounts are not grouped)
+    PlPl,
+    /// English (United Kingdom): `£1,234.56`
+    EnGb,
+}
+
+/// Errors raised when parsing user-entered amounts
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AmountParseError {
+    #[error("no amount given")]
+    Empty,
+
+    #[error("unexpected character '{0}'")]
+    InvalidCharacter(char),
+
+    #[error("unrecognized currency symbol '{0}'")]
+    UnknownSymbol(String),
+
+    #[error("expected {expected} but found '{found}'")]
+    SymbolMismatch { expected: Currency, found: String },
+
+    #[error("digit group separator in the wrong place")]
+    MisplacedGroupSeparator,
+
+    #[error("more than one decimal separator")]
+    MultipleDecimalSeparators,
+
+    #[error("missing digits")]
+    MissingDigits,
+
+    #[error("amount out of range")]
+    OutOfRange,
+
+    #[error(transparent)]
+    Money(#[from] MoneyError),
+}
+
+impl Locale {
+    /// Every supported locale
+    pub const ALL: [Locale; 4] = [Locale::DeDe, Locale::PtBr, Locale::PlPl, Locale::EnGb];
+
+    /// BCP 47 language tag (e.g. `de-DE`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DeDe => "de-DE",
+            Self::PtBr => "pt-BR",
+            Self::PlPl => "pl-PL",
+            Self::EnGb => "en-GB",
+        }
+    }
+
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Self::EnGb => '.',
+            Self::DeDe | Self::PtBr | Self::PlPl => ',',
+        }
+    }
+
+    pub fn group_separator(&self) -> char {
+        match self {
+            Self::DeDe | Self::PtBr => '.',
+            Self::PlPl => NBSP,
+            Self::EnGb => ',',
+        }
+    }
+
+    /// Currency symbol used in this locale, falling back to the ISO code
+    pub fn currency_symbol<'a>(&self, currency: &'a Currency) -> &'a str {
+        match (currency.as_str(), self) {
+            ("EUR", _) => "€",
+            ("GBP", _) => "£",
+            ("BRL", _) => "R$",
+            ("PLN", Self::PlPl) => "zł",
+            ("USD", Self::DeDe) => "$",
+            ("USD", Self::PtBr | Self::EnGb) => "US$",
+            (code, _) => code,
+        }
+    }
+
+    /// Format an amount with the currency's minor units (rounding half away from zero),
+    /// locale separators and symbol placement
+    pub fn format(&self, amount: Amount, currency: &Currency) -> String {
+        let minor_units = currency.minor_units().unwrap_or(2);
+        let mut value = amount
+            .0
+            .round_dp_with_strategy(minor_units, RoundingStrategy::MidpointAwayFromZero);
+        value.rescale(minor_units);
+        let negative = value.is_sign_negative() && !value.is_zero();
+        let digits = value.abs().to_string();
+        let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+
+        let mut number = self.group(integer);
+        if !fraction.is_empty() {
+            number.push(self.decimal_separator());
+            number.push_str(fraction);
+        }
+
+        let sign = if negative { "-" } else { "" };
+        let symbol = self.currency_symbol(currency);
+        match self {
+            Self::DeDe | Self::PlPl => format!("{}{}{}{}", sign, number, NBSP, symbol),
+            Self::PtBr => format!("{}{}{}{}", sign, symbol, NBSP, number),
+            Self::EnGb if symbol.chars().all(|c| c.is_ascii_alphabetic()) => {
+                format!("{}{}{}{}", sign, symbol, NBSP, number)
+            }
+            Self::EnGb => format!("{}{}{}", sign, symbol, number),
+        }
+    }
+
+    /// Format a money value (see [`Locale::format`])
+    pub fn format_money(&self, money: &Money) -> String {
+        self.format(money.amount(), money.currency())
+    }
+
+    /// Parse a user-entered amount such as `1.234,56`, `-1 234,56 zł` or `£1,234.56`.
+    ///
+    /// Group separators are optional but must split the integer part into groups of
+    /// three, so `1.5` in `de-DE` or `1,5` in `en-GB` is rejected rather than read in
+    /// the wrong convention. A currency symbol or ISO code may precede or follow the
+    /// number. Ordinary spaces are accepted wherever the locale uses a no-break space.
+    pub fn parse_amount(&self, input: &str) -> Result<Amount, AmountParseError> {
+        self.parse(input, None)
+    }
+
+    /// Parse a user-entered amount in a given currency.
+    ///
+    /// Any symbol in the input must belong to `currency`, and the amount may not have
+    /// more decimals than the currency's minor unit.
+    pub fn parse_money(
+        &self,
+        input: &str,
+        currency: impl Into<Currency>,
+    ) -> Result<Money, AmountParseError> {
+        let currency = currency.into();
+        let amount = self.parse(input, Some(&currency))?;
+        Ok(Money::new(amount, currency)?)
+    }
+
+    fn parse(&self, input: &str, currency: Option<&Currency>) -> Result<Amount, AmountParseError> {
+        let mut rest = input.trim_matches(is_space);
+        if rest.is_empty() {
+            return Err(AmountParseError::Empty);
+        }
+
+        let mut negative = false;
+        let mut symbols = Vec::new();
+        rest = strip_sign(rest, &mut negative);
+        let (prefix, after) = split_symbol_prefix(rest, self.decimal_separator());
+        if !prefix.is_empty() {
+            symbols.push(prefix);
+            rest = strip_sign(after.trim_start_matches(is_space), &mut negative);
+        }
+        let (before, suffix) = split_symbol_suffix(rest, self.decimal_separator());
+        if !suffix.is_empty() {
+            symbols.push(suffix);
+            rest = before.trim_end_matches(is_space);
+        }
+        for symbol in symbols {
+            self.check_symbol(symbol, currency)?;
+        }
+
+        let (integer, fraction) = match rest.split_once(self.decimal_separator()) {
+            Some((_, f)) if f.contains(self.decimal_separator()) => {
+                return Err(AmountParseError::MultipleDecimalSeparators)
+            }
+            Some((i, f)) => (i, Some(f)),
+            None => (rest, None),
+        };
+        let integer = self.ungroup(integer)?;
+        if let Some(fraction) = fraction {
+            if fraction.is_empty() {
+                return Err(AmountParseError::MissingDigits);
+            }
+            if let Some(c) = fraction.chars().find(|c| !c.is_ascii_digit()) {
+                return Err(invalid_char(c));
+            }
+        }
+        if integer.is_empty() && fraction.is_none() {
+            return Err(AmountParseError::MissingDigits);
+        }
+
+        let literal = format!(
+            "{}{}.{}",
+            if negative { "-" } else { "" },
+            if integer.is_empty() { "0" } else { &integer },
+            fraction.unwrap_or("0")
+        );
+        literal
+            .parse::<Decimal>()
+            .map(|d| Amount(d.normalize()))
+            .map_err(|_| AmountParseError::OutOfRange)
+    }
+
+    /// Insert group separators into a string of integer digits
+    fn group(&self, integer: &str) -> String {
+        // Polish only groups numbers of five or more digits ("1234" but "12 345")
+        let min_digits = if *self == Self::PlPl { 5 } else { 4 };
+        if integer.len() < min_digits {
+            return integer.to_string();
+        }
+        let mut out = String::with_capacity(integer.len() + integer.len() / 3);
+        for (i, c) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                out.push(self.group_separator());
+            }
+            out.push(c);
+        }
+        out
+    }
+
+    /// Remove group separators from the integer part, checking their positions
+    fn ungroup(&self, integer: &str) -> Result<String, AmountParseError> {
+        let is_group = |c: char| match self {
+            Self::PlPl => is_space(c),
+            _ => c == self.group_separator(),
+        };
+        let groups: Vec<&str> = integer.split(is_group).collect();
+        for group in &groups {
+            if let Some(c) = group.chars().find(|c| !c.is_ascii_digit()) {
+                return Err(invalid_char(c));
+            }
+        }
+        if groups.len() > 1 {
+            let first_ok = (1..=3).contains(&groups[0].len());
+            if !first_ok || groups[1..].iter().any(|g| g.len() != 3) {
+                return Err(AmountParseError::MisplacedGroupSeparator);
+            }
+        }
+        Ok(groups.concat())
+    }
+
+    fn check_symbol(
+        &self,
+        symbol: &str,
+        currency: Option<&Currency>,
+    ) -> Result<(), AmountParseError> {
+        match currency {
+            Some(currency) => {
+                let matches = symbol == self.currency_symbol(currency)
+                    || symbol.eq_ignore_ascii_case(currency.as_str());
+                if !matches {
+                    return Err(AmountParseError::SymbolMismatch {
+                        expected: currency.clone(),
+                        found: symbol.to_string(),
+                    });
+                }
+            }
+            None => {
+                let known = ["€", "£", "R$", "zł", "$", "US$"].contains(&symbol)
+                    || Currency::new(symbol.to_ascii_uppercase())
+                        .minor_units()
+                        .is_some();
+                if !known {
+                    return Err(AmountParseError::UnknownSymbol(symbol.to_string()));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for Locale {
+    type Err = crate::Error;
+
+    /// Accepts `de-DE` as well as `de_DE`, case-insensitively
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tag = s.replace('_', "-");
+        Self::ALL
+            .into_iter()
+            .find(|l| l.as_str().eq_ignore_ascii_case(&tag))
+            .ok_or_else(|| crate::Error::InvalidInput(format!("unsupported locale '{}'", s)))
+    }
+}
+
+impl Money {
+    /// Format for display in a locale (see [`Locale::format`])
+    pub fn format(&self, locale: Locale) -> String {
+        locale.format_money(self)
+    }
+}
+
+fn is_space(c: char) -> bool {
+    c == ' ' || c == NBSP || c == '\u{202f}'
+}
+
+fn is_sign(c: char) -> bool {
+    c == '-' || c == '\u{2212}'
+}
+
+fn strip_sign<'a>(s: &'a str, negative: &mut bool) -> &'a str {
+    match s.strip_prefix(is_sign) {
+        Some(rest) if !*negative => {
+            *negative = true;
+            rest.trim_start_matches(is_space)
+        }
+        _ => s,
+    }
+}
+
+/// Characters that can appear in the numeric part of an input
+fn is_numeric(c: char, decimal_separator: char) -> bool {
+    c.is_ascii_digit()
+        || c == decimal_separator
+        || c == '.'
+        || c == ','
+        || is_space(c)
+        || is_sign(c)
+}
+
+fn split_symbol_prefix(s: &str, decimal_separator: char) -> (&str, &str) {
+    let end = s
+        .find(|c| is_numeric(c, decimal_separator))
+        .unwrap_or(s.len());
+    s.split_at(end)
+}
+
+fn split_symbol_suffix(s: &str, decimal_separator: char) -> (&str, &str) {
+    let start = s
+        .rfind(|c| is_numeric(c, decimal_separator))
+        .map(|i| i + s[i..].chars().next().map_or(0, char::len_utf8))
+        .unwrap_or(0);
+    s.split_at(start)
+}
+
+fn invalid_char(c: char) -> AmountParseError {
+    if c == '.' || c == ',' || is_space(c) {
+        // A separator from another convention, e.g. "1 234" in de-DE
+        return AmountParseError::MisplacedGroupSeparator;
+    }
+    AmountParseError::InvalidCharacter(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eur() -> Currency {
+        Currency::new("EUR")
+    }
+
+    #[test]
+    fn test_format_per_locale() {
+        let amount = Amount(Decimal::new(123456, 2));
+        assert_eq!(Locale::DeDe.format(amount, &eur()), "1.234,56\u{a0}€");
+        assert_eq!(
+            Locale::PtBr.format(amount, &Currency::new("BRL")),
+            "R$\u{a0}1.234,56"
+        );
+        assert_eq!(
+            Locale::PlPl.format(Amount(Decimal::new(1234567, 2)), &Currency::new("PLN")),
+            "12\u{a0}345,67\u{a0}zł"
+        );
+        assert_eq!(
+            Locale::EnGb.format(amount, &Currency::new("GBP")),
+            "£1,234.56"
+        );
+        assert_eq!(
+            Locale::EnGb.format(amount, &Currency::new("CHF")),
+            "CHF\u{a0}1,234.56"
+        );
+    }
+
+    #[test]
+    fn test_format_rounds_to_minor_units_and_handles_signs() {
+        assert_eq!(
+            Locale::EnGb.format(Amount::from_major(-1_000_000), &Currency::new("GBP")),
+            "-£1,000,000.00"
+        );
+        assert_eq!(
+            Locale::PlPl.format(Amount::from_major(1234), &Currency::new("PLN")),
+            "1234,00\u{a0}zł"
+        );
+        assert_eq!(
+            Locale::DeDe.format(Amount(Decimal::new(10005, 3)), &eur()),
+            "10,01\u{a0}€"
+        );
+        assert_eq!(
+            Locale::PtBr.format(Amount(Decimal::new(15004, 1)), &Currency::new("CLP")),
+            "CLP\u{a0}1.500"
+        );
+        assert_eq!(
+            Locale::DeDe.format(Amount(Decimal::new(-1, 3)), &eur()),
+            "0,00\u{a0}€"
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trips_formatting() {
+        for locale in Locale::ALL {
+            for (cents, code) in [
+                (123456, "EUR"),
+                (-99, "GBP"),
+                (100000000, "PLN"),
+                (5, "BRL"),
+            ] {
+                let money = Money::from_minor_units(cents, code).unwrap();
+                let formatted = money.format(locale);
+                assert_eq!(
+                    locale.parse_money(&formatted, code).unwrap(),
+                    money,
+                    "{}",
+                    formatted
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_user_input() {
+        let d = |v: i64, s: u32| Amount(Decimal::new(v, s));
+        assert_eq!(Locale::DeDe.parse_amount("1234,5").unwrap(), d(12345, 1));
+        assert_eq!(
+            Locale::DeDe.parse_amount(" -1.234 € ").unwrap(),
+            d(-1234, 0)
+        );
+        assert_eq!(Locale::PtBr.parse_amount("R$ 0,99").unwrap(), d(99, 2));
+        assert_eq!(
+            Locale::PlPl.parse_amount("12 345,00 PLN").unwrap(),
+            d(12345, 0)
+        );
+        assert_eq!(
+            Locale::EnGb.parse_amount("-£1,000.5").unwrap(),
+            d(-10005, 1)
+        );
+        assert_eq!(Locale::EnGb.parse_amount(".50").unwrap(), d(5, 1));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use AmountParseError::*;
+        assert_eq!(Locale::DeDe.parse_amount("  "), Err(Empty));
+        assert_eq!(
+            Locale::DeDe.parse_amount("1.5"),
+            Err(MisplacedGroupSeparator)
+        );
+        assert_eq!(
+            Locale::EnGb.parse_amount("1,5"),
+            Err(MisplacedGroupSeparator)
+        );
+        assert_eq!(
+            Locale::DeDe.parse_amount("1,2,3"),
+            Err(MultipleDecimalSeparators)
+        );
+        assert_eq!(Locale::DeDe.parse_amount("12,"), Err(MissingDigits));
+        assert_eq!(Locale::DeDe.parse_amount("1x2"), Err(InvalidCharacter('x')));
+        assert_eq!(
+            Locale::DeDe.parse_amount("10 Taler"),
+            Err(UnknownSymbol("Taler".into()))
+        );
+        assert!(matches!(
+            Locale::EnGb.parse_money("€10", "GBP"),
+            Err(SymbolMismatch { .. })
+        ));
+        assert!(matches!(
+            Locale::PtBr.parse_money("10,5", "CLP"),
+            Err(Money(MoneyError::TooManyDecimals { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_locale_from_str() {
+        assert_eq!("pt_BR".parse::<Locale>().unwrap(), Locale::PtBr);
+        assert_eq!("en-gb".parse::<Locale>().unwrap(), Locale::EnGb);
+        assert!("fr-FR".parse::<Locale>().is_err());
+    }
+}
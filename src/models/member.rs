@@ -1,4 +1,5 @@
-use super::enums::MembershipStatus;
+use super::enums::{MemberId, MembershipStatus, RoleId};
+use crate::utils::{impl_extra_fields, UnknownFields};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -13,25 +14,31 @@ pub struct MemberUser {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Member {
     /// Unique identifier for the member
-    pub id: String,
+    pub id: MemberId,
     /// Member's email address
     pub email: String,
     /// Assigned roles
-    pub roles: Vec<String>,
+    pub roles: Vec<RoleId>,
     /// Membership status
     pub status: MembershipStatus,
     /// User information
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub user: Option<MemberUser>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
 }
 
+impl_extra_fields!(Member);
+
 /// Request to create a new merchant member (per OpenAPI spec)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateMemberRequest {
     /// Email address for the member (required)
     pub email: String,
     /// Roles to assign (required)
-    pub roles: Vec<String>,
+    pub roles: Vec<RoleId>,
     /// Whether this is a managed user (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_managed_user: Option<bool>,
@@ -50,10 +57,13 @@ pub struct CreateMemberRequest {
 }
 
 impl CreateMemberRequest {
-    pub fn new(email: impl Into<String>, roles: Vec<String>) -> Self {
+    pub fn new(
+        email: impl Into<String>,
+        roles: impl IntoIterator<Item = impl Into<RoleId>>,
+    ) -> Self {
         Self {
             email: email.into(),
-            roles,
+            roles: roles.into_iter().map(Into::into).collect(),
             is_managed_user: None,
             password: None,
             nickname: None,
@@ -79,12 +89,35 @@ impl CreateMemberRequest {
 pub struct UpdateMemberRequest {
     /// Updated roles (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub roles: Option<Vec<String>>,
+    pub roles: Option<Vec<RoleId>>,
 }
 
 impl UpdateMemberRequest {
-    pub fn roles(mut self, roles: Vec<String>) -> Self {
-        self.roles = Some(roles);
+    pub fn roles(mut self, roles: impl IntoIterator<Item = impl Into<RoleId>>) -> Self {
+        self.roles = Some(roles.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+/// Query parameters for listing members
+#[derive(Debug, Clone, Default)]
+pub struct MemberListQuery {
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
+}
+
+impl MemberListQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: i32) -> Self {
+        self.offset = Some(offset);
         self
     }
 }
@@ -1,4 +1,7 @@
-use super::enums::MembershipStatus;
+use super::enums::{MembershipId, MembershipStatus, MerchantCode, ResourceId, RoleId};
+use super::permission::{Permission, PermissionSet};
+use crate::decode::api_enum;
+use crate::utils::{impl_extra_fields, UnknownFields};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -9,25 +12,25 @@ pub struct MembershipListResponse {
     pub total_count: i32,
 }
 
-/// Resource type for memberships (per OpenAPI spec)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum ResourceType {
-    Merchant,
-    Organization,
+api_enum! {
+    /// Resource type for memberships (per OpenAPI spec)
+    pub enum ResourceType {
+        Merchant = "merchant",
+        Organization = "organization",
+    }
 }
 
 /// Membership object (per OpenAPI spec)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Membership {
-    pub id: String,
+    pub id: MembershipId,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub resource_id: Option<String>,
+    pub resource_id: Option<ResourceId>,
     #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
-    pub resource_type: Option<String>,
-    pub roles: Vec<String>,
+    pub resource_type: Option<ResourceType>,
+    pub roles: Vec<RoleId>,
     #[serde(default)]
-    pub permissions: Vec<String>,
+    pub permissions: Vec<Permission>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<DateTime<Utc>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -35,13 +38,54 @@ pub struct Membership {
     pub status: MembershipStatus,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub resource: Option<MembershipResource>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
+}
+
+impl_extra_fields!(Membership);
+
+impl Membership {
+    /// Whether this membership is an invitation still waiting to be accepted or declined.
+    pub fn is_pending_invitation(&self) -> bool {
+        self.status == MembershipStatus::Pending
+    }
+
+    pub fn permission_set(&self) -> PermissionSet {
+        self.permissions.iter().cloned().collect()
+    }
+
+    /// Whether the membership grants `permission`
+    pub fn has_permission(&self, permission: &Permission) -> bool {
+        self.permissions.contains(permission)
+    }
+
+    /// Returns the merchant code this membership grants access to.
+    ///
+    /// Taken from the embedded `resource` when it is a merchant, falling back to
+    /// `resource_id` for merchant memberships listed without one. Organization
+    /// memberships yield `None`.
+    pub fn merchant_code(&self) -> Option<MerchantCode> {
+        let merchant = ResourceType::Merchant;
+        match &self.resource {
+            Some(resource) => (resource.resource_type == merchant)
+                .then(|| MerchantCode::from(resource.id.as_str())),
+            None => match (&self.resource_type, &self.resource_id) {
+                (Some(kind), Some(id)) if *kind == merchant => {
+                    Some(MerchantCode::from(id.as_str()))
+                }
+                _ => None,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MembershipResource {
-    pub id: String,
+    pub id: ResourceId,
     #[serde(rename = "type")]
-    pub resource_type: String,
+    pub resource_type: ResourceType,
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logo: Option<String>,
@@ -49,16 +93,50 @@ pub struct MembershipResource {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CreateMembershipRequest {
-    pub name: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+/// Query parameters for listing memberships
+#[derive(Debug, Clone, Default)]
+pub struct MembershipListQuery {
+    pub status: Option<MembershipStatus>,
+    pub resource_type: Option<ResourceType>,
+    /// Only memberships holding any of these roles
+    pub roles: Vec<RoleId>,
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
 }
 
+impl MembershipListQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(mut self, status: MembershipStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn resource_type(mut self, resource_type: ResourceType) -> Self {
+        self.resource_type = Some(resource_type);
+        self
+    }
+
+    pub fn role(mut self, role: impl Into<RoleId>) -> Self {
+        self.roles.push(role.into());
+        self
+    }
+
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: i32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+/// Request to accept or decline a membership invitation
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct UpdateMembershipRequest {
-    pub name: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+pub struct UpdateMembershipStatusRequest {
+    pub status: MembershipStatus,
 }
@@ -1,5 +1,6 @@
 use super::customer::Address;
 use super::enums::{CountryCode, Currency, MerchantCode};
+use crate::utils::{impl_extra_fields, Nullable, UnknownFields};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -25,11 +26,13 @@ pub struct Merchant {
     pub created_at: Option<DateTime<Utc>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
-    /// Catch any extra fields
+    /// Fields not modelled by this crate (always captured for this model)
     #[serde(flatten)]
-    pub extra: Option<std::collections::HashMap<String, serde_json::Value>>,
+    pub extra: UnknownFields,
 }
 
+impl_extra_fields!(Merchant);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MerchantProfile {
     #[serde(rename = "merchant_profile")]
@@ -61,3 +64,291 @@ pub struct DoingBusinessAs {
     pub website: Option<String>,
     pub address: Address,
 }
+
+/// Bank account registered for a merchant's payouts
+///
+/// `Debug` masks the IBAN and account number down to their last four characters.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MerchantBankAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iban: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swift: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bank_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_holder_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Whether payouts go to this account
+    #[serde(default)]
+    pub primary: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
+}
+
+impl_extra_fields!(MerchantBankAccount);
+
+/// Mask all but the last four characters, e.g. `******************3000`
+pub(super) fn mask_account(value: &str) -> String {
+    let chars: Vec<char> = value.chars().filter(|c| !c.is_whitespace()).collect();
+    if chars.len() <= 4 {
+        return "*".repeat(chars.len());
+    }
+    let (hidden, last4) = chars.split_at(chars.len() - 4);
+    format!(
+        "{}{}",
+        "*".repeat(hidden.len()),
+        last4.iter().collect::<String>()
+    )
+}
+
+impl std::fmt::Debug for MerchantBankAccount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MerchantBankAccount")
+            .field("iban", &self.iban.as_deref().map(mask_account))
+            .field(
+                "account_number",
+                &self.account_number.as_deref().map(mask_account),
+            )
+            .field("bank_code", &self.bank_code)
+            .field("branch_code", &self.branch_code)
+            .field("swift", &self.swift)
+            .field("bank_name", &self.bank_name)
+            .field("account_holder_name", &self.account_holder_name)
+            .field("account_type", &self.account_type)
+            .field("status", &self.status)
+            .field("primary", &self.primary)
+            .field("created_at", &self.created_at)
+            .field("extra", &self.extra)
+            .finish()
+    }
+}
+
+/// Personal profile of the account owner
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonalProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    /// Date of birth (YYYY-MM-DD)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_of_birth: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mobile_phone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// Whether all required details have been provided
+    #[serde(default)]
+    pub complete: bool,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
+}
+
+impl_extra_fields!(PersonalProfile);
+
+/// Merchant account settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerchantSettings {
+    /// Whether VAT is calculated on sales
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tax_enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payout_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payout_period: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payout_on_demand_available: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payout_on_demand: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub printers_enabled: Option<bool>,
+    /// Mail order / telephone order payments: `"ENFORCED"`, `"ON"` or `"OFF"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moto_payment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_payout_email: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_payout_email: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gross_settlement: Option<bool>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
+}
+
+impl_extra_fields!(MerchantSettings);
+
+/// Partial update of the business profile.
+///
+/// Only fields that were set are sent; `clear_*` sends `null` to remove a value.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UpdateMerchantProfileRequest {
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub company_name: Nullable<String>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub legal_type: Nullable<String>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub website: Nullable<String>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub mobile_phone: Nullable<String>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub address: Nullable<Address>,
+}
+
+impl UpdateMerchantProfileRequest {
+    pub fn company_name(mut self, name: impl Into<String>) -> Self {
+        self.company_name = Nullable::Value(name.into());
+        self
+    }
+
+    pub fn legal_type(mut self, legal_type: impl Into<String>) -> Self {
+        self.legal_type = Nullable::Value(legal_type.into());
+        self
+    }
+
+    pub fn website(mut self, website: impl Into<String>) -> Self {
+        self.website = Nullable::Value(website.into());
+        self
+    }
+
+    pub fn clear_website(mut self) -> Self {
+        self.website = Nullable::Null;
+        self
+    }
+
+    pub fn mobile_phone(mut self, phone: impl Into<String>) -> Self {
+        self.mobile_phone = Nullable::Value(phone.into());
+        self
+    }
+
+    pub fn clear_mobile_phone(mut self) -> Self {
+        self.mobile_phone = Nullable::Null;
+        self
+    }
+
+    pub fn address(mut self, address: Address) -> Self {
+        self.address = Nullable::Value(address);
+        self
+    }
+}
+
+/// Partial update of the name and contact details shown to customers.
+///
+/// Only fields that were set are sent; `clear_*` sends `null` to remove a value.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UpdateDoingBusinessAsRequest {
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub business_name: Nullable<String>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub email: Nullable<String>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub website: Nullable<String>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub address: Nullable<Address>,
+}
+
+impl UpdateDoingBusinessAsRequest {
+    pub fn business_name(mut self, name: impl Into<String>) -> Self {
+        self.business_name = Nullable::Value(name.into());
+        self
+    }
+
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Nullable::Value(email.into());
+        self
+    }
+
+    pub fn website(mut self, website: impl Into<String>) -> Self {
+        self.website = Nullable::Value(website.into());
+        self
+    }
+
+    pub fn clear_website(mut self) -> Self {
+        self.website = Nullable::Null;
+        self
+    }
+
+    pub fn address(mut self, address: Address) -> Self {
+        self.address = Nullable::Value(address);
+        self
+    }
+
+    pub fn clear_address(mut self) -> Self {
+        self.address = Nullable::Null;
+        self
+    }
+}
+
+/// Partial update of the merchant settings.
+///
+/// Only fields that were set are sent; `clear_*` sends `null` to reset a value to the
+/// account default.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UpdateMerchantSettingsRequest {
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub tax_enabled: Nullable<bool>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub printers_enabled: Nullable<bool>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub moto_payment: Nullable<String>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub daily_payout_email: Nullable<bool>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub monthly_payout_email: Nullable<bool>,
+    #[serde(skip_serializing_if = "Nullable::is_absent")]
+    pub payout_on_demand: Nullable<bool>,
+}
+
+impl UpdateMerchantSettingsRequest {
+    pub fn tax_enabled(mut self, enabled: bool) -> Self {
+        self.tax_enabled = Nullable::Value(enabled);
+        self
+    }
+
+    pub fn printers_enabled(mut self, enabled: bool) -> Self {
+        self.printers_enabled = Nullable::Value(enabled);
+        self
+    }
+
+    pub fn moto_payment(mut self, mode: impl Into<String>) -> Self {
+        self.moto_payment = Nullable::Value(mode.into());
+        self
+    }
+
+    pub fn clear_moto_payment(mut self) -> Self {
+        self.moto_payment = Nullable::Null;
+        self
+    }
+
+    pub fn daily_payout_email(mut self, enabled: bool) -> Self {
+        self.daily_payout_email = Nullable::Value(enabled);
+        self
+    }
+
+    pub fn monthly_payout_email(mut self, enabled: bool) -> Self {
+        self.monthly_payout_email = Nullable::Value(enabled);
+        self
+    }
+
+    pub fn payout_on_demand(mut self, enabled: bool) -> Self {
+        self.payout_on_demand = Nullable::Value(enabled);
+        self
+    }
+}
@@ -1,27 +1,49 @@
 // Re-export all model modules
+pub mod card_validation;
 pub mod checkout;
 pub mod common;
+pub mod currency;
 pub mod customer;
 pub mod enums;
+pub mod ledger;
+pub mod locale;
 pub mod member;
 pub mod membership;
 pub mod merchant;
+pub mod money;
+pub mod organization;
 pub mod payout;
+pub mod permission;
 pub mod reader;
+pub mod reconciliation;
 pub mod receipt;
 pub mod role;
+pub mod tax;
+pub mod tip;
 pub mod transaction;
+pub mod wallet;
 
 // Re-export commonly used types for convenience
+pub use card_validation::*;
 pub use checkout::*;
 pub use common::*;
+pub use currency::*;
 pub use enums::*;
+pub use ledger::*;
+pub use locale::*;
 pub use customer::*;
 pub use member::*;
 pub use membership::*;
 pub use merchant::*;
+pub use money::*;
+pub use organization::*;
 pub use payout::*;
+pub use permission::*;
 pub use reader::*;
+pub use reconciliation::*;
 pub use receipt::*;
 pub use role::*;
+pub use tax::*;
+pub use tip::*;
 pub use transaction::*;
+pub use wallet::*;
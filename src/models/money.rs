@@ -0,0 +1,382 @@
+use super::enums::{Amount, Currency};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+/// Errors raised when constructing or combining [`Money`] values
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MoneyError {
+    #[error("unknown currency code '{0}'")]
+    UnknownCurrency(String),
+
+    #[error("{amount} has more than {minor_units} decimal places allowed for {currency}")]
+    TooManyDecimals {
+        amount: Amount,
+        currency: Currency,
+        minor_units: u32,
+    },
+
+    #[error("cannot combine {left} and {right} amounts")]
+    CurrencyMismatch { left: Currency, right: Currency },
+
+    #[error("amount out of range for {0}")]
+    Overflow(Currency),
+
+    #[error("cannot allocate an amount across no shares")]
+    EmptyAllocation,
+}
+
+/// An [`Amount`] together with the [`Currency`] it is denominated in.
+///
+/// The currency must be a known ISO 4217 code, and the amount may not carry more
+/// decimal places than the currency's minor unit (so `10.5 CLP` is rejected).
+/// Arithmetic is checked and refuses to mix currencies.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "RawMoney")]
+pub struct Money {
+    amount: Amount,
+    currency: Currency,
+}
+
+#[derive(Deserialize)]
+struct RawMoney {
+    amount: Amount,
+    currency: Currency,
+}
+
+impl TryFrom<RawMoney> for Money {
+    type Error = MoneyError;
+
+    fn try_from(raw: RawMoney) -> Result<Self, Self::Error> {
+        Money::new(raw.amount, raw.currency)
+    }
+}
+
+impl Money {
+    /// Create a money value, validating the currency and the amount's precision
+    pub fn new(
+        amount: impl Into<Amount>,
+        currency: impl Into<Currency>,
+    ) -> Result<Self, MoneyError> {
+        let amount = amount.into();
+        let currency = currency.into();
+        let minor_units = minor_units_of(&currency)?;
+        let normalized = amount.0.normalize();
+        if normalized.scale() > minor_units {
+            return Err(MoneyError::TooManyDecimals {
+                amount,
+                currency,
+                minor_units,
+            });
+        }
+        Ok(Self {
+            amount: Amount(rescaled(amount.0, minor_units)),
+            currency,
+        })
+    }
+
+    /// Create a money value, rounding the amount half away from zero to the
+    /// currency's minor unit
+    pub fn rounded(
+        amount: impl Into<Amount>,
+        currency: impl Into<Currency>,
+    ) -> Result<Self, MoneyError> {
+        let amount = amount.into();
+        let currency = currency.into();
+        let minor_units = minor_units_of(&currency)?;
+        let value = amount
+            .0
+            .round_dp_with_strategy(minor_units, RoundingStrategy::MidpointAwayFromZero);
+        Self::new(value, currency)
+    }
+
+    /// Create a money value from an integer count of minor units
+    /// (e.g. `1050` EUR cents is `10.50 EUR`, `1050` CLP is `1050 CLP`)
+    pub fn from_minor_units(value: i64, currency: impl Into<Currency>) -> Result<Self, MoneyError> {
+        let currency = currency.into();
+        let minor_units = minor_units_of(&currency)?;
+        Ok(Self {
+            amount: Amount(Decimal::new(value, minor_units)),
+            currency,
+        })
+    }
+
+    /// Zero in the given currency
+    pub fn zero(currency: impl Into<Currency>) -> Result<Self, MoneyError> {
+        Self::from_minor_units(0, currency)
+    }
+
+    pub fn amount(&self) -> Amount {
+        self.amount
+    }
+
+    pub fn currency(&self) -> &Currency {
+        &self.currency
+    }
+
+    /// Number of decimal places of the currency's minor unit
+    pub fn minor_units(&self) -> u32 {
+        self.amount.0.scale()
+    }
+
+    /// The amount as an integer count of minor units
+    pub fn to_minor_units(&self) -> Result<i64, MoneyError> {
+        let scaled = self
+            .amount
+            .0
+            .checked_mul(Decimal::from(10i64.pow(self.minor_units())))
+            .ok_or_else(|| MoneyError::Overflow(self.currency.clone()))?;
+        scaled
+            .to_i64()
+            .ok_or_else(|| MoneyError::Overflow(self.currency.clone()))
+    }
+
+    pub fn into_parts(self) -> (Amount, Currency) {
+        (self.amount, self.currency)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount.is_zero()
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.amount.is_positive()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.amount.is_negative()
+    }
+
+    /// Add two values of the same currency
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(other)?;
+        let value = self
+            .amount
+            .0
+            .checked_add(other.amount.0)
+            .ok_or_else(|| MoneyError::Overflow(self.currency.clone()))?;
+        Ok(self.with_value(value))
+    }
+
+    /// Subtract a value of the same currency
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.ensure_same_currency(other)?;
+        let value = self
+            .amount
+            .0
+            .checked_sub(other.amount.0)
+            .ok_or_else(|| MoneyError::Overflow(self.currency.clone()))?;
+        Ok(self.with_value(value))
+    }
+
+    /// Multiply by an integer quantity
+    pub fn checked_mul(&self, quantity: i64) -> Result<Money, MoneyError> {
+        let value = self
+            .amount
+            .0
+            .checked_mul(Decimal::from(quantity))
+            .ok_or_else(|| MoneyError::Overflow(self.currency.clone()))?;
+        Ok(self.with_value(value))
+    }
+
+    /// Sum values that must all share `currency`
+    pub fn sum<'a>(
+        currency: impl Into<Currency>,
+        values: impl IntoIterator<Item = &'a Money>,
+    ) -> Result<Money, MoneyError> {
+        values
+            .into_iter()
+            .try_fold(Self::zero(currency)?, |acc, m| acc.checked_add(m))
+    }
+
+    /// Split into `parts` values that differ by at most one minor unit and sum exactly
+    /// to `self`; earlier parts receive the leftover minor units
+    pub fn split(&self, parts: usize) -> Result<Vec<Money>, MoneyError> {
+        self.allocate(&vec![1; parts])
+    }
+
+    /// Split in proportion to `weights` without rounding loss.
+    ///
+    /// Each share is rounded down to a whole minor unit, then the leftover minor units
+    /// go to the shares with the largest remainders (earlier shares win ties), so the
+    /// result always sums exactly to `self`.
+    pub fn allocate(&self, weights: &[u32]) -> Result<Vec<Money>, MoneyError> {
+        let total_weight: i128 = weights.iter().map(|&w| i128::from(w)).sum();
+        if total_weight == 0 {
+            return Err(MoneyError::EmptyAllocation);
+        }
+        let total = i128::from(self.to_minor_units()?);
+        let sign = if total < 0 { -1 } else { 1 };
+        let magnitude = total.abs();
+
+        let mut shares: Vec<i128> = Vec::with_capacity(weights.len());
+        let mut remainders: Vec<(usize, i128)> = Vec::with_capacity(weights.len());
+        for (i, &weight) in weights.iter().enumerate() {
+            let product = magnitude * i128::from(weight);
+            shares.push(product / total_weight);
+            remainders.push((i, product % total_weight));
+        }
+        let mut leftover = magnitude - shares.iter().sum::<i128>();
+        remainders.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        for (i, _) in remainders {
+            if leftover == 0 {
+                break;
+            }
+            shares[i] += 1;
+            leftover -= 1;
+        }
+
+        shares
+            .into_iter()
+            .map(|share| {
+                let minor = i64::try_from(sign * share)
+                    .map_err(|_| MoneyError::Overflow(self.currency.clone()))?;
+                Money::from_minor_units(minor, self.currency.clone())
+            })
+            .collect()
+    }
+
+    fn ensure_same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency != other.currency {
+            return Err(MoneyError::CurrencyMismatch {
+                left: self.currency.clone(),
+                right: other.currency.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn with_value(&self, value: Decimal) -> Money {
+        Money {
+            amount: Amount(rescaled(value, self.minor_units())),
+            currency: self.currency.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+fn minor_units_of(currency: &Currency) -> Result<u32, MoneyError> {
+    currency
+        .minor_units()
+        .ok_or_else(|| MoneyError::UnknownCurrency(currency.to_string()))
+}
+
+/// Give `value` exactly `scale` decimal places (it must not need rounding)
+fn rescaled(mut value: Decimal, scale: u32) -> Decimal {
+    value.rescale(scale);
+    value
+}
+
+/// Euro amount from cents, shared by the unit tests of modules that handle money
+#[cfg(test)]
+pub(crate) fn eur(cents: i64) -> Money {
+    Money::from_minor_units(cents, "EUR").unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minor_units_per_currency() {
+        assert_eq!(
+            Money::from_minor_units(1050, "EUR").unwrap().to_string(),
+            "10.50 EUR"
+        );
+        assert_eq!(
+            Money::from_minor_units(1050, "CLP").unwrap().to_string(),
+            "1050 CLP"
+        );
+        assert_eq!(
+            Money::from_minor_units(1050, "COP").unwrap().to_string(),
+            "10.50 COP"
+        );
+        assert_eq!(
+            Money::from_minor_units(1050, "HUF").unwrap().to_string(),
+            "10.50 HUF"
+        );
+        assert_eq!(
+            Money::from_minor_units(1050, "KWD").unwrap().to_string(),
+            "1.050 KWD"
+        );
+    }
+
+    #[test]
+    fn test_new_rejects_excess_precision_and_unknown_codes() {
+        assert!(matches!(
+            Money::new(Decimal::new(105, 1), "CLP"),
+            Err(MoneyError::TooManyDecimals { minor_units: 0, .. })
+        ));
+        assert!(Money::new(Decimal::new(10500, 3), "EUR").is_ok());
+        assert!(matches!(
+            Money::new(Amount::from_major(1), "XYZ"),
+            Err(MoneyError::UnknownCurrency(_))
+        ));
+        assert!(Money::new(Amount::from_major(1), "eur").is_err());
+    }
+
+    #[test]
+    fn test_rounded_uses_currency_exponent() {
+        let clp = Money::rounded(Decimal::new(105, 1), "CLP").unwrap();
+        assert_eq!(clp.to_minor_units().unwrap(), 11);
+        let eur = Money::rounded(Decimal::new(-1_005, 3), "EUR").unwrap();
+        assert_eq!(eur.to_minor_units().unwrap(), -101);
+    }
+
+    #[test]
+    fn test_arithmetic_refuses_mixed_currencies() {
+        let eur = Money::from_minor_units(1000, "EUR").unwrap();
+        let gbp = Money::from_minor_units(1000, "GBP").unwrap();
+        assert!(matches!(
+            eur.checked_add(&gbp),
+            Err(MoneyError::CurrencyMismatch { .. })
+        ));
+
+        let total = eur
+            .checked_add(&Money::from_minor_units(1, "EUR").unwrap())
+            .unwrap()
+            .checked_mul(3)
+            .unwrap();
+        assert_eq!(total.to_minor_units().unwrap(), 3003);
+        assert_eq!(
+            Money::sum("EUR", [&eur, &eur])
+                .unwrap()
+                .to_minor_units()
+                .unwrap(),
+            2000
+        );
+    }
+
+    #[test]
+    fn test_split_and_allocate_sum_exactly() {
+        let total = Money::from_minor_units(1000, "EUR").unwrap();
+        let parts: Vec<i64> = total
+            .split(3)
+            .unwrap()
+            .iter()
+            .map(|m| m.to_minor_units().unwrap())
+            .collect();
+        assert_eq!(parts, vec![334, 333, 333]);
+
+        let refund = Money::from_minor_units(-1001, "CLP").unwrap();
+        let shares = refund.allocate(&[1, 2, 2]).unwrap();
+        let minor: Vec<i64> = shares.iter().map(|m| m.to_minor_units().unwrap()).collect();
+        assert_eq!(minor, vec![-200, -401, -400]);
+        assert_eq!(Money::sum("CLP", &shares).unwrap(), refund);
+
+        assert_eq!(total.split(0), Err(MoneyError::EmptyAllocation));
+        assert_eq!(total.allocate(&[0, 0]), Err(MoneyError::EmptyAllocation));
+    }
+
+    #[test]
+    fn test_deserialize_validates() {
+        let money: Money = serde_json::from_str(r#"{"amount": 1500, "currency": "CLP"}"#).unwrap();
+        assert_eq!(money.to_minor_units().unwrap(), 1500);
+        assert!(serde_json::from_str::<Money>(r#"{"amount": 15.5, "currency": "CLP"}"#).is_err());
+    }
+}
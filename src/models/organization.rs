@@ -0,0 +1,81 @@
+use super::enums::{CountryCode, MerchantCode, OrganizationId};
+use crate::utils::{impl_extra_fields, UnknownFields};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An organization grouping several merchant accounts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Organization {
+    pub id: OrganizationId,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
+}
+
+impl_extra_fields!(Organization);
+
+/// Response for listing organizations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationListResponse {
+    pub items: Vec<Organization>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<i32>,
+}
+
+/// A merchant account belonging to an organization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationMerchant {
+    pub merchant_code: MerchantCode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<CountryCode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
+}
+
+impl_extra_fields!(OrganizationMerchant);
+
+/// Response for listing the merchants of an organization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationMerchantListResponse {
+    pub items: Vec<OrganizationMerchant>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<i32>,
+}
+
+/// Paging for [`SumUpClient::list_organizations_with_query`](crate::SumUpClient::list_organizations_with_query)
+/// and [`SumUpClient::list_organization_merchants_with_query`](crate::SumUpClient::list_organization_merchants_with_query)
+#[derive(Debug, Clone, Default)]
+pub struct OrganizationListQuery {
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
+}
+
+impl OrganizationListQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: i32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
@@ -1,40 +1,133 @@
-use super::enums::{Amount, Currency, MerchantCode};
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use super::enums::{Amount, Currency, MerchantCode, PayoutId, PayoutStatus};
+use super::merchant::mask_account;
+use super::money::Money;
+use crate::decode::api_enum;
+use crate::utils::{impl_extra_fields, UnknownFields};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize};
 
-/// Payout object (fields based on FinancialPayouts schema)
-/// Note: Made flexible with Option types since exact schema is not fully documented
+api_enum! {
+    /// Kind of payout entry (per OpenAPI FinancialPayouts schema)
+    pub enum PayoutType {
+        /// Proceeds of a transaction paid out to the merchant
+        Payout = "PAYOUT",
+        /// A chargeback deducted from the payout
+        ChargeBackDeduction = "CHARGE_BACK_DEDUCTION",
+        /// A refund deducted from the payout
+        RefundDeduction = "REFUND_DEDUCTION",
+        /// A returned direct debit deducted from the payout
+        DdReturnDeduction = "DD_RETURN_DEDUCTION",
+        /// A negative balance deducted from the payout
+        BalanceDeduction = "BALANCE_DEDUCTION",
+    }
+}
+
+impl PayoutType {
+    /// Whether this entry reduces the payout rather than adding a transaction to it
+    pub fn is_deduction(&self) -> bool {
+        !matches!(self, Self::Payout | Self::Unknown(_))
+    }
+}
+
+/// One entry of a payout (per OpenAPI FinancialPayouts schema).
+///
+/// SumUp reports a payout as one entry per transaction or deduction; the entries of a
+/// single bank transfer share its `reference`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Payout {
+    /// Payout ID (the API sends an integer)
+    #[serde(
+        default,
+        deserialize_with = "deserialize_payout_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub id: Option<PayoutId>,
+    /// Amount of this entry
+    pub amount: Amount,
+    /// Fee SumUp deducted for this entry
+    #[serde(default)]
+    pub fee: Amount,
+    pub currency: Currency,
+    /// Date of the payout
+    #[serde(deserialize_with = "deserialize_payout_date")]
+    pub date: NaiveDate,
+    pub status: PayoutStatus,
+    #[serde(rename = "type")]
+    pub payout_type: PayoutType,
+    /// Code of the transaction this entry pays out or deducts
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub id: Option<String>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub amount: Option<Amount>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub currency: Option<Currency>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub date: Option<DateTime<Utc>>,
+    pub transaction_code: Option<String>,
+    /// Reference of the bank transfer, shown on the merchant's bank statement
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub status: Option<String>,
+    pub reference: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    /// Transaction code (for some response formats)
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub transaction_code: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub merchant_code: Option<MerchantCode>,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub reference: Option<String>,
     /// Bank account details
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bank_account: Option<BankAccount>,
-    /// Catch any extra fields
+    /// Fields not modelled by this crate (always captured for this model)
     #[serde(flatten)]
-    pub extra: Option<std::collections::HashMap<String, serde_json::Value>>,
+    pub extra: UnknownFields,
+}
+
+impl_extra_fields!(Payout);
+
+impl Payout {
+    /// Amount of this entry
+    pub fn money(&self) -> crate::Result<Money> {
+        Ok(Money::new(self.amount, self.currency.clone())?)
+    }
+
+    /// Fee SumUp deducted for this entry
+    pub fn fee_money(&self) -> crate::Result<Money> {
+        Ok(Money::new(self.fee, self.currency.clone())?)
+    }
+
+    /// Whether this entry belongs to the payout with the given reference
+    pub fn is_part_of(&self, reference: &str) -> bool {
+        self.reference.as_deref() == Some(reference)
+    }
+}
+
+/// Accept the integer IDs the financial API sends as well as strings
+fn deserialize_payout_id<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<PayoutId>, D::Error> {
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::Number(id)) => Ok(Some(PayoutId(id.to_string()))),
+        Some(serde_json::Value::String(id)) => Ok(Some(PayoutId(id))),
+        Some(other) => Err(serde::de::Error::custom(format!(
+            "invalid payout id {}",
+            other
+        ))),
+    }
+}
+
+/// Accept `YYYY-MM-DD` dates as documented as well as RFC 3339 timestamps
+fn deserialize_payout_date<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<NaiveDate, D::Error> {
+    let raw = String::deserialize(deserializer)?;
+    parse_payout_date(&raw)
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid payout date '{}'", raw)))
+}
+
+/// Parse a payout date, either `YYYY-MM-DD` as documented or an RFC 3339 timestamp
+pub(crate) fn parse_payout_date(raw: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d").ok().or_else(|| {
+        chrono::DateTime::parse_from_rfc3339(raw)
+            .ok()
+            .map(|dt| dt.date_naive())
+    })
 }
 
 /// Bank account information for payouts
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `Debug` masks the IBAN down to its last four characters.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct BankAccount {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub iban: Option<String>,
@@ -46,10 +139,75 @@ pub struct BankAccount {
     pub bank_name: Option<String>,
 }
 
-/// Response for listing payouts
+impl std::fmt::Debug for BankAccount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BankAccount")
+            .field("iban", &self.iban.as_deref().map(mask_account))
+            .field("bic", &self.bic)
+            .field("account_holder_name", &self.account_holder_name)
+            .field("bank_name", &self.bank_name)
+            .finish()
+    }
+}
+
+/// Response for listing payouts.
+///
+/// The financial API returns a bare JSON array; wrapped `items`, `payouts` or `data`
+/// lists are accepted as well.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "RawPayoutList")]
 pub struct PayoutListResponse {
-    /// List of payouts (field name may vary by endpoint)
-    #[serde(default, alias = "payouts", alias = "data")]
     pub items: Vec<Payout>,
 }
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawPayoutList {
+    List(Vec<Payout>),
+    Wrapped {
+        #[serde(default, alias = "payouts", alias = "data")]
+        items: Vec<Payout>,
+    },
+}
+
+impl From<RawPayoutList> for PayoutListResponse {
+    fn from(raw: RawPayoutList) -> Self {
+        match raw {
+            RawPayoutList::List(items) | RawPayoutList::Wrapped { items } => Self { items },
+        }
+    }
+}
+
+impl PayoutListResponse {
+    /// Entries of the payout with the given bank transfer reference
+    pub fn entries_for(&self, reference: &str) -> Vec<&Payout> {
+        self.items
+            .iter()
+            .filter(|p| p.is_part_of(reference))
+            .collect()
+    }
+
+    /// Codes of the transactions paid out or deducted in the payout with the given reference
+    pub fn transaction_codes_for(&self, reference: &str) -> Vec<&str> {
+        let mut codes: Vec<&str> = Vec::new();
+        for entry in self.entries_for(reference) {
+            if let Some(code) = entry.transaction_code.as_deref() {
+                if !codes.contains(&code) {
+                    codes.push(code);
+                }
+            }
+        }
+        codes
+    }
+
+    /// Distinct bank transfer references, in the order they first appear
+    pub fn references(&self) -> Vec<&str> {
+        let mut references: Vec<&str> = Vec::new();
+        for reference in self.items.iter().filter_map(|p| p.reference.as_deref()) {
+            if !references.contains(&reference) {
+                references.push(reference);
+            }
+        }
+        references
+    }
+}
@@ -0,0 +1,335 @@
+use super::role::{CreateRoleRequest, Role, UpdateRoleRequest};
+use crate::decode::api_enum;
+use std::collections::HashSet;
+use std::ops::{BitAnd, BitOr, Sub};
+
+api_enum! {
+    /// Permission granted by a role or membership
+    pub enum Permission {
+        CreateMotoPayments = "create_moto_payments",
+        CreateReferral = "create_referral",
+        FullTransactionHistoryView = "full_transaction_history_view",
+        RefundTransactions = "refund_transactions",
+        ViewPayouts = "view_payouts",
+        ViewReports = "view_reports",
+        ManageMembers = "manage_members",
+        DeveloperSettingsAccess = "developer_settings_access",
+        DeveloperSettingsEdit = "developer_settings_edit",
+    }
+}
+
+impl From<&str> for Permission {
+    fn from(value: &str) -> Self {
+        value.parse().unwrap_or_else(|never| match never {})
+    }
+}
+
+impl From<&String> for Permission {
+    fn from(value: &String) -> Self {
+        Self::from(value.as_str())
+    }
+}
+
+/// An unordered set of permissions
+///
+/// Supports the usual set algebra through methods and the `|`, `&` and `-` operators on
+/// references, like `std::collections::HashSet`. Permission strings the crate does not
+/// know are kept as [`Permission::Unknown`] so typos can be found with [`Self::unknown`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionSet {
+    permissions: HashSet<Permission>,
+}
+
+impl PermissionSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a set from permission strings as used by the API
+    pub fn from_strings<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        values
+            .into_iter()
+            .map(|v| Permission::from(v.as_ref()))
+            .collect()
+    }
+
+    pub fn insert(&mut self, permission: Permission) -> bool {
+        self.permissions.insert(permission)
+    }
+
+    pub fn remove(&mut self, permission: &Permission) -> bool {
+        self.permissions.remove(permission)
+    }
+
+    pub fn contains(&self, permission: &Permission) -> bool {
+        self.permissions.contains(permission)
+    }
+
+    pub fn len(&self) -> usize {
+        self.permissions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.permissions.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Permission> {
+        self.permissions.iter()
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.permissions
+            .union(&other.permissions)
+            .cloned()
+            .collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.permissions
+            .intersection(&other.permissions)
+            .cloned()
+            .collect()
+    }
+
+    /// Permissions in `self` that are not in `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        self.permissions
+            .difference(&other.permissions)
+            .cloned()
+            .collect()
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.permissions
+            .symmetric_difference(&other.permissions)
+            .cloned()
+            .collect()
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.permissions.is_subset(&other.permissions)
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        self.permissions.is_superset(&other.permissions)
+    }
+
+    /// Permission strings the crate does not know, sorted
+    pub fn unknown(&self) -> Vec<&str> {
+        let mut unknown: Vec<&str> = self
+            .iter()
+            .filter(|p| p.is_unknown())
+            .map(Permission::as_str)
+            .collect();
+        unknown.sort_unstable();
+        unknown
+    }
+
+    /// Permission strings as sent to the API, sorted for stable requests
+    pub fn to_strings(&self) -> Vec<String> {
+        let mut values: Vec<String> = self.iter().map(|p| p.as_str().to_string()).collect();
+        values.sort_unstable();
+        values
+    }
+
+    /// The permissions sorted by their API string, as sent in role requests
+    pub fn to_vec(&self) -> Vec<Permission> {
+        let mut values: Vec<Permission> = self.iter().cloned().collect();
+        values.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+        values
+    }
+}
+
+impl FromIterator<Permission> for PermissionSet {
+    fn from_iter<I: IntoIterator<Item = Permission>>(iter: I) -> Self {
+        Self {
+            permissions: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<Permission> for PermissionSet {
+    fn extend<I: IntoIterator<Item = Permission>>(&mut self, iter: I) {
+        self.permissions.extend(iter);
+    }
+}
+
+impl IntoIterator for PermissionSet {
+    type Item = Permission;
+    type IntoIter = std::collections::hash_set::IntoIter<Permission>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.permissions.into_iter()
+    }
+}
+
+impl<const N: usize> From<[Permission; N]> for PermissionSet {
+    fn from(permissions: [Permission; N]) -> Self {
+        permissions.into_iter().collect()
+    }
+}
+
+impl BitOr for &PermissionSet {
+    type Output = PermissionSet;
+
+    fn bitor(self, rhs: Self) -> PermissionSet {
+        self.union(rhs)
+    }
+}
+
+impl BitAnd for &PermissionSet {
+    type Output = PermissionSet;
+
+    fn bitand(self, rhs: Self) -> PermissionSet {
+        self.intersection(rhs)
+    }
+}
+
+impl Sub for &PermissionSet {
+    type Output = PermissionSet;
+
+    fn sub(self, rhs: Self) -> PermissionSet {
+        self.difference(rhs)
+    }
+}
+
+/// A predefined role: a name and the permissions it should grant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoleTemplate {
+    pub name: String,
+    pub permissions: PermissionSet,
+}
+
+impl RoleTemplate {
+    pub fn new(name: impl Into<String>, permissions: impl Into<PermissionSet>) -> Self {
+        Self {
+            name: name.into(),
+            permissions: permissions.into(),
+        }
+    }
+
+    /// Takes payments at the till
+    ///
+    /// Card-present payments need no permission of their own, so the only grant is
+    /// [`Permission::CreateMotoPayments`] for keying in mail and phone orders. Refunds are
+    /// left to [`Self::manager`].
+    pub fn cashier() -> Self {
+        Self::new("Cashier", [Permission::CreateMotoPayments])
+    }
+
+    /// Runs a location: payments, refunds, history, reports and the team
+    pub fn manager() -> Self {
+        Self::new(
+            "Manager",
+            [
+                Permission::CreateMotoPayments,
+                Permission::RefundTransactions,
+                Permission::FullTransactionHistoryView,
+                Permission::ViewReports,
+                Permission::ManageMembers,
+            ],
+        )
+    }
+
+    /// Read-only access to the books
+    pub fn accountant() -> Self {
+        Self::new(
+            "Accountant",
+            [
+                Permission::FullTransactionHistoryView,
+                Permission::ViewPayouts,
+                Permission::ViewReports,
+            ],
+        )
+    }
+
+    /// Compares the template with an existing role
+    pub fn diff(&self, role: &Role) -> RoleDiff {
+        let current = role.permission_set();
+        RoleDiff {
+            missing: &self.permissions - &current,
+            extra: &current - &self.permissions,
+        }
+    }
+
+    pub fn to_create_request(&self) -> CreateRoleRequest {
+        CreateRoleRequest::new(self.name.clone(), self.permissions.clone())
+    }
+
+    pub fn to_update_request(&self) -> UpdateRoleRequest {
+        UpdateRoleRequest {
+            name: self.name.clone(),
+            permissions: self.permissions.to_vec(),
+        }
+    }
+}
+
+/// Difference between a [`RoleTemplate`] and an existing [`Role`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RoleDiff {
+    /// Permissions the template grants but the role lacks
+    pub missing: PermissionSet,
+    /// Permissions the role grants beyond the template
+    pub extra: PermissionSet,
+}
+
+impl RoleDiff {
+    /// Whether the role grants exactly the template's permissions
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_permissions_are_kept() {
+        let set = PermissionSet::from_strings([
+            "refund_transactions",
+            "refund_transaction",
+            "view_reports",
+        ]);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&Permission::RefundTransactions));
+        assert_eq!(set.unknown(), vec!["refund_transaction"]);
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = PermissionSet::from([Permission::ViewReports, Permission::ViewPayouts]);
+        let b = PermissionSet::from([Permission::ViewPayouts, Permission::ManageMembers]);
+
+        assert_eq!((&a | &b).len(), 3);
+        assert_eq!(&a & &b, PermissionSet::from([Permission::ViewPayouts]));
+        assert_eq!(&a - &b, PermissionSet::from([Permission::ViewReports]));
+        assert_eq!(
+            a.symmetric_difference(&b),
+            PermissionSet::from([Permission::ViewReports, Permission::ManageMembers])
+        );
+        assert!((&a & &b).is_subset(&a));
+        assert!((&a | &b).is_superset(&b));
+    }
+
+    #[test]
+    fn test_templates_are_distinct() {
+        let cashier = RoleTemplate::cashier().permissions;
+        let manager = RoleTemplate::manager().permissions;
+        let accountant = RoleTemplate::accountant().permissions;
+        assert!(cashier.is_subset(&manager));
+        assert!(!accountant.contains(&Permission::RefundTransactions));
+        assert_eq!(
+            RoleTemplate::accountant().to_create_request().permissions,
+            vec![
+                Permission::FullTransactionHistoryView,
+                Permission::ViewPayouts,
+                Permission::ViewReports,
+            ]
+        );
+    }
+}
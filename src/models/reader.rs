@@ -1,4 +1,12 @@
-use super::enums::{Amount, Currency, MerchantCode, ReaderCardType, ReaderDeviceModel, ReaderStatus, TransactionId};
+use super::enums::{
+    Amount, ClientTransactionId, Currency, MerchantCode, ReaderCardType, ReaderDeviceModel,
+    ReaderId, ReaderStatus, TransactionId,
+};
+use super::money::Money;
+use super::tip::{validate_tip_rates, validate_tip_timeout, TipBreakdown, TipRate};
+use crate::decode::api_enum;
+use crate::utils::{impl_extra_fields, UnknownFields};
+use crate::{Error, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -16,7 +24,7 @@ pub struct ReaderDevice {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Reader {
     /// Unique identifier of the reader (30 chars, e.g., "rdr_3MSAFM23CK82VSTT4BN6RWSQ65")
-    pub id: String,
+    pub id: ReaderId,
     /// Custom human-readable, user-defined name for easier identification
     pub name: String,
     /// The current status of the reader
@@ -30,8 +38,14 @@ pub struct Reader {
     pub created_at: DateTime<Utc>,
     /// The timestamp of when the reader was last updated
     pub updated_at: DateTime<Utc>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
 }
 
+impl_extra_fields!(Reader);
+
 /// Response for listing readers
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReaderListResponse {
@@ -99,13 +113,59 @@ pub struct TotalAmount {
 }
 
 impl TotalAmount {
-    /// Create a new TotalAmount from an Amount (using exact decimal arithmetic)
+    /// Create a new TotalAmount from an Amount, using the currency's minor unit
+    /// (two decimals for unknown currency codes)
+    ///
+    /// Rounds half away from zero, like [`Money::rounded`]. Negative amounts become zero
+    /// and amounts too large for a reader checkout become `u64::MAX` minor units; use
+    /// [`Self::try_from_amount`] to reject them instead.
+    #[deprecated(since = "0.2.0", note = "use try_from_amount, which rejects negative and oversized amounts")]
     pub fn from_amount(amount: Amount, currency: impl Into<Currency>) -> Self {
-        Self {
-            value: amount.to_cents() as u64,
-            currency: currency.into(),
-            minor_unit: 2,
-        }
+        let currency = currency.into();
+        Self::try_from_amount(amount, currency.clone()).unwrap_or_else(|_| {
+            let minor_unit = currency.minor_units().unwrap_or(2);
+            Self {
+                value: if amount.0.is_sign_negative() { 0 } else { u64::MAX },
+                currency,
+                minor_unit: minor_unit as u8,
+            }
+        })
+    }
+
+    /// Create a new TotalAmount from an Amount, using the currency's minor unit
+    /// (two decimals for unknown currency codes)
+    ///
+    /// Rounds half away from zero, like [`Money::rounded`]. Fails for negative amounts
+    /// and amounts too large for a reader checkout.
+    pub fn try_from_amount(amount: Amount, currency: impl Into<Currency>) -> Result<Self> {
+        use rust_decimal::prelude::ToPrimitive;
+        let currency = currency.into();
+        let minor_unit = currency.minor_units().unwrap_or(2);
+        let scaled = amount.0.round_dp_with_strategy(minor_unit, rust_decimal::RoundingStrategy::MidpointAwayFromZero);
+        let value = scaled
+            .checked_mul(rust_decimal::Decimal::from(10u64.pow(minor_unit)))
+            .and_then(|v| v.to_u64())
+            .ok_or_else(|| Error::InvalidInput(format!("reader checkout amount {} {} is negative or too large", amount, currency)))?;
+        Ok(Self {
+            value,
+            currency,
+            minor_unit: minor_unit as u8,
+        })
+    }
+
+    /// Create a new TotalAmount from a validated money value.
+    ///
+    /// Fails for negative amounts, which reader checkouts cannot charge.
+    pub fn from_money(money: &Money) -> Result<Self> {
+        let value = money.to_minor_units()?;
+        let value = u64::try_from(value).map_err(|_| {
+            Error::InvalidInput(format!("reader checkout amount must not be negative, got {}", money))
+        })?;
+        Ok(Self {
+            value,
+            currency: money.currency().clone(),
+            minor_unit: money.minor_units() as u8,
+        })
     }
 
     /// Create a new TotalAmount from minor units (e.g., cents)
@@ -118,8 +178,36 @@ impl TotalAmount {
     }
 
     /// Get the value as an Amount (exact decimal)
+    ///
+    /// A `minor_unit` larger than a decimal can represent is capped at 28 decimals; use
+    /// [`Self::try_to_amount`] to reject it instead.
     pub fn to_amount(&self) -> Amount {
-        Amount::from_cents(self.value as i64)
+        let scale = u32::from(self.minor_unit).min(rust_decimal::Decimal::MAX_SCALE);
+        Amount(rust_decimal::Decimal::from_i128_with_scale(i128::from(self.value), scale))
+    }
+
+    /// Get the value as an Amount (exact decimal)
+    ///
+    /// Fails if `minor_unit` is larger than a decimal can represent.
+    pub fn try_to_amount(&self) -> Result<Amount> {
+        let mut value = rust_decimal::Decimal::from(self.value);
+        value
+            .set_scale(self.minor_unit as u32)
+            .map_err(|_| Error::InvalidInput(format!("unsupported minor unit {} for {}", self.minor_unit, self.currency)))?;
+        Ok(Amount(value))
+    }
+
+    /// Get the value as a validated money value
+    pub fn to_money(&self) -> Result<Money> {
+        Ok(Money::new(self.try_to_amount()?, self.currency.clone())?)
+    }
+}
+
+impl TryFrom<&Money> for TotalAmount {
+    type Error = Error;
+
+    fn try_from(money: &Money) -> Result<Self> {
+        Self::from_money(money)
     }
 }
 
@@ -213,13 +301,70 @@ impl CreateReaderCheckoutRequest {
         self.tip_timeout = Some(timeout);
         self
     }
+
+    /// Set the tipping rates from validated, exact rates (sorted ascending)
+    pub fn tip_rate_values(mut self, rates: &[TipRate]) -> Self {
+        let mut rates = rates.to_vec();
+        rates.sort();
+        rates.dedup();
+        self.tip_rates = Some(rates.iter().map(TipRate::to_f32).collect());
+        self
+    }
+
+    /// Split a bill into `parts` reader checkouts whose amounts sum exactly to `total`
+    /// (see [`Money::split`])
+    pub fn split(total: &Money, parts: usize) -> Result<Vec<Self>> {
+        total
+            .split(parts)?
+            .iter()
+            .map(|share| Ok(Self::new(TotalAmount::from_money(share)?)))
+            .collect()
+    }
+
+    /// Check the tipping constraints
+    pub fn validate(&self) -> Result<()> {
+        if let Some(rates) = &self.tip_rates {
+            validate_tip_rates(rates)?;
+        }
+        if let Some(timeout) = self.tip_timeout {
+            validate_tip_timeout(timeout)?;
+        }
+        Ok(())
+    }
+
+    /// Check that the amount uses the minor unit the crate knows for its currency
+    ///
+    /// Not part of [`Self::validate`]: the API is the authority on minor units, so this
+    /// is only a hint for callers that build `total_amount` by hand.
+    pub fn check_minor_unit(&self) -> Result<()> {
+        let currency = &self.total_amount.currency;
+        if let Some(minor_units) = currency.minor_units() {
+            if u32::from(self.total_amount.minor_unit) != minor_units {
+                return Err(Error::InvalidInput(format!(
+                    "{} amounts have {} minor units, got {}",
+                    currency, minor_units, self.total_amount.minor_unit
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Tip, net and total for each offered tipping rate, with tips added on top of
+    /// the checkout amount
+    pub fn tip_breakdowns(&self) -> Result<Vec<TipBreakdown>> {
+        let net = self.total_amount.to_money()?;
+        validate_tip_rates(self.tip_rates.as_deref().unwrap_or_default())?
+            .iter()
+            .map(|rate| rate.breakdown_on_net(&net))
+            .collect()
+    }
 }
 
 /// Response data from creating a reader checkout
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReaderCheckoutResponseData {
     /// The client transaction ID (UUID) for fetching transaction details later
-    pub client_transaction_id: String,
+    pub client_transaction_id: ClientTransactionId,
 }
 
 /// Response from creating a reader checkout
@@ -243,7 +388,7 @@ pub struct ReaderCheckoutStatusChange {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReaderCheckoutStatusPayload {
     /// The unique client transaction ID
-    pub client_transaction_id: String,
+    pub client_transaction_id: ClientTransactionId,
     /// The merchant code associated with the transaction
     pub merchant_code: MerchantCode,
     /// The current status of the transaction
@@ -253,10 +398,10 @@ pub struct ReaderCheckoutStatusPayload {
     pub transaction_id: Option<TransactionId>,
 }
 
-/// Status of a reader checkout transaction
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum ReaderCheckoutTransactionStatus {
-    Successful,
-    Failed,
+api_enum! {
+    /// Status of a reader checkout transaction
+    pub enum ReaderCheckoutTransactionStatus {
+        Successful = "successful",
+        Failed = "failed",
+    }
 }
@@ -1,4 +1,5 @@
-use super::enums::{Amount, Currency, MerchantCode, TransactionId};
+use super::enums::{Amount, Currency, MerchantCode, ReceiptId, TransactionId};
+use crate::utils::{impl_extra_fields, UnknownFields};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -6,7 +7,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Receipt {
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub id: Option<String>,
+    pub id: Option<ReceiptId>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub transaction_id: Option<TransactionId>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -33,11 +34,13 @@ pub struct Receipt {
     /// Merchant details
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub merchant: Option<ReceiptMerchant>,
-    /// Catch any extra fields
+    /// Fields not modelled by this crate (always captured for this model)
     #[serde(flatten)]
-    pub extra: Option<std::collections::HashMap<String, serde_json::Value>>,
+    pub extra: UnknownFields,
 }
 
+impl_extra_fields!(Receipt);
+
 /// Line item on a receipt
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReceiptItem {
@@ -0,0 +1,430 @@
+use super::enums::{Currency, TransactionStatus};
+use super::money::Money;
+use super::payout::{Payout, PayoutType};
+use super::transaction::Transaction;
+use crate::Result;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// A payout entry matched to the transaction it pays out or deducts
+#[derive(Debug, Clone)]
+pub struct ReconciledEntry {
+    pub payout: Payout,
+    pub transaction: Transaction,
+}
+
+/// Why a matched payout entry does not agree with its transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy {
+    /// The payout entry and the transaction are in different currencies
+    CurrencyMismatch {
+        payout: Currency,
+        transaction: Currency,
+    },
+    /// Amount plus fee of a payout entry differs from the transaction amount
+    AmountMismatch { expected: Money, actual: Money },
+    /// Refund and chargeback deductions that together take back more than the original
+    /// transaction; `deducted` is their running total up to and including this entry
+    DeductionExceedsTransaction { deducted: Money, transaction: Money },
+    /// The transaction was already paid out by an earlier entry
+    DuplicatePayout,
+}
+
+/// A matched payout entry that needs attention
+#[derive(Debug, Clone)]
+pub struct DiscrepantEntry {
+    pub payout: Payout,
+    pub transaction: Transaction,
+    pub discrepancy: Discrepancy,
+}
+
+/// Why a payout entry could not be matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnmatchedReason {
+    /// The entry does not name a transaction (e.g. a balance deduction)
+    MissingTransactionCode,
+    /// No transaction with the entry's code was supplied
+    TransactionNotFound,
+}
+
+/// A payout entry without a matching transaction
+#[derive(Debug, Clone)]
+pub struct UnmatchedPayout {
+    pub payout: Payout,
+    pub reason: UnmatchedReason,
+}
+
+/// Totals of a [`ReconciliationReport`] for one currency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconciliationTotals {
+    pub currency: Currency,
+    /// Sum of `PAYOUT` entries, net of fees
+    pub paid_out: Money,
+    /// Fees SumUp kept on all entries
+    pub fees: Money,
+    /// Sum of refund, chargeback and other deduction entries (negative)
+    pub deductions: Money,
+    /// What reached the bank account: `paid_out + deductions`
+    pub net: Money,
+    /// Sum of the transactions paid out by matched `PAYOUT` entries
+    pub matched_sales: Money,
+    /// Sum of `PAYOUT` entries without a matching transaction
+    pub unmatched_payouts: Money,
+    /// Sum of deduction entries without a matching transaction (negative)
+    pub unmatched_deductions: Money,
+    /// Sum of transactions not paid out in the window
+    pub unmatched_transactions: Money,
+}
+
+impl ReconciliationTotals {
+    fn zero(currency: &Currency) -> Result<Self> {
+        let zero = Money::zero(currency.clone())?;
+        Ok(Self {
+            currency: currency.clone(),
+            paid_out: zero.clone(),
+            fees: zero.clone(),
+            deductions: zero.clone(),
+            net: zero.clone(),
+            matched_sales: zero.clone(),
+            unmatched_payouts: zero.clone(),
+            unmatched_deductions: zero.clone(),
+            unmatched_transactions: zero,
+        })
+    }
+}
+
+/// Result of matching payout entries to the transactions they pay out.
+///
+/// Build it from the entries returned by `list_merchant_payouts` and the transactions of
+/// the period those payouts cover (from `list_transactions_history`). SumUp reports each
+/// payout entry net of its fee, so a `PAYOUT` entry matches when `amount + fee` equals the
+/// transaction amount. Refund and chargeback deductions are matched to the original
+/// transaction by code and must not exceed it.
+#[derive(Debug, Clone, Default)]
+pub struct ReconciliationReport {
+    pub matched: Vec<ReconciledEntry>,
+    pub discrepancies: Vec<DiscrepantEntry>,
+    pub unmatched_payouts: Vec<UnmatchedPayout>,
+    /// Successful, refunded or charged back transactions no payout entry refers to
+    pub unmatched_transactions: Vec<Transaction>,
+    /// Transactions paid out before the window, seen here only through a refund or
+    /// chargeback deduction
+    pub settled_earlier: Vec<Transaction>,
+    /// Totals per currency, ordered by currency code
+    pub totals: Vec<ReconciliationTotals>,
+}
+
+impl ReconciliationReport {
+    /// Match payout entries to transactions by transaction code
+    pub fn reconcile<'a>(
+        payouts: impl IntoIterator<Item = &'a Payout>,
+        transactions: impl IntoIterator<Item = &'a Transaction>,
+    ) -> Result<Self> {
+        let transactions: Vec<&Transaction> = transactions.into_iter().collect();
+        let by_code: HashMap<&str, &Transaction> = transactions
+            .iter()
+            .map(|t| (t.transaction_code.as_str(), *t))
+            .collect();
+        let mut report = Self::default();
+        let mut totals: BTreeMap<String, ReconciliationTotals> = BTreeMap::new();
+        let mut paid_out: HashSet<&str> = HashSet::new();
+        // refund and chargeback deductions so far, per transaction code
+        let mut deducted: HashMap<&str, Money> = HashMap::new();
+
+        for payout in payouts {
+            let row = totals_entry(&mut totals, &payout.currency)?;
+            row.fees = row.fees.checked_add(&payout.fee_money()?)?;
+            if payout.payout_type.is_deduction() {
+                row.deductions = row.deductions.checked_add(&payout.money()?)?;
+            } else {
+                row.paid_out = row.paid_out.checked_add(&payout.money()?)?;
+            }
+
+            let found = match payout.transaction_code.as_deref() {
+                None => Err(UnmatchedReason::MissingTransactionCode),
+                Some(code) => by_code
+                    .get(code)
+                    .map(|t| (code, *t))
+                    .ok_or(UnmatchedReason::TransactionNotFound),
+            };
+            let (code, transaction) = match found {
+                Ok(found) => found,
+                Err(reason) => {
+                    if payout.payout_type.is_deduction() {
+                        row.unmatched_deductions =
+                            row.unmatched_deductions.checked_add(&payout.money()?)?;
+                    } else {
+                        row.unmatched_payouts =
+                            row.unmatched_payouts.checked_add(&payout.money()?)?;
+                    }
+                    report.unmatched(payout, reason);
+                    continue;
+                }
+            };
+
+            let discrepancy = if payout.currency != transaction.currency {
+                Some(Discrepancy::CurrencyMismatch {
+                    payout: payout.currency.clone(),
+                    transaction: transaction.currency.clone(),
+                })
+            } else if payout.payout_type.is_deduction() {
+                let so_far = match deducted.get(code) {
+                    Some(total) => total.clone(),
+                    None => Money::zero(payout.currency.clone())?,
+                };
+                let (total, discrepancy) = check_deduction(payout, transaction, &so_far)?;
+                deducted.insert(code, total);
+                discrepancy
+            } else if !paid_out.insert(code) {
+                Some(Discrepancy::DuplicatePayout)
+            } else {
+                let expected = transaction.money()?;
+                let actual = payout.money()?.checked_add(&payout.fee_money()?)?;
+                row.matched_sales = row.matched_sales.checked_add(&expected)?;
+                (expected != actual).then_some(Discrepancy::AmountMismatch { expected, actual })
+            };
+
+            match discrepancy {
+                Some(discrepancy) => report.discrepancies.push(DiscrepantEntry {
+                    payout: payout.clone(),
+                    transaction: transaction.clone(),
+                    discrepancy,
+                }),
+                None => report.matched.push(ReconciledEntry {
+                    payout: payout.clone(),
+                    transaction: transaction.clone(),
+                }),
+            }
+        }
+
+        for transaction in transactions {
+            let settles = matches!(
+                transaction.status,
+                None | Some(TransactionStatus::Successful)
+                    | Some(TransactionStatus::Refunded)
+                    | Some(TransactionStatus::ChargeBack)
+            );
+            let code = transaction.transaction_code.as_str();
+            if !settles || paid_out.contains(code) {
+                continue;
+            }
+            if deducted.contains_key(code) {
+                report.settled_earlier.push(transaction.clone());
+            } else {
+                let row = totals_entry(&mut totals, &transaction.currency)?;
+                row.unmatched_transactions = row
+                    .unmatched_transactions
+                    .checked_add(&transaction.money()?)?;
+                report.unmatched_transactions.push(transaction.clone());
+            }
+        }
+
+        for row in totals.values_mut() {
+            row.net = row.paid_out.checked_add(&row.deductions)?;
+        }
+        report.totals = totals.into_values().collect();
+        Ok(report)
+    }
+
+    /// Totals for one currency, if any entry or transaction used it
+    pub fn totals_for(&self, currency: &Currency) -> Option<&ReconciliationTotals> {
+        self.totals.iter().find(|t| &t.currency == currency)
+    }
+
+    /// Whether every payout entry and transaction was matched without discrepancy
+    pub fn is_clean(&self) -> bool {
+        self.discrepancies.is_empty()
+            && self.unmatched_payouts.is_empty()
+            && self.unmatched_transactions.is_empty()
+    }
+
+    fn unmatched(&mut self, payout: &Payout, reason: UnmatchedReason) {
+        self.unmatched_payouts.push(UnmatchedPayout {
+            payout: payout.clone(),
+            reason,
+        });
+    }
+}
+
+fn totals_entry<'a>(
+    totals: &'a mut BTreeMap<String, ReconciliationTotals>,
+    currency: &Currency,
+) -> Result<&'a mut ReconciliationTotals> {
+    Ok(match totals.entry(currency.to_string()) {
+        std::collections::btree_map::Entry::Occupied(e) => e.into_mut(),
+        std::collections::btree_map::Entry::Vacant(e) => {
+            e.insert(ReconciliationTotals::zero(currency)?)
+        }
+    })
+}
+
+/// Refund and chargeback deductions may not take back more than the transaction in
+/// total; returns the deductions so far including `payout`
+fn check_deduction(
+    payout: &Payout,
+    transaction: &Transaction,
+    so_far: &Money,
+) -> Result<(Money, Option<Discrepancy>)> {
+    if !matches!(
+        payout.payout_type,
+        PayoutType::RefundDeduction | PayoutType::ChargeBackDeduction
+    ) {
+        return Ok((so_far.clone(), None));
+    }
+    let deducted = so_far.checked_add(&payout.money()?)?;
+    let original = transaction.money()?;
+    let exceeds = deducted.amount().0.abs() > original.amount().0;
+    let discrepancy = exceeds.then(|| Discrepancy::DeductionExceedsTransaction {
+        deducted: deducted.clone(),
+        transaction: original,
+    });
+    Ok((deducted, discrepancy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::money::eur;
+
+    fn payouts() -> Vec<Payout> {
+        serde_json::from_value(serde_json::json!([
+            {"amount": 98.10, "fee": 1.90, "currency": "EUR", "date": "2024-03-04",
+             "status": "SUCCESSFUL", "type": "PAYOUT", "transaction_code": "TX1", "reference": "PO-1"},
+            {"amount": 49.00, "fee": 0.95, "currency": "EUR", "date": "2024-03-04",
+             "status": "SUCCESSFUL", "type": "PAYOUT", "transaction_code": "TX2", "reference": "PO-1"},
+            {"amount": -20.00, "currency": "EUR", "date": "2024-03-04",
+             "status": "SUCCESSFUL", "type": "REFUND_DEDUCTION", "transaction_code": "TX0", "reference": "PO-1"},
+            {"amount": -5.00, "currency": "EUR", "date": "2024-03-04",
+             "status": "SUCCESSFUL", "type": "BALANCE_DEDUCTION", "reference": "PO-1"},
+            {"amount": 10.00, "currency": "EUR", "date": "2024-03-04",
+             "status": "SUCCESSFUL", "type": "PAYOUT", "transaction_code": "TX9", "reference": "PO-1"}
+        ]))
+        .unwrap()
+    }
+
+    fn transactions() -> Vec<Transaction> {
+        serde_json::from_value(serde_json::json!([
+            {"id": "t0", "transaction_code": "TX0", "amount": 20.00, "currency": "EUR",
+             "timestamp": "2024-02-28T10:00:00Z", "status": "REFUNDED"},
+            {"id": "t1", "transaction_code": "TX1", "amount": 100.00, "currency": "EUR",
+             "timestamp": "2024-03-01T10:00:00Z", "status": "SUCCESSFUL"},
+            {"id": "t2", "transaction_code": "TX2", "amount": 50.00, "currency": "EUR",
+             "timestamp": "2024-03-01T11:00:00Z", "status": "SUCCESSFUL"},
+            {"id": "t3", "transaction_code": "TX3", "amount": 7.50, "currency": "EUR",
+             "timestamp": "2024-03-01T12:00:00Z", "status": "SUCCESSFUL"},
+            {"id": "t4", "transaction_code": "TX4", "amount": 3.00, "currency": "EUR",
+             "timestamp": "2024-03-01T13:00:00Z", "status": "FAILED"}
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_reconcile_classifies_entries() {
+        let report = ReconciliationReport::reconcile(&payouts(), &transactions()).unwrap();
+
+        let matched: Vec<_> = report
+            .matched
+            .iter()
+            .map(|m| m.transaction.transaction_code.as_str())
+            .collect();
+        assert_eq!(matched, vec!["TX1", "TX0"]);
+
+        assert_eq!(report.discrepancies.len(), 1);
+        assert_eq!(
+            report.discrepancies[0].discrepancy,
+            Discrepancy::AmountMismatch {
+                expected: eur(5000),
+                actual: eur(4995)
+            }
+        );
+
+        let reasons: Vec<_> = report.unmatched_payouts.iter().map(|u| u.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                UnmatchedReason::MissingTransactionCode,
+                UnmatchedReason::TransactionNotFound
+            ]
+        );
+
+        // TX0 was paid out before the window and only refunded in it; TX4 failed and
+        // never settles
+        let unpaid: Vec<_> = report
+            .unmatched_transactions
+            .iter()
+            .map(|t| t.transaction_code.as_str())
+            .collect();
+        assert_eq!(unpaid, vec!["TX3"]);
+        assert_eq!(report.settled_earlier.len(), 1);
+        assert_eq!(report.settled_earlier[0].transaction_code, "TX0");
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_totals_per_currency() {
+        let report = ReconciliationReport::reconcile(&payouts(), &transactions()).unwrap();
+        let totals = report.totals_for(&Currency::new("EUR")).unwrap();
+        assert_eq!(totals.paid_out, eur(15710));
+        assert_eq!(totals.fees, eur(285));
+        assert_eq!(totals.deductions, eur(-2500));
+        assert_eq!(totals.net, eur(13210));
+        assert_eq!(totals.matched_sales, eur(15000));
+        assert_eq!(totals.unmatched_payouts, eur(1000));
+        assert_eq!(totals.unmatched_deductions, eur(-500));
+        assert_eq!(totals.unmatched_transactions, eur(750));
+    }
+
+    #[test]
+    fn test_refund_of_earlier_sale_is_clean() {
+        let entries = vec![payouts()[0].clone(), payouts()[2].clone()];
+        let window = vec![transactions()[0].clone(), transactions()[1].clone()];
+        let report = ReconciliationReport::reconcile(&entries, &window).unwrap();
+        assert!(report.unmatched_transactions.is_empty());
+        assert_eq!(report.settled_earlier.len(), 1);
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn test_duplicate_and_excessive_deduction() {
+        let mut entries = payouts();
+        entries.push(entries[0].clone());
+        entries[2].amount = crate::Amount::from_cents(-2500);
+        let report = ReconciliationReport::reconcile(&entries, &transactions()).unwrap();
+
+        let kinds: Vec<_> = report
+            .discrepancies
+            .iter()
+            .map(|d| std::mem::discriminant(&d.discrepancy))
+            .collect();
+        assert!(kinds.contains(&std::mem::discriminant(&Discrepancy::DuplicatePayout)));
+        assert!(report.discrepancies.iter().any(|d| matches!(
+            d.discrepancy,
+            Discrepancy::DeductionExceedsTransaction { .. }
+        )));
+    }
+
+    #[test]
+    fn test_partial_refunds_are_checked_against_their_total() {
+        let refund = |cents: i64| {
+            let mut entry = payouts()[2].clone();
+            entry.transaction_code = Some("TX1".into());
+            entry.amount = crate::Amount::from_cents(cents);
+            entry
+        };
+        let window = vec![transactions()[1].clone()];
+
+        let within = vec![payouts()[0].clone(), refund(-6000), refund(-4000)];
+        let report = ReconciliationReport::reconcile(&within, &window).unwrap();
+        assert!(report.discrepancies.is_empty());
+
+        let exceeding = vec![payouts()[0].clone(), refund(-6000), refund(-5000)];
+        let report = ReconciliationReport::reconcile(&exceeding, &window).unwrap();
+        assert_eq!(report.discrepancies.len(), 1);
+        assert_eq!(
+            report.discrepancies[0].discrepancy,
+            Discrepancy::DeductionExceedsTransaction {
+                deducted: eur(-11000),
+                transaction: eur(10000)
+            }
+        );
+    }
+}
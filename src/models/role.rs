@@ -1,28 +1,57 @@
+use super::enums::{MembershipId, RoleId};
+use super::permission::{Permission, PermissionSet};
+use crate::utils::{impl_extra_fields, UnknownFields};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Role {
-    pub id: String,
+    pub id: RoleId,
     pub name: String,
-    pub membership_id: String,
-    pub permissions: Vec<String>,
+    pub membership_id: MembershipId,
+    pub permissions: Vec<Permission>,
     pub is_predefined: bool,
     pub created_at: DateTime<Utc>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
+}
+
+impl_extra_fields!(Role);
+
+impl Role {
+    pub fn permission_set(&self) -> PermissionSet {
+        self.permissions.iter().cloned().collect()
+    }
+
+    pub fn has_permission(&self, permission: &Permission) -> bool {
+        self.permissions.contains(permission)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateRoleRequest {
     pub name: String,
-    pub permissions: Vec<String>,
+    pub permissions: Vec<Permission>,
+}
+
+impl CreateRoleRequest {
+    pub fn new(name: impl Into<String>, permissions: impl IntoIterator<Item = Permission>) -> Self {
+        let permissions: PermissionSet = permissions.into_iter().collect();
+        Self {
+            name: name.into(),
+            permissions: permissions.to_vec(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateRoleRequest {
     pub name: String,
-    pub permissions: Vec<String>,
+    pub permissions: Vec<Permission>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,503 @@
+use super::enums::{Amount, Currency, TransactionStatus};
+use super::money::Money;
+use super::transaction::Transaction;
+use crate::{Error, Result};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How VAT is rounded to the currency's minor unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VatRounding {
+    /// Round the VAT of every line, then add the lines up
+    #[default]
+    PerLine,
+    /// Add up the lines of each rate, then round the VAT once per rate
+    PerTotal,
+}
+
+/// Whether line item prices include VAT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PriceBasis {
+    /// Prices are net; VAT is added on top
+    #[default]
+    Net,
+    /// Prices are gross; VAT is extracted from them
+    Gross,
+}
+
+/// A product line on a checkout or transaction.
+///
+/// Rates are fractions (`0.19` for 19%). `price` is the unit price on the basis used to
+/// build the line; the computed fields are filled in by [`VatCalculator`] and returned by
+/// the API on transaction details.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Product {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_quantity")]
+    pub quantity: u32,
+    /// Unit price; zero when a line from the API has none, in which case reports rely on
+    /// the line totals
+    #[serde(default)]
+    pub price: Amount,
+    #[serde(default, with = "rust_decimal::serde::arbitrary_precision")]
+    pub vat_rate: Decimal,
+    /// Unit price including VAT
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_with_vat: Option<Amount>,
+    /// Total VAT of the line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vat_amount: Option<Amount>,
+    /// Total net price of the line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_price: Option<Amount>,
+    /// Total gross price of the line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_with_vat: Option<Amount>,
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+impl Product {
+    /// A line of `quantity` units at `price` each, taxed at `vat_rate` (a fraction
+    /// between 0 and 1)
+    pub fn new(
+        name: impl Into<String>,
+        quantity: u32,
+        price: impl Into<Amount>,
+        vat_rate: Decimal,
+    ) -> Result<Self> {
+        if vat_rate < Decimal::ZERO || vat_rate >= Decimal::ONE {
+            return Err(Error::InvalidInput(format!(
+                "VAT rate {} must be a fraction between 0 and 1",
+                vat_rate
+            )));
+        }
+        if quantity == 0 {
+            return Err(Error::InvalidInput("quantity must be at least 1".into()));
+        }
+        Ok(Self {
+            name: name.into(),
+            quantity,
+            price: price.into(),
+            vat_rate: vat_rate.normalize(),
+            price_with_vat: None,
+            vat_amount: None,
+            total_price: None,
+            total_with_vat: None,
+        })
+    }
+}
+
+/// VAT totals for one rate, as returned on transaction details
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VatRateSummary {
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub rate: Decimal,
+    pub net: Amount,
+    pub vat: Amount,
+    pub gross: Amount,
+}
+
+/// Net, VAT and gross amounts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VatAmounts {
+    pub net: Money,
+    pub vat: Money,
+    pub gross: Money,
+}
+
+impl VatAmounts {
+    fn zero(currency: &Currency) -> Result<Self> {
+        Ok(Self {
+            net: Money::zero(currency.clone())?,
+            vat: Money::zero(currency.clone())?,
+            gross: Money::zero(currency.clone())?,
+        })
+    }
+
+    fn add(&mut self, other: &VatAmounts) -> Result<()> {
+        self.net = self.net.checked_add(&other.net)?;
+        self.vat = self.vat.checked_add(&other.vat)?;
+        self.gross = self.gross.checked_add(&other.gross)?;
+        Ok(())
+    }
+}
+
+/// Result of a [`VatCalculator`] run
+#[derive(Debug, Clone, PartialEq)]
+pub struct VatBreakdown {
+    /// Products with their computed totals filled in
+    pub lines: Vec<Product>,
+    /// Totals per VAT rate, ordered by rate
+    pub rates: Vec<(Decimal, VatAmounts)>,
+    /// Totals over all lines
+    pub total: VatAmounts,
+}
+
+/// Computes net, VAT and gross amounts for product lines in one currency.
+///
+/// With [`VatRounding::PerTotal`] the line amounts are informational: they are rounded
+/// individually and may not add up to the per-rate totals, which are authoritative.
+#[derive(Debug, Clone)]
+pub struct VatCalculator {
+    currency: Currency,
+    rounding: VatRounding,
+    basis: PriceBasis,
+}
+
+impl VatCalculator {
+    pub fn new(currency: impl Into<Currency>) -> Result<Self> {
+        let currency = currency.into();
+        Money::zero(currency.clone())?;
+        Ok(Self {
+            currency,
+            rounding: VatRounding::default(),
+            basis: PriceBasis::default(),
+        })
+    }
+
+    pub fn rounding(mut self, rounding: VatRounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn basis(mut self, basis: PriceBasis) -> Self {
+        self.basis = basis;
+        self
+    }
+
+    pub fn calculate(&self, products: &[Product]) -> Result<VatBreakdown> {
+        let mut lines = Vec::with_capacity(products.len());
+        let mut line_amounts = Vec::with_capacity(products.len());
+        for product in products {
+            let amounts = self.split(
+                product.price.0 * Decimal::from(product.quantity),
+                product.vat_rate,
+            )?;
+            let unit = self.split(product.price.0, product.vat_rate)?;
+            let mut line = product.clone();
+            line.price_with_vat = Some(unit.gross.amount());
+            line.vat_amount = Some(amounts.vat.amount());
+            line.total_price = Some(amounts.net.amount());
+            line.total_with_vat = Some(amounts.gross.amount());
+            lines.push(line);
+            line_amounts.push((product.vat_rate, amounts));
+        }
+
+        let mut rates: BTreeMap<Decimal, VatAmounts> = BTreeMap::new();
+        match self.rounding {
+            VatRounding::PerLine => {
+                for (rate, amounts) in &line_amounts {
+                    accumulate(&mut rates, *rate, amounts, &self.currency)?;
+                }
+            }
+            VatRounding::PerTotal => {
+                let mut bases: BTreeMap<Decimal, Decimal> = BTreeMap::new();
+                for product in products {
+                    *bases.entry(product.vat_rate).or_default() +=
+                        product.price.0 * Decimal::from(product.quantity);
+                }
+                for (rate, base) in bases {
+                    rates.insert(rate, self.split(base, rate)?);
+                }
+            }
+        }
+
+        let mut total = VatAmounts::zero(&self.currency)?;
+        for amounts in rates.values() {
+            total.add(amounts)?;
+        }
+        Ok(VatBreakdown {
+            lines,
+            rates: rates.into_iter().collect(),
+            total,
+        })
+    }
+
+    /// Split an amount on the calculator's price basis into net, VAT and gross
+    fn split(&self, value: Decimal, rate: Decimal) -> Result<VatAmounts> {
+        let round = |v: Decimal| Money::rounded(v, self.currency.clone());
+        Ok(match self.basis {
+            PriceBasis::Net => {
+                let net = round(value)?;
+                let vat = round(net.amount().0 * rate)?;
+                let gross = net.checked_add(&vat)?;
+                VatAmounts { net, vat, gross }
+            }
+            PriceBasis::Gross => {
+                let gross = round(value)?;
+                let net = round(gross.amount().0 / (Decimal::ONE + rate))?;
+                let vat = gross.checked_sub(&net)?;
+                VatAmounts { net, vat, gross }
+            }
+        })
+    }
+}
+
+/// VAT totals per currency and rate across transactions, for tax reporting
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VatReport {
+    /// Totals per currency and rate, ordered by currency then rate
+    pub rates: Vec<VatReportLine>,
+    /// VAT reported on transactions without a per-rate breakdown, per currency
+    pub unclassified: Vec<VatReportLine>,
+    /// Transactions left out: not successful, or carrying no VAT information
+    pub skipped: usize,
+}
+
+/// One row of a [`VatReport`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VatReportLine {
+    pub currency: Currency,
+    /// `None` for unclassified VAT
+    pub rate: Option<Decimal>,
+    pub amounts: VatAmounts,
+    /// Number of transactions contributing to the row
+    pub transactions: usize,
+}
+
+impl VatReport {
+    /// Aggregate VAT by rate.
+    ///
+    /// Only successful transactions (or ones without a status) are counted. The per-rate
+    /// `vat_rates` summary is used when present, otherwise the `products`; transactions
+    /// with only `vat_amount` are reported as unclassified.
+    pub fn from_transactions<'a>(
+        transactions: impl IntoIterator<Item = &'a Transaction>,
+    ) -> Result<Self> {
+        let mut rates: BTreeMap<(String, Decimal), VatReportLine> = BTreeMap::new();
+        let mut unclassified: BTreeMap<String, VatReportLine> = BTreeMap::new();
+        let mut skipped = 0;
+
+        for txn in transactions {
+            if !matches!(txn.status, None | Some(TransactionStatus::Successful)) {
+                skipped += 1;
+                continue;
+            }
+            let currency = &txn.currency;
+            let money = |a: Amount| Money::new(a, currency.clone());
+            let mut rows: BTreeMap<Decimal, VatAmounts> = BTreeMap::new();
+
+            if let Some(summaries) = txn.vat_rates.as_ref().filter(|s| !s.is_empty()) {
+                for s in summaries {
+                    let amounts = VatAmounts {
+                        net: money(s.net)?,
+                        vat: money(s.vat)?,
+                        gross: money(s.gross)?,
+                    };
+                    accumulate(&mut rows, s.rate.normalize(), &amounts, currency)?;
+                }
+            } else if let Some(products) = txn.products.as_ref().filter(|p| !p.is_empty()) {
+                for p in products {
+                    let net = match p.total_price {
+                        Some(total) => money(total)?,
+                        None => {
+                            Money::rounded(p.price.0 * Decimal::from(p.quantity), currency.clone())?
+                        }
+                    };
+                    let vat = match p.vat_amount {
+                        Some(vat) => money(vat)?,
+                        None => Money::rounded(net.amount().0 * p.vat_rate, currency.clone())?,
+                    };
+                    let gross = match p.total_with_vat {
+                        Some(gross) => money(gross)?,
+                        None => net.checked_add(&vat)?,
+                    };
+                    accumulate(
+                        &mut rows,
+                        p.vat_rate.normalize(),
+                        &VatAmounts { net, vat, gross },
+                        currency,
+                    )?;
+                }
+            } else if let Some(vat) = txn.vat_amount {
+                let gross = txn.net_of_tip()?;
+                let vat = money(vat)?;
+                let amounts = VatAmounts {
+                    net: gross.checked_sub(&vat)?,
+                    vat,
+                    gross,
+                };
+                let line = unclassified
+                    .entry(currency.to_string())
+                    .or_insert(VatReportLine {
+                        currency: currency.clone(),
+                        rate: None,
+                        amounts: VatAmounts::zero(currency)?,
+                        transactions: 0,
+                    });
+                line.amounts.add(&amounts)?;
+                line.transactions += 1;
+                continue;
+            } else {
+                skipped += 1;
+                continue;
+            }
+
+            for (rate, amounts) in rows {
+                let line = match rates.entry((currency.to_string(), rate)) {
+                    std::collections::btree_map::Entry::Occupied(e) => e.into_mut(),
+                    std::collections::btree_map::Entry::Vacant(e) => e.insert(VatReportLine {
+                        currency: currency.clone(),
+                        rate: Some(rate),
+                        amounts: VatAmounts::zero(currency)?,
+                        transactions: 0,
+                    }),
+                };
+                line.amounts.add(&amounts)?;
+                line.transactions += 1;
+            }
+        }
+
+        Ok(Self {
+            rates: rates.into_values().collect(),
+            unclassified: unclassified.into_values().collect(),
+            skipped,
+        })
+    }
+}
+
+fn accumulate(
+    rows: &mut BTreeMap<Decimal, VatAmounts>,
+    rate: Decimal,
+    amounts: &VatAmounts,
+    currency: &Currency,
+) -> Result<()> {
+    let row = match rows.entry(rate) {
+        std::collections::btree_map::Entry::Occupied(e) => e.into_mut(),
+        std::collections::btree_map::Entry::Vacant(e) => e.insert(VatAmounts::zero(currency)?),
+    };
+    row.add(amounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::money::eur;
+
+    fn rate(percent: i64) -> Decimal {
+        Decimal::new(percent, 2)
+    }
+
+    #[test]
+    fn test_per_line_and_per_total_rounding_differ() {
+        // Three lines of 0.02 at 19%: 0.0038 VAT each, 0.0114 together
+        let products =
+            vec![Product::new("sticker", 1, Amount::from_cents(2), rate(19)).unwrap(); 3];
+
+        let per_line = VatCalculator::new("EUR")
+            .unwrap()
+            .calculate(&products)
+            .unwrap();
+        assert_eq!(per_line.total.vat, eur(0));
+        assert_eq!(per_line.total.gross, eur(6));
+        assert_eq!(per_line.lines[0].vat_amount, Some(Amount::from_cents(0)));
+
+        let per_total = VatCalculator::new("EUR")
+            .unwrap()
+            .rounding(VatRounding::PerTotal)
+            .calculate(&products)
+            .unwrap();
+        assert_eq!(per_total.total.vat, eur(1));
+        assert_eq!(per_total.total.gross, eur(7));
+    }
+
+    #[test]
+    fn test_report_aggregates_by_rate() {
+        let transactions: Vec<Transaction> = serde_json::from_value(serde_json::json!([
+            {
+                "id": "t1", "transaction_code": "T1", "amount": 11.9, "currency": "EUR",
+                "timestamp": "2024-01-15T10:30:00Z", "status": "SUCCESSFUL",
+                "vat_rates": [{"rate": 0.19, "net": 10.0, "vat": 1.9, "gross": 11.9}]
+            },
+            {
+                "id": "t2", "transaction_code": "T2", "amount": 13.0, "currency": "EUR",
+                "timestamp": "2024-01-15T11:00:00Z", "status": "SUCCESSFUL",
+                "products": [
+                    {"name": "mug", "quantity": 1, "price": 10.0, "vat_rate": 0.19},
+                    {"name": "tea", "quantity": 1, "price": 1.0, "vat_rate": 0.07,
+                     "vat_amount": 0.07, "total_price": 1.0, "total_with_vat": 1.07}
+                ]
+            },
+            {
+                "id": "t3", "transaction_code": "T3", "amount": 5.5, "currency": "EUR",
+                "timestamp": "2024-01-15T12:00:00Z", "status": "SUCCESSFUL",
+                "vat_amount": 0.88, "tip_amount": 0.5
+            },
+            {
+                "id": "t4", "transaction_code": "T4", "amount": 99.0, "currency": "EUR",
+                "timestamp": "2024-01-15T13:00:00Z", "status": "FAILED", "vat_amount": 15.8
+            }
+        ]))
+        .unwrap();
+
+        let report = VatReport::from_transactions(&transactions).unwrap();
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.rates.len(), 2);
+        assert_eq!(report.rates[0].rate, Some(rate(7)));
+        assert_eq!(report.rates[0].amounts.vat, eur(7));
+        let standard = &report.rates[1];
+        assert_eq!(standard.rate, Some(rate(19)));
+        assert_eq!(standard.transactions, 2);
+        assert_eq!(standard.amounts.net, eur(2000));
+        assert_eq!(standard.amounts.vat, eur(380));
+        assert_eq!(standard.amounts.gross, eur(2380));
+
+        let unclassified = &report.unclassified[0];
+        assert_eq!(unclassified.amounts.gross, eur(500));
+        assert_eq!(unclassified.amounts.net, eur(412));
+    }
+
+    #[test]
+    fn test_gross_prices_and_rates() {
+        let products = vec![
+            Product::new("coffee", 2, Amount::from_cents(250), rate(7)).unwrap(),
+            Product::new("mug", 1, Amount::from_cents(1190), rate(19)).unwrap(),
+        ];
+        let breakdown = VatCalculator::new("EUR")
+            .unwrap()
+            .basis(PriceBasis::Gross)
+            .calculate(&products)
+            .unwrap();
+        assert_eq!(breakdown.total.gross, eur(1690));
+        assert_eq!(breakdown.rates.len(), 2);
+        let (r, standard) = &breakdown.rates[1];
+        assert_eq!(*r, rate(19));
+        assert_eq!(
+            (standard.net.clone(), standard.vat.clone()),
+            (eur(1000), eur(190))
+        );
+        let (_, reduced) = &breakdown.rates[0];
+        assert_eq!(
+            (reduced.net.clone(), reduced.vat.clone()),
+            (eur(467), eur(33))
+        );
+    }
+
+    #[test]
+    fn test_product_line_without_price_decodes() {
+        let txn: Transaction = serde_json::from_value(serde_json::json!({
+            "id": "t1", "transaction_code": "T1", "amount": 1.07, "currency": "EUR",
+            "timestamp": "2024-01-15T10:30:00Z", "status": "SUCCESSFUL",
+            "products": [{"name": "tea", "quantity": 1, "vat_rate": 0.07,
+                          "vat_amount": 0.07, "total_price": 1.0, "total_with_vat": 1.07}]
+        }))
+        .unwrap();
+        let products = txn.products.as_ref().unwrap();
+        assert_eq!(products[0].price, Amount::default());
+
+        let report = VatReport::from_transactions(&[txn]).unwrap();
+        assert_eq!(report.rates[0].amounts.gross, eur(107));
+    }
+
+    #[test]
+    fn test_product_validation() {
+        assert!(Product::new("x", 1, Amount::from_cents(1), Decimal::ONE).is_err());
+        assert!(Product::new("x", 0, Amount::from_cents(1), rate(19)).is_err());
+        assert!(VatCalculator::new("ZZZ").is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use super::money::Money;
+use crate::{Error, Result};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::ops::RangeInclusive;
+
+/// Seconds a reader may wait for the customer to pick a tip
+pub const TIP_TIMEOUT_RANGE: RangeInclusive<i32> = 30..=120;
+
+/// A tipping rate offered on a reader, between 0.01 and 0.99 (1% to 99%).
+///
+/// Held as an exact decimal; rates given as `f32` are converted through their shortest
+/// decimal representation, so `0.15f32` is exactly `0.15`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TipRate(Decimal);
+
+impl TipRate {
+    /// Validate a rate in the range 0.01 to 0.99
+    pub fn new(rate: Decimal) -> Result<Self> {
+        let min = Decimal::new(1, 2);
+        let max = Decimal::new(99, 2);
+        if rate < min || rate > max {
+            return Err(Error::InvalidInput(format!(
+                "tip rate {} must be between 0.01 and 0.99",
+                rate
+            )));
+        }
+        Ok(Self(rate.normalize()))
+    }
+
+    /// Validate a rate given as the `f32` the reader API uses
+    pub fn from_f32(rate: f32) -> Result<Self> {
+        if !rate.is_finite() {
+            return Err(Error::InvalidInput(format!(
+                "tip rate must be finite, got {}",
+                rate
+            )));
+        }
+        let rate = rate
+            .to_string()
+            .parse::<Decimal>()
+            .map_err(|e| Error::InvalidInput(format!("invalid tip rate {}: {}", rate, e)))?;
+        Self::new(rate)
+    }
+
+    /// Rate from a whole percentage (e.g. `15` for 15%)
+    pub fn percent(percent: u32) -> Result<Self> {
+        Self::new(Decimal::new(i64::from(percent), 2))
+    }
+
+    pub fn value(&self) -> Decimal {
+        self.0
+    }
+
+    /// The rate as the `f32` the reader API expects
+    pub fn to_f32(&self) -> f32 {
+        self.0.to_f32().unwrap_or_default()
+    }
+
+    /// Tip on a net amount, rounded half away from zero to the currency's minor unit
+    pub fn tip_on(&self, net: &Money) -> Result<Money> {
+        Ok(Money::rounded(
+            net.amount().0 * self.0,
+            net.currency().clone(),
+        )?)
+    }
+
+    /// Tip, net and total when the tip is added on top of `net`
+    pub fn breakdown_on_net(&self, net: &Money) -> Result<TipBreakdown> {
+        let tip = self.tip_on(net)?;
+        let total = net.checked_add(&tip)?;
+        Ok(TipBreakdown {
+            rate: *self,
+            net: net.clone(),
+            tip,
+            total,
+        })
+    }
+
+    /// Split a tip-inclusive `total` into net and tip.
+    ///
+    /// The net amount is rounded to the currency's minor unit and the tip takes the
+    /// difference, so `net + tip == total` exactly.
+    pub fn breakdown_of_total(&self, total: &Money) -> Result<TipBreakdown> {
+        let minor_units = total.minor_units();
+        let net = (total.amount().0 / (Decimal::ONE + self.0))
+            .round_dp_with_strategy(minor_units, RoundingStrategy::MidpointAwayFromZero);
+        let net = Money::new(net, total.currency().clone())?;
+        let tip = total.checked_sub(&net)?;
+        Ok(TipBreakdown {
+            rate: *self,
+            net,
+            tip,
+            total: total.clone(),
+        })
+    }
+}
+
+impl std::fmt::Display for TipRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}%", (self.0 * Decimal::ONE_HUNDRED).normalize())
+    }
+}
+
+/// Amounts for one tipping rate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TipBreakdown {
+    pub rate: TipRate,
+    /// Amount before tip
+    pub net: Money,
+    pub tip: Money,
+    /// Amount charged, `net + tip`
+    pub total: Money,
+}
+
+/// Validate reader tipping rates: each between 0.01 and 0.99, strictly ascending
+pub fn validate_tip_rates(rates: &[f32]) -> Result<Vec<TipRate>> {
+    let rates = rates
+        .iter()
+        .map(|&r| TipRate::from_f32(r))
+        .collect::<Result<Vec<_>>>()?;
+    if let Some(pair) = rates.windows(2).find(|w| w[0] >= w[1]) {
+        return Err(Error::InvalidInput(format!(
+            "tip rates must be sorted ascending without duplicates, got {} before {}",
+            pair[0].value(),
+            pair[1].value()
+        )));
+    }
+    Ok(rates)
+}
+
+/// Validate a reader tip selection timeout in seconds (30 to 120)
+pub fn validate_tip_timeout(seconds: i32) -> Result<()> {
+    if !TIP_TIMEOUT_RANGE.contains(&seconds) {
+        return Err(Error::InvalidInput(format!(
+            "tip timeout {}s must be between {} and {} seconds",
+            seconds,
+            TIP_TIMEOUT_RANGE.start(),
+            TIP_TIMEOUT_RANGE.end()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::money::eur;
+
+    #[test]
+    fn test_tip_rate_bounds_and_exact_conversion() {
+        assert_eq!(
+            TipRate::from_f32(0.15).unwrap().value(),
+            Decimal::new(15, 2)
+        );
+        assert_eq!(
+            TipRate::from_f32(0.1).unwrap(),
+            TipRate::percent(10).unwrap()
+        );
+        assert!(TipRate::from_f32(0.0).is_err());
+        assert!(TipRate::from_f32(1.0).is_err());
+        assert!(TipRate::from_f32(0.995).is_err());
+        assert!(TipRate::from_f32(f32::NAN).is_err());
+        assert_eq!(TipRate::percent(15).unwrap().to_string(), "15%");
+    }
+
+    #[test]
+    fn test_validate_tip_rates_requires_ascending() {
+        assert_eq!(validate_tip_rates(&[0.1, 0.15, 0.2]).unwrap().len(), 3);
+        assert!(validate_tip_rates(&[0.2, 0.1]).is_err());
+        assert!(validate_tip_rates(&[0.1, 0.1]).is_err());
+        assert!(validate_tip_timeout(30).is_ok());
+        assert!(validate_tip_timeout(120).is_ok());
+        assert!(validate_tip_timeout(29).is_err());
+        assert!(validate_tip_timeout(121).is_err());
+    }
+
+    #[test]
+    fn test_breakdowns() {
+        let rate = TipRate::percent(15).unwrap();
+        let on_net = rate.breakdown_on_net(&eur(1999)).unwrap();
+        assert_eq!(on_net.tip, eur(300));
+        assert_eq!(on_net.total, eur(2299));
+
+        let of_total = rate.breakdown_of_total(&eur(1000)).unwrap();
+        assert_eq!(of_total.net, eur(870));
+        assert_eq!(of_total.tip, eur(130));
+
+        let clp = Money::from_minor_units(10_000, "CLP").unwrap();
+        let tip = TipRate::percent(10).unwrap().tip_on(&clp).unwrap();
+        assert_eq!(tip.to_minor_units().unwrap(), 1000);
+    }
+}
@@ -1,5 +1,9 @@
 use super::common::Link;
-use super::enums::{Amount, Currency, MerchantCode, TransactionId, TransactionStatus};
+use super::enums::{Amount, CardType, Currency, MerchantCode, TransactionId, TransactionStatus};
+use super::money::Money;
+use super::tax::{Product, VatRateSummary};
+use crate::decode::api_enum;
+use crate::utils::{impl_extra_fields, UnknownFields};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -35,4 +39,143 @@ pub struct Transaction {
     pub auth_code: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub internal_id: Option<String>,
+    /// Card scheme, returned on transaction history items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card_type: Option<CardType>,
+    /// Card details, returned on transaction details
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card: Option<TransactionCard>,
+    /// Payouts, refunds and chargebacks booked on the transaction, returned on
+    /// transaction details
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<TransactionEvent>>,
+    /// Product lines, returned on transaction details
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub products: Option<Vec<Product>>,
+    /// VAT totals per rate, returned on transaction details
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vat_rates: Option<Vec<VatRateSummary>>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
+}
+
+impl_extra_fields!(Transaction);
+
+api_enum! {
+    /// Kind of a transaction event (per OpenAPI spec)
+    pub enum TransactionEventType {
+        Payout = "PAYOUT",
+        ChargeBack = "CHARGE_BACK",
+        Refund = "REFUND",
+        PayoutDeduction = "PAYOUT_DEDUCTION",
+    }
+}
+
+api_enum! {
+    /// Status of a transaction event (per OpenAPI spec)
+    pub enum TransactionEventStatus {
+        Pending = "PENDING",
+        Scheduled = "SCHEDULED",
+        Failed = "FAILED",
+        Refunded = "REFUNDED",
+        Successful = "SUCCESSFUL",
+        PaidOut = "PAID_OUT",
+    }
+}
+
+/// A payout, refund or chargeback booked on a transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionEvent {
+    #[serde(rename = "type")]
+    pub event_type: TransactionEventType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<TransactionEventStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<Amount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_amount: Option<Amount>,
+    /// Amount deducted from a payout for this event
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deducted_amount: Option<Amount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deducted_fee_amount: Option<Amount>,
+}
+
+/// Card used for a transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionCard {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_4_digits: Option<String>,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub card_type: Option<CardType>,
+}
+
+impl Transaction {
+    /// The charged amount, including any tip
+    pub fn money(&self) -> crate::Result<Money> {
+        Ok(Money::new(self.amount, self.currency.clone())?)
+    }
+
+    /// The tip, zero if none was given
+    pub fn tip(&self) -> crate::Result<Money> {
+        Ok(Money::new(
+            self.tip_amount.unwrap_or_default(),
+            self.currency.clone(),
+        )?)
+    }
+
+    /// Card scheme from the history item or the card details, whichever is present
+    pub fn card_scheme(&self) -> Option<&CardType> {
+        self.card_type
+            .as_ref()
+            .or_else(|| self.card.as_ref()?.card_type.as_ref())
+    }
+
+    /// Chargeback events booked on the transaction, empty if none were returned
+    pub fn chargeback_events(&self) -> impl Iterator<Item = &TransactionEvent> {
+        self.events
+            .iter()
+            .flatten()
+            .filter(|e| e.event_type == TransactionEventType::ChargeBack)
+    }
+
+    /// Refunds or chargebacks (`event_type`) of the transaction as amount and time, one
+    /// per event that did not fail.
+    ///
+    /// The transaction history omits events, so a REFUNDED or CHARGE_BACK transaction
+    /// without matching events yields the full amount at the sale's time.
+    pub fn reversals(&self, event_type: TransactionEventType) -> Vec<(Amount, DateTime<Utc>)> {
+        let reversals: Vec<_> = self
+            .events
+            .iter()
+            .flatten()
+            .filter(|e| e.event_type == event_type)
+            .filter(|e| e.status != Some(TransactionEventStatus::Failed))
+            .map(|e| {
+                (
+                    e.amount.map_or(self.amount, |a| Amount(a.0.abs())),
+                    e.timestamp.unwrap_or(self.timestamp),
+                )
+            })
+            .collect();
+        let full = match event_type {
+            TransactionEventType::Refund => self.status == Some(TransactionStatus::Refunded),
+            TransactionEventType::ChargeBack => self.status == Some(TransactionStatus::ChargeBack),
+            _ => false,
+        };
+        if reversals.is_empty() && full {
+            vec![(self.amount, self.timestamp)]
+        } else {
+            reversals
+        }
+    }
+
+    /// The charged amount without the tip
+    pub fn net_of_tip(&self) -> crate::Result<Money> {
+        Ok(self.money()?.checked_sub(&self.tip()?)?)
+    }
 }
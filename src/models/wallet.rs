@@ -0,0 +1,259 @@
+use super::checkout::{AvailablePaymentMethodsResponse, Checkout};
+use super::enums::{Amount, CountryCode, Currency, PaymentType};
+use super::money::Money;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// Gateway identifier SumUp is registered under with Google Pay
+pub const GOOGLE_PAY_GATEWAY: &str = "sumup";
+
+/// Apple Pay payment token, passed through to SumUp unmodified.
+///
+/// This is the `payment.token` object from the Apple Pay JS `onpaymentauthorized`
+/// event (or `PKPayment.token` on iOS).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApplePayToken(pub serde_json::Value);
+
+impl ApplePayToken {
+    pub fn new(token: serde_json::Value) -> Self {
+        Self(token)
+    }
+
+    /// Parse the token from the JSON sent by the frontend
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(Self(serde_json::from_str(json)?))
+    }
+
+    pub(crate) fn is_present(&self) -> bool {
+        self.0.as_object().is_some_and(|o| !o.is_empty())
+    }
+}
+
+/// Google Pay payment data, passed through to SumUp unmodified.
+///
+/// This is the `PaymentData` object resolved by `PaymentsClient.loadPaymentData()`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GooglePayToken(pub serde_json::Value);
+
+impl GooglePayToken {
+    pub fn new(payment_data: serde_json::Value) -> Self {
+        Self(payment_data)
+    }
+
+    /// Parse the payment data from the JSON sent by the frontend
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(Self(serde_json::from_str(json)?))
+    }
+
+    pub(crate) fn is_present(&self) -> bool {
+        self.0.as_object().is_some_and(|o| !o.is_empty())
+    }
+}
+
+/// Request to start an Apple Pay merchant session for a checkout.
+///
+/// Send it from your backend when the browser fires `onvalidatemerchant`, then pass
+/// the returned session object to `completeMerchantValidation`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplePaySessionRequest {
+    /// Domain the payment sheet is shown on (registered with Apple)
+    pub context: String,
+    /// The `validationURL` from the `onvalidatemerchant` event
+    pub target: String,
+}
+
+impl ApplePaySessionRequest {
+    pub fn new(context: impl Into<String>, target: impl Into<String>) -> Self {
+        Self {
+            context: context.into(),
+            target: target.into(),
+        }
+    }
+}
+
+/// Line item shown on the Apple Pay payment sheet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplePayLineItem {
+    pub label: String,
+    /// Decimal amount as a string (e.g. "10.50")
+    pub amount: String,
+}
+
+/// `ApplePayPaymentRequest` for the Apple Pay JS `ApplePaySession` constructor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplePayPaymentRequest {
+    pub country_code: CountryCode,
+    pub currency_code: Currency,
+    pub supported_networks: Vec<String>,
+    pub merchant_capabilities: Vec<String>,
+    pub total: ApplePayLineItem,
+}
+
+impl ApplePayPaymentRequest {
+    /// Card networks offered by default
+    pub const DEFAULT_NETWORKS: &'static [&'static str] =
+        &["visa", "masterCard", "amex", "maestro"];
+
+    /// Build the payment sheet configuration for a checkout.
+    ///
+    /// Fails if Apple Pay is not among the merchant's available payment methods or
+    /// `country` is not an ISO 3166-1 alpha-2 code.
+    pub fn for_checkout(
+        methods: &AvailablePaymentMethodsResponse,
+        checkout: &Checkout,
+        country: impl AsRef<str>,
+        label: impl Into<String>,
+    ) -> Result<Self> {
+        methods.ensure_supported(PaymentType::ApplePay)?;
+        Ok(Self {
+            country_code: CountryCode::parse(country.as_ref())?,
+            currency_code: checkout.currency.clone(),
+            supported_networks: Self::DEFAULT_NETWORKS
+                .iter()
+                .map(|n| n.to_string())
+                .collect(),
+            merchant_capabilities: vec!["supports3DS".to_string()],
+            total: ApplePayLineItem {
+                label: label.into(),
+                amount: decimal_string(checkout.amount, &checkout.currency)?,
+            },
+        })
+    }
+
+    pub fn supported_networks(mut self, networks: Vec<String>) -> Self {
+        self.supported_networks = networks;
+        self
+    }
+}
+
+/// Google Pay `PaymentDataRequest` for `PaymentsClient.loadPaymentData()`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooglePayPaymentDataRequest {
+    pub api_version: u8,
+    pub api_version_minor: u8,
+    pub allowed_payment_methods: Vec<GooglePayPaymentMethod>,
+    pub merchant_info: GooglePayMerchantInfo,
+    pub transaction_info: GooglePayTransactionInfo,
+}
+
+/// Entry of `allowedPaymentMethods` in a Google Pay request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooglePayPaymentMethod {
+    #[serde(rename = "type")]
+    pub method_type: String,
+    pub parameters: GooglePayCardParameters,
+    pub tokenization_specification: GooglePayTokenizationSpecification,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooglePayCardParameters {
+    pub allowed_auth_methods: Vec<String>,
+    pub allowed_card_networks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GooglePayTokenizationSpecification {
+    #[serde(rename = "type")]
+    pub spec_type: String,
+    pub parameters: GooglePayGatewayParameters,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooglePayGatewayParameters {
+    pub gateway: String,
+    pub gateway_merchant_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooglePayMerchantInfo {
+    pub merchant_name: String,
+    /// Merchant ID from the Google Pay & Wallet Console (required in production)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merchant_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GooglePayTransactionInfo {
+    pub total_price_status: String,
+    pub total_price: String,
+    pub currency_code: Currency,
+    pub country_code: CountryCode,
+}
+
+impl GooglePayPaymentDataRequest {
+    /// Card networks offered by default
+    pub const DEFAULT_NETWORKS: &'static [&'static str] = &["VISA", "MASTERCARD", "AMEX"];
+
+    /// Build the payment data request for a checkout, tokenizing through SumUp with the
+    /// checkout's merchant code as gateway merchant ID.
+    ///
+    /// Fails if Google Pay is not among the merchant's available payment methods or
+    /// `country` is not an ISO 3166-1 alpha-2 code.
+    pub fn for_checkout(
+        methods: &AvailablePaymentMethodsResponse,
+        checkout: &Checkout,
+        country: impl AsRef<str>,
+        merchant_name: impl Into<String>,
+    ) -> Result<Self> {
+        methods.ensure_supported(PaymentType::GooglePay)?;
+        let merchant_code = checkout
+            .merchant_code
+            .as_ref()
+            .ok_or_else(|| Error::InvalidInput("checkout has no merchant_code".into()))?;
+        Ok(Self {
+            api_version: 2,
+            api_version_minor: 0,
+            allowed_payment_methods: vec![GooglePayPaymentMethod {
+                method_type: "CARD".to_string(),
+                parameters: GooglePayCardParameters {
+                    allowed_auth_methods: vec![
+                        "PAN_ONLY".to_string(),
+                        "CRYPTOGRAM_3DS".to_string(),
+                    ],
+                    allowed_card_networks: Self::DEFAULT_NETWORKS
+                        .iter()
+                        .map(|n| n.to_string())
+                        .collect(),
+                },
+                tokenization_specification: GooglePayTokenizationSpecification {
+                    spec_type: "PAYMENT_GATEWAY".to_string(),
+                    parameters: GooglePayGatewayParameters {
+                        gateway: GOOGLE_PAY_GATEWAY.to_string(),
+                        gateway_merchant_id: merchant_code.to_string(),
+                    },
+                },
+            }],
+            merchant_info: GooglePayMerchantInfo {
+                merchant_name: merchant_name.into(),
+                merchant_id: None,
+            },
+            transaction_info: GooglePayTransactionInfo {
+                total_price_status: "FINAL".to_string(),
+                total_price: decimal_string(checkout.amount, &checkout.currency)?,
+                currency_code: checkout.currency.clone(),
+                country_code: CountryCode::parse(country.as_ref())?,
+            },
+        })
+    }
+
+    /// Set the Google merchant ID (required in production)
+    pub fn merchant_id(mut self, id: impl Into<String>) -> Self {
+        self.merchant_info.merchant_id = Some(id.into());
+        self
+    }
+}
+
+/// Format an amount as a decimal string with the currency's minor units, as the
+/// wallet APIs expect (`12.50` EUR, `1500` CLP, `1.250` KWD)
+fn decimal_string(amount: Amount, currency: &Currency) -> Result<String> {
+    Ok(Money::new(amount, currency.clone())?.amount().0.to_string())
+}
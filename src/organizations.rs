@@ -0,0 +1,124 @@
+use crate::{
+    Organization, OrganizationId, OrganizationListQuery, OrganizationListResponse,
+    OrganizationMerchant, OrganizationMerchantListResponse, Result, SumUpClient,
+};
+use url::Url;
+
+impl SumUpClient {
+    /// Lists every organization the authenticated user has access to, following offset
+    /// pagination until `total_count` is reached or a page comes back empty.
+    pub async fn list_organizations(&self) -> Result<Vec<Organization>> {
+        let mut query = OrganizationListQuery::new();
+        let mut organizations = Vec::new();
+        loop {
+            let resp = self.list_organizations_with_query(&query).await?;
+            let fetched = resp.items.len() as i32;
+            organizations.extend(resp.items);
+            match next_offset(&query, fetched, resp.total_count) {
+                Some(offset) => query.offset = Some(offset),
+                None => break,
+            }
+        }
+        Ok(organizations)
+    }
+
+    /// Lists one page of the organizations the authenticated user has access to.
+    pub async fn list_organizations_with_query(
+        &self,
+        query: &OrganizationListQuery,
+    ) -> Result<OrganizationListResponse> {
+        let mut url = self.build_url("/v0.1/organizations")?;
+        append_paging(&mut url, query);
+        let response = self
+            .http_client
+            .get(url)
+            .bearer_auth(self.api_key_str())
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Retrieves an organization by ID.
+    pub async fn retrieve_organization(
+        &self,
+        organization_id: impl Into<OrganizationId>,
+    ) -> Result<Organization> {
+        let organization_id: OrganizationId = organization_id.into();
+        let url = self.build_url(&format!("/v0.1/organizations/{}", organization_id.as_str()))?;
+        let response = self
+            .http_client
+            .get(url)
+            .bearer_auth(self.api_key_str())
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Lists every merchant account belonging to an organization, following offset
+    /// pagination like [`Self::list_organizations`].
+    pub async fn list_organization_merchants(
+        &self,
+        organization_id: impl Into<OrganizationId>,
+    ) -> Result<Vec<OrganizationMerchant>> {
+        let organization_id: OrganizationId = organization_id.into();
+        let mut query = OrganizationListQuery::new();
+        let mut merchants = Vec::new();
+        loop {
+            let resp = self
+                .list_organization_merchants_with_query(&organization_id, &query)
+                .await?;
+            let fetched = resp.items.len() as i32;
+            merchants.extend(resp.items);
+            match next_offset(&query, fetched, resp.total_count) {
+                Some(offset) => query.offset = Some(offset),
+                None => break,
+            }
+        }
+        Ok(merchants)
+    }
+
+    /// Lists one page of the merchant accounts belonging to an organization.
+    pub async fn list_organization_merchants_with_query(
+        &self,
+        organization_id: impl Into<OrganizationId>,
+        query: &OrganizationListQuery,
+    ) -> Result<OrganizationMerchantListResponse> {
+        let organization_id: OrganizationId = organization_id.into();
+        let mut url = self.build_url(&format!(
+            "/v0.1/organizations/{}/merchants",
+            organization_id.as_str()
+        ))?;
+        append_paging(&mut url, query);
+        let response = self
+            .http_client
+            .get(url)
+            .bearer_auth(self.api_key_str())
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+}
+
+fn append_paging(url: &mut Url, query: &OrganizationListQuery) {
+    let mut pairs = url.query_pairs_mut();
+    if let Some(v) = query.limit {
+        pairs.append_pair("limit", &v.to_string());
+    }
+    if let Some(v) = query.offset {
+        pairs.append_pair("offset", &v.to_string());
+    }
+}
+
+/// Offset of the page after one that returned `fetched` items; `None` once the last page
+/// was read. Responses without `total_count` are a single page.
+fn next_offset(
+    query: &OrganizationListQuery,
+    fetched: i32,
+    total_count: Option<i32>,
+) -> Option<i32> {
+    let offset = query.offset.unwrap_or(0) + fetched;
+    match total_count {
+        Some(total) if fetched > 0 && offset < total => Some(offset),
+        _ => None,
+    }
+}
@@ -1,37 +1,60 @@
-use crate::{Payout, PayoutListResponse, Result, SumUpClient};
+use crate::transactions::TransactionHistoryQuery;
+use crate::{Error, MerchantCode, Payout, PayoutId, PayoutListResponse, ReconciliationReport, Result, SumUpClient, Transaction};
+use chrono::NaiveDate;
 use serde::Serialize;
 
+/// Response format for listing payouts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayoutFormat {
+    Json,
+    Csv,
+}
+
+/// Sort order of list results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
 /// Query parameters for listing payouts (per OpenAPI spec)
-#[derive(Debug, Clone, Serialize, Default)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PayoutListQuery {
-    /// Start date (ISO8601 format, required)
-    pub start_date: String,
-    /// End date (ISO8601 format, required)
-    pub end_date: String,
-    /// Response format (json or csv)
+    /// Start date (inclusive, required)
+    pub start_date: NaiveDate,
+    /// End date (inclusive, required)
+    pub end_date: NaiveDate,
+    /// Response format (JSON by default)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub format: Option<String>,
+    pub format: Option<PayoutFormat>,
     /// Maximum number of results
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<i32>,
-    /// Sort order (desc or asc)
+    /// Sort order (descending by default)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub order: Option<String>,
+    pub order: Option<SortOrder>,
 }
 
 impl PayoutListQuery {
-    pub fn new(start_date: impl Into<String>, end_date: impl Into<String>) -> Self {
+    pub fn new(start_date: NaiveDate, end_date: NaiveDate) -> Self {
         Self {
-            start_date: start_date.into(),
-            end_date: end_date.into(),
+            start_date,
+            end_date,
             format: None,
             limit: None,
             order: None,
         }
     }
 
-    pub fn format(mut self, format: impl Into<String>) -> Self {
-        self.format = Some(format.into());
+    /// Query for the payouts of a single day
+    pub fn on(date: NaiveDate) -> Self {
+        Self::new(date, date)
+    }
+
+    pub fn format(mut self, format: PayoutFormat) -> Self {
+        self.format = Some(format);
         self
     }
 
@@ -40,36 +63,92 @@ impl PayoutListQuery {
         self
     }
 
-    pub fn order_desc(mut self) -> Self {
-        self.order = Some("desc".to_string());
+    pub fn order(mut self, order: SortOrder) -> Self {
+        self.order = Some(order);
         self
     }
 
-    pub fn order_asc(mut self) -> Self {
-        self.order = Some("asc".to_string());
-        self
+    pub fn order_desc(self) -> Self {
+        self.order(SortOrder::Desc)
+    }
+
+    pub fn order_asc(self) -> Self {
+        self.order(SortOrder::Asc)
+    }
+
+    /// Check that the date range is not reversed
+    pub fn validate(&self) -> Result<()> {
+        if self.start_date > self.end_date {
+            return Err(Error::InvalidInput(format!(
+                "payout start date {} is after end date {}",
+                self.start_date, self.end_date
+            )));
+        }
+        Ok(())
     }
 }
 
 impl SumUpClient {
     /// Lists payouts for a merchant.
-    pub async fn list_merchant_payouts(&self, merchant_code: impl AsRef<str>, query: &PayoutListQuery) -> Result<PayoutListResponse> {
-        let url = self.build_url(&format!("/v1.0/merchants/{}/payouts", merchant_code.as_ref()))?;
+    pub async fn list_merchant_payouts(&self, merchant_code: impl Into<MerchantCode>, query: &PayoutListQuery) -> Result<PayoutListResponse> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        query.validate()?;
+        if query.format == Some(PayoutFormat::Csv) {
+            return Err(Error::InvalidInput("CSV payout reports are read with list_merchant_payouts_csv (feature `csv`)".to_string()));
+        }
+        let url = self.build_url(&format!("/v1.0/merchants/{}/payouts", merchant_code.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).query(query).send().await?;
         self.handle_response(response).await
     }
 
     /// Retrieves a payout by ID.
-    pub async fn retrieve_payout(&self, payout_id: impl AsRef<str>) -> Result<Payout> {
-        let url = self.build_url(&format!("/v1.0/me/payouts/{}", payout_id.as_ref()))?;
+    pub async fn retrieve_payout(&self, payout_id: impl Into<PayoutId>) -> Result<Payout> {
+        let payout_id: PayoutId = payout_id.into();
+        let url = self.build_url(&format!("/v1.0/me/payouts/{}", payout_id.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
 
     /// Retrieves a payout for a specific merchant.
-    pub async fn retrieve_merchant_payout(&self, merchant_code: impl AsRef<str>, payout_id: impl AsRef<str>) -> Result<Payout> {
-        let url = self.build_url(&format!("/v1.0/merchants/{}/payouts/{}", merchant_code.as_ref(), payout_id.as_ref()))?;
+    pub async fn retrieve_merchant_payout(&self, merchant_code: impl Into<MerchantCode>, payout_id: impl Into<PayoutId>) -> Result<Payout> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let payout_id: PayoutId = payout_id.into();
+        let url = self.build_url(&format!("/v1.0/merchants/{}/payouts/{}", merchant_code.as_str(), payout_id.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
+
+    /// Lists the transactions paid out or deducted in the payout that `payout` belongs to.
+    ///
+    /// Fetches the payout entries of that day, keeps those sharing the payout's reference
+    /// (or just `payout` itself if it has none) and retrieves each referenced transaction.
+    pub async fn list_payout_transactions(&self, merchant_code: impl Into<MerchantCode>, payout: &Payout) -> Result<Vec<Transaction>> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let codes: Vec<String> = match payout.reference.as_deref() {
+            Some(reference) => {
+                let entries = self.list_merchant_payouts(&merchant_code, &PayoutListQuery::on(payout.date)).await?;
+                entries.transaction_codes_for(reference).into_iter().map(str::to_string).collect()
+            }
+            None => payout.transaction_code.iter().cloned().collect(),
+        };
+
+        let mut transactions = Vec::with_capacity(codes.len());
+        for code in codes {
+            transactions.push(self.retrieve_transaction_by_code(&merchant_code, &code).await?);
+        }
+        Ok(transactions)
+    }
+
+    /// Reconciles a merchant's payouts against their transaction history.
+    ///
+    /// Fetches the payout entries for `payouts` and every page of transactions matching
+    /// `transactions`, which should cover the sales those payouts settle (usually a few
+    /// days before the payout window), then matches them with [`ReconciliationReport::reconcile`].
+    pub async fn reconcile_payouts(&self, merchant_code: impl Into<MerchantCode>, payouts: &PayoutListQuery, transactions: &TransactionHistoryQuery) -> Result<ReconciliationReport> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let entries = self.list_merchant_payouts(&merchant_code, payouts).await?;
+
+        let history = self.list_transactions_history_all(&merchant_code, transactions, None).await?;
+        ReconciliationReport::reconcile(&entries.items, &history)
+    }
 }
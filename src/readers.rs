@@ -1,54 +1,75 @@
 use crate::{
-    CreateReaderCheckoutRequest, CreateReaderRequest, Reader, ReaderCheckoutResponse,
+    CheckoutId, CreateReaderCheckoutRequest, CreateReaderRequest, MerchantCode, Reader, ReaderCheckoutResponse, ReaderId,
     ReaderListResponse, Result, SumUpClient, UpdateReaderRequest,
 };
 
 impl SumUpClient {
     /// Lists readers for a merchant.
-    pub async fn list_merchant_readers(&self, merchant_code: impl AsRef<str>) -> Result<ReaderListResponse> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/readers", merchant_code.as_ref()))?;
+    pub async fn list_merchant_readers(&self, merchant_code: impl Into<MerchantCode>) -> Result<ReaderListResponse> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let url = self.build_url(&format!("/v0.1/merchants/{}/readers", merchant_code.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
 
     /// Creates a reader for a merchant.
-    pub async fn create_merchant_reader(&self, merchant_code: impl AsRef<str>, body: &CreateReaderRequest) -> Result<Reader> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/readers", merchant_code.as_ref()))?;
+    pub async fn create_merchant_reader(&self, merchant_code: impl Into<MerchantCode>, body: &CreateReaderRequest) -> Result<Reader> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let url = self.build_url(&format!("/v0.1/merchants/{}/readers", merchant_code.as_str()))?;
         let response = self.http_client.post(url).bearer_auth(self.api_key_str()).json(body).send().await?;
         self.handle_response(response).await
     }
 
     /// Retrieves a reader for a merchant.
-    pub async fn retrieve_merchant_reader(&self, merchant_code: impl AsRef<str>, reader_id: impl AsRef<str>) -> Result<Reader> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/readers/{}", merchant_code.as_ref(), reader_id.as_ref()))?;
+    pub async fn retrieve_merchant_reader(&self, merchant_code: impl Into<MerchantCode>, reader_id: impl Into<ReaderId>) -> Result<Reader> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let reader_id: ReaderId = reader_id.into();
+        reader_id.validate()?;
+        let url = self.build_url(&format!("/v0.1/merchants/{}/readers/{}", merchant_code.as_str(), reader_id.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
 
     /// Updates a reader for a merchant.
-    pub async fn update_merchant_reader(&self, merchant_code: impl AsRef<str>, reader_id: impl AsRef<str>, body: &UpdateReaderRequest) -> Result<Reader> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/readers/{}", merchant_code.as_ref(), reader_id.as_ref()))?;
+    pub async fn update_merchant_reader(&self, merchant_code: impl Into<MerchantCode>, reader_id: impl Into<ReaderId>, body: &UpdateReaderRequest) -> Result<Reader> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let reader_id: ReaderId = reader_id.into();
+        reader_id.validate()?;
+        let url = self.build_url(&format!("/v0.1/merchants/{}/readers/{}", merchant_code.as_str(), reader_id.as_str()))?;
         let response = self.http_client.put(url).bearer_auth(self.api_key_str()).json(body).send().await?;
         self.handle_response(response).await
     }
 
     /// Deletes a reader for a merchant.
-    pub async fn delete_merchant_reader(&self, merchant_code: impl AsRef<str>, reader_id: impl AsRef<str>) -> Result<()> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/readers/{}", merchant_code.as_ref(), reader_id.as_ref()))?;
+    pub async fn delete_merchant_reader(&self, merchant_code: impl Into<MerchantCode>, reader_id: impl Into<ReaderId>) -> Result<()> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let reader_id: ReaderId = reader_id.into();
+        reader_id.validate()?;
+        let url = self.build_url(&format!("/v0.1/merchants/{}/readers/{}", merchant_code.as_str(), reader_id.as_str()))?;
         let response = self.http_client.delete(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_empty_response(response).await
     }
 
     /// Creates a checkout for a reader (in-person payment).
-    pub async fn create_merchant_reader_checkout(&self, merchant_code: impl AsRef<str>, reader_id: impl AsRef<str>, body: &CreateReaderCheckoutRequest) -> Result<ReaderCheckoutResponse> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/readers/{}/checkout", merchant_code.as_ref(), reader_id.as_ref()))?;
+    ///
+    /// The request is validated locally first (see [`CreateReaderCheckoutRequest::validate`]).
+    pub async fn create_merchant_reader_checkout(&self, merchant_code: impl Into<MerchantCode>, reader_id: impl Into<ReaderId>, body: &CreateReaderCheckoutRequest) -> Result<ReaderCheckoutResponse> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let reader_id: ReaderId = reader_id.into();
+        reader_id.validate()?;
+        body.validate()?;
+        let url = self.build_url(&format!("/v0.1/merchants/{}/readers/{}/checkout", merchant_code.as_str(), reader_id.as_str()))?;
         let response = self.http_client.post(url).bearer_auth(self.api_key_str()).json(body).send().await?;
         self.handle_response(response).await
     }
 
     /// Terminates a reader checkout.
-    pub async fn terminate_merchant_reader_checkout(&self, merchant_code: impl AsRef<str>, reader_id: impl AsRef<str>, checkout_id: impl AsRef<str>) -> Result<()> {
-        let url = self.build_url(&format!("/v0.1/merchants/{}/readers/{}/checkout/{}", merchant_code.as_ref(), reader_id.as_ref(), checkout_id.as_ref()))?;
+    pub async fn terminate_merchant_reader_checkout(&self, merchant_code: impl Into<MerchantCode>, reader_id: impl Into<ReaderId>, checkout_id: impl Into<CheckoutId>) -> Result<()> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let reader_id: ReaderId = reader_id.into();
+        reader_id.validate()?;
+        let checkout_id: CheckoutId = checkout_id.into();
+        let url = self.build_url(&format!("/v0.1/merchants/{}/readers/{}/checkout/{}", merchant_code.as_str(), reader_id.as_str(), checkout_id.as_str()))?;
         let response = self.http_client.delete(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_empty_response(response).await
     }
@@ -1,4 +1,4 @@
-use crate::{Receipt, Result, SumUpClient};
+use crate::{Receipt, ReceiptId, Result, SumUpClient};
 use serde::Serialize;
 
 /// Query parameters for retrieving receipts (per OpenAPI spec)
@@ -27,15 +27,17 @@ impl ReceiptRetrieveQuery {
 
 impl SumUpClient {
     /// Retrieves a receipt by ID.
-    pub async fn retrieve_receipt(&self, receipt_id: &str, query: &ReceiptRetrieveQuery) -> Result<Receipt> {
-        let url = self.build_url(&format!("/v1.1/receipts/{}", receipt_id))?;
+    pub async fn retrieve_receipt(&self, receipt_id: impl Into<ReceiptId>, query: &ReceiptRetrieveQuery) -> Result<Receipt> {
+        let receipt_id: ReceiptId = receipt_id.into();
+        let url = self.build_url(&format!("/v1.1/receipts/{}", receipt_id.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).query(query).send().await?;
         self.handle_response(response).await
     }
 
     /// Retrieves a receipt for a specific merchant.
-    pub async fn retrieve_merchant_receipt(&self, merchant_code: &str, receipt_id: &str, query: &ReceiptRetrieveQuery) -> Result<Receipt> {
-        let url = self.build_url(&format!("/v1.1/merchants/{}/receipts/{}", merchant_code, receipt_id))?;
+    pub async fn retrieve_merchant_receipt(&self, merchant_code: &str, receipt_id: impl Into<ReceiptId>, query: &ReceiptRetrieveQuery) -> Result<Receipt> {
+        let receipt_id: ReceiptId = receipt_id.into();
+        let url = self.build_url(&format!("/v1.1/merchants/{}/receipts/{}", merchant_code, receipt_id.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).query(query).send().await?;
         self.handle_response(response).await
     }
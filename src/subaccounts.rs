@@ -1,9 +1,10 @@
-use crate::{Result, SumUpClient};
+use crate::utils::{impl_extra_fields, UnknownFields};
+use crate::{OperatorId, Result, SumUpClient};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Operator {
-    pub id: String,
+    pub id: OperatorId,
     pub username: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub email: Option<String>,
@@ -17,8 +18,14 @@ pub struct Operator {
     pub status: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
+    /// Fields not modelled by this crate, captured with the `unknown-fields` feature
+    #[cfg_attr(feature = "unknown-fields", serde(flatten))]
+    #[cfg_attr(not(feature = "unknown-fields"), serde(skip))]
+    pub extra: UnknownFields,
 }
 
+impl_extra_fields!(Operator);
+
 #[derive(Debug, Clone, Serialize)]
 pub struct CreateOperatorRequest {
     pub username: String,
@@ -66,24 +73,27 @@ impl SumUpClient {
 
     /// Retrieves an operator by ID.
     #[deprecated(since = "0.1.0", note = "This endpoint is deprecated in the SumUp OpenAPI spec")]
-    pub async fn retrieve_operator(&self, operator_id: &str) -> Result<Operator> {
-        let url = self.build_url(&format!("/v0.1/me/accounts/{}", operator_id))?;
+    pub async fn retrieve_operator(&self, operator_id: impl Into<OperatorId>) -> Result<Operator> {
+        let operator_id: OperatorId = operator_id.into();
+        let url = self.build_url(&format!("/v0.1/me/accounts/{}", operator_id.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
 
     /// Updates an operator.
     #[deprecated(since = "0.1.0", note = "This endpoint is deprecated in the SumUp OpenAPI spec")]
-    pub async fn update_operator(&self, operator_id: &str, body: &UpdateOperatorRequest) -> Result<Operator> {
-        let url = self.build_url(&format!("/v0.1/me/accounts/{}", operator_id))?;
+    pub async fn update_operator(&self, operator_id: impl Into<OperatorId>, body: &UpdateOperatorRequest) -> Result<Operator> {
+        let operator_id: OperatorId = operator_id.into();
+        let url = self.build_url(&format!("/v0.1/me/accounts/{}", operator_id.as_str()))?;
         let response = self.http_client.put(url).bearer_auth(self.api_key_str()).json(body).send().await?;
         self.handle_response(response).await
     }
 
     /// Disables an operator.
     #[deprecated(since = "0.1.0", note = "This endpoint is deprecated in the SumUp OpenAPI spec")]
-    pub async fn disable_operator(&self, operator_id: &str) -> Result<()> {
-        let url = self.build_url(&format!("/v0.1/me/accounts/{}", operator_id))?;
+    pub async fn disable_operator(&self, operator_id: impl Into<OperatorId>) -> Result<()> {
+        let operator_id: OperatorId = operator_id.into();
+        let url = self.build_url(&format!("/v0.1/me/accounts/{}", operator_id.as_str()))?;
         let response = self.http_client.delete(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_empty_response(response).await
     }
@@ -0,0 +1,549 @@
+//! Declarative team management.
+//!
+//! A [`DesiredTeam`] describes the members (by email, with role names) and custom roles
+//! a merchant account should have, typically loaded from a YAML or JSON file. A
+//! [`TeamSync`] compares it with what `list_all_members` and `list_roles` return, produces a
+//! [`TeamPlan`] of role and member changes, and applies it, collecting the outcome in a
+//! [`TeamSyncReport`]. Applying a plan and planning again yields an empty plan, so a sync
+//! can be re-run safely after a partial failure.
+//!
+//! Members and custom roles missing from the desired state are only deleted when
+//! [`TeamSync::prune`] is enabled; predefined roles are never changed. Even then, members
+//! holding a predefined owner or admin role (which includes the account owner) are kept,
+//! and a listed member's update keeps those roles, unless [`TeamSync::prune_privileged`]
+//! is enabled.
+//!
+//! Enabled with the `team-sync` cargo feature.
+
+use crate::{
+    CreateMemberRequest, CreateRoleRequest, Error, Member, MemberId, MerchantCode, Permission,
+    PermissionSet, Result, Role, RoleId, SumUpClient, UpdateMemberRequest, UpdateRoleRequest,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+
+/// A custom role as it should exist on the merchant account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DesiredRole {
+    pub name: String,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+/// A member as it should exist on the merchant account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DesiredMember {
+    /// Matched case-insensitively against existing members
+    pub email: String,
+    /// Names of predefined roles or of roles listed in [`DesiredTeam::roles`]
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// The desired members and custom roles of a merchant account
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DesiredTeam {
+    #[serde(default)]
+    pub roles: Vec<DesiredRole>,
+    #[serde(default)]
+    pub members: Vec<DesiredMember>,
+}
+
+impl DesiredTeam {
+    pub fn from_json(text: &str) -> Result<Self> {
+        Ok(serde_json::from_str(text)?)
+    }
+
+    pub fn from_yaml(text: &str) -> Result<Self> {
+        Ok(serde_yaml::from_str(text)?)
+    }
+
+    /// Check that no role name or member email is listed twice and that every role
+    /// permission is one the crate knows, so a typo fails instead of being sent
+    pub fn validate(&self) -> Result<()> {
+        let mut roles = HashSet::new();
+        if let Some(role) = self.roles.iter().find(|r| !roles.insert(r.name.as_str())) {
+            return Err(Error::InvalidInput(format!(
+                "role {:?} is listed more than once",
+                role.name
+            )));
+        }
+        for role in &self.roles {
+            let permissions = PermissionSet::from_strings(&role.permissions);
+            if let Some(unknown) = permissions.unknown().first() {
+                return Err(Error::InvalidInput(format!(
+                    "role {:?} has unknown permission {:?}",
+                    role.name, unknown
+                )));
+            }
+        }
+        let mut emails = HashSet::new();
+        if let Some(member) = self
+            .members
+            .iter()
+            .find(|m| !emails.insert(m.email.to_lowercase()))
+        {
+            return Err(Error::InvalidInput(format!(
+                "member {:?} is listed more than once",
+                member.email
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A single change in a [`TeamPlan`]
+///
+/// Member roles are given by name; they are resolved to role IDs when the plan is
+/// applied, after any roles it creates exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TeamChange {
+    CreateRole {
+        name: String,
+        permissions: Vec<Permission>,
+    },
+    UpdateRole {
+        role_id: RoleId,
+        name: String,
+        permissions: Vec<Permission>,
+    },
+    DeleteRole {
+        role_id: RoleId,
+        name: String,
+    },
+    CreateMember {
+        email: String,
+        roles: Vec<String>,
+    },
+    UpdateMember {
+        member_id: MemberId,
+        email: String,
+        roles: Vec<String>,
+    },
+    DeleteMember {
+        member_id: MemberId,
+        email: String,
+    },
+}
+
+fn join_permissions(permissions: &[Permission]) -> String {
+    let names: Vec<&str> = permissions.iter().map(Permission::as_str).collect();
+    names.join(", ")
+}
+
+impl fmt::Display for TeamChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreateRole { name, permissions } => {
+                write!(f, "create role {name} [{}]", join_permissions(permissions))
+            }
+            Self::UpdateRole {
+                name, permissions, ..
+            } => {
+                write!(f, "update role {name} [{}]", join_permissions(permissions))
+            }
+            Self::DeleteRole { name, .. } => write!(f, "delete role {name}"),
+            Self::CreateMember { email, roles } => {
+                write!(f, "create member {email} [{}]", roles.join(", "))
+            }
+            Self::UpdateMember { email, roles, .. } => {
+                write!(f, "update member {email} [{}]", roles.join(", "))
+            }
+            Self::DeleteMember { email, .. } => write!(f, "delete member {email}"),
+        }
+    }
+}
+
+/// The changes that bring a merchant's team to a [`DesiredTeam`], in the order they
+/// are applied: role creations and updates, member changes, then role deletions.
+#[derive(Debug, Clone, Default)]
+pub struct TeamPlan {
+    pub changes: Vec<TeamChange>,
+    /// Emails of members whose owner or admin roles were kept: unlisted members that
+    /// pruning left in place, and listed members whose update keeps those roles
+    pub protected_members: Vec<String>,
+    /// IDs of the roles that existed when the plan was made, by name
+    role_ids: HashMap<String, RoleId>,
+}
+
+impl TeamPlan {
+    /// Whether the team already matches the desired state
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TeamChange> {
+        self.changes.iter()
+    }
+}
+
+impl fmt::Display for TeamPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A change that could not be applied
+#[derive(Debug)]
+pub struct FailedTeamChange {
+    pub change: TeamChange,
+    pub error: Error,
+}
+
+/// Outcome of [`TeamSync::sync`] or [`TeamSync::apply`]
+#[derive(Debug, Default)]
+pub struct TeamSyncReport {
+    /// Nothing was sent to the API; `planned` holds what would have been applied
+    pub dry_run: bool,
+    pub planned: Vec<TeamChange>,
+    pub applied: Vec<TeamChange>,
+    pub failed: Vec<FailedTeamChange>,
+}
+
+impl TeamSyncReport {
+    /// Whether every planned change was applied, or this was a dry run
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Reconciles a merchant's members and custom roles with a [`DesiredTeam`]
+#[derive(Debug, Clone)]
+pub struct TeamSync {
+    desired: DesiredTeam,
+    prune: bool,
+    prune_privileged: bool,
+    dry_run: bool,
+}
+
+impl TeamSync {
+    pub fn new(desired: DesiredTeam) -> Self {
+        Self {
+            desired,
+            prune: false,
+            prune_privileged: false,
+            dry_run: false,
+        }
+    }
+
+    /// Delete members and custom roles that are not in the desired state
+    pub fn prune(mut self, prune: bool) -> Self {
+        self.prune = prune;
+        self
+    }
+
+    /// Let the sync remove predefined owner and admin roles: pruning deletes members
+    /// holding them, and updates replace them with the listed roles
+    pub fn prune_privileged(mut self, prune_privileged: bool) -> Self {
+        self.prune_privileged = prune_privileged;
+        self
+    }
+
+    /// Only plan: [`Self::sync`] reports the changes without applying them
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Plans the changes from the given current members and roles.
+    ///
+    /// Fails if the desired state lists a role or member twice, uses an unknown permission,
+    /// redefines a predefined role, or gives a member a role that neither exists nor is listed.
+    pub fn plan_against(&self, members: &[Member], roles: &[Role]) -> Result<TeamPlan> {
+        self.desired.validate()?;
+
+        let existing_roles: HashMap<&str, &Role> =
+            roles.iter().map(|r| (r.name.as_str(), r)).collect();
+        let role_names: HashMap<&RoleId, &str> =
+            roles.iter().map(|r| (&r.id, r.name.as_str())).collect();
+        let mut plan = TeamPlan {
+            changes: Vec::new(),
+            protected_members: Vec::new(),
+            role_ids: roles
+                .iter()
+                .map(|r| (r.name.clone(), r.id.clone()))
+                .collect(),
+        };
+
+        for desired in &self.desired.roles {
+            let permissions = PermissionSet::from_strings(&desired.permissions);
+            match existing_roles.get(desired.name.as_str()) {
+                Some(role) if role.is_predefined => {
+                    return Err(Error::InvalidInput(format!(
+                        "role {:?} is predefined and cannot be changed",
+                        desired.name
+                    )));
+                }
+                Some(role) if role.permission_set() == permissions => {}
+                Some(role) => plan.changes.push(TeamChange::UpdateRole {
+                    role_id: role.id.clone(),
+                    name: desired.name.clone(),
+                    permissions: permissions.to_vec(),
+                }),
+                None => plan.changes.push(TeamChange::CreateRole {
+                    name: desired.name.clone(),
+                    permissions: permissions.to_vec(),
+                }),
+            }
+        }
+
+        let listed_roles: HashSet<&str> =
+            self.desired.roles.iter().map(|r| r.name.as_str()).collect();
+        let existing_members: HashMap<String, &Member> = members
+            .iter()
+            .map(|m| (m.email.to_lowercase(), m))
+            .collect();
+        let mut referenced_roles = HashSet::new();
+        let privileged: HashSet<&RoleId> = roles
+            .iter()
+            .filter(|r| is_privileged_role(r))
+            .map(|r| &r.id)
+            .collect();
+        // roles missing from the list are judged by their ID, e.g. `role_owner`
+        let holds_privileged = |id: &RoleId| {
+            privileged.contains(id)
+                || (!role_names.contains_key(id) && is_privileged_name(id.as_str()))
+        };
+
+        for desired in &self.desired.members {
+            let wanted: BTreeSet<&str> = desired.roles.iter().map(String::as_str).collect();
+            if let Some(name) = wanted
+                .iter()
+                .find(|name| !existing_roles.contains_key(*name) && !listed_roles.contains(*name))
+            {
+                return Err(Error::InvalidInput(format!(
+                    "member {:?} has unknown role {:?}",
+                    desired.email, name
+                )));
+            }
+            referenced_roles.extend(wanted.iter().copied());
+
+            match existing_members.get(&desired.email.to_lowercase()) {
+                Some(member) => {
+                    let role_name = |id: &'_ RoleId| -> String {
+                        role_names
+                            .get(id)
+                            .copied()
+                            .unwrap_or(id.as_str())
+                            .to_string()
+                    };
+                    let current: BTreeSet<String> = member.roles.iter().map(role_name).collect();
+                    let mut wanted: BTreeSet<String> =
+                        wanted.iter().map(|name| name.to_string()).collect();
+                    if !self.prune_privileged {
+                        let kept: Vec<&RoleId> = member
+                            .roles
+                            .iter()
+                            .filter(|id| holds_privileged(id) && !wanted.contains(&role_name(id)))
+                            .collect();
+                        for id in &kept {
+                            let name = role_name(id);
+                            plan.role_ids
+                                .entry(name.clone())
+                                .or_insert_with(|| (*id).clone());
+                            wanted.insert(name);
+                        }
+                        if !kept.is_empty() {
+                            plan.protected_members.push(member.email.clone());
+                        }
+                    }
+                    if current != wanted {
+                        plan.changes.push(TeamChange::UpdateMember {
+                            member_id: member.id.clone(),
+                            email: member.email.clone(),
+                            roles: wanted.into_iter().collect(),
+                        });
+                    }
+                }
+                None => plan.changes.push(TeamChange::CreateMember {
+                    email: desired.email.clone(),
+                    roles: wanted.iter().map(|name| name.to_string()).collect(),
+                }),
+            }
+        }
+
+        if self.prune {
+            let desired_emails: HashSet<String> = self
+                .desired
+                .members
+                .iter()
+                .map(|m| m.email.to_lowercase())
+                .collect();
+            let mut protected_roles: HashSet<&RoleId> = HashSet::new();
+            for member in members {
+                if desired_emails.contains(&member.email.to_lowercase()) {
+                    continue;
+                }
+                if member.roles.iter().any(holds_privileged) && !self.prune_privileged {
+                    protected_roles.extend(&member.roles);
+                    plan.protected_members.push(member.email.clone());
+                } else {
+                    plan.changes.push(TeamChange::DeleteMember {
+                        member_id: member.id.clone(),
+                        email: member.email.clone(),
+                    });
+                }
+            }
+            for role in roles {
+                let name = role.name.as_str();
+                if !role.is_predefined
+                    && !listed_roles.contains(name)
+                    && !referenced_roles.contains(name)
+                    && !protected_roles.contains(&role.id)
+                {
+                    plan.changes.push(TeamChange::DeleteRole {
+                        role_id: role.id.clone(),
+                        name: role.name.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(plan)
+    }
+
+    /// Fetches the merchant's members and roles and plans the changes.
+    pub async fn plan(
+        &self,
+        client: &SumUpClient,
+        merchant_code: impl Into<MerchantCode>,
+    ) -> Result<TeamPlan> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let members = client.list_all_members(&merchant_code).await?;
+        let roles = client.list_roles(&merchant_code).await?.roles;
+        self.plan_against(&members, &roles)
+    }
+
+    /// Plans the changes and, unless this is a dry run, applies them.
+    pub async fn sync(
+        &self,
+        client: &SumUpClient,
+        merchant_code: impl Into<MerchantCode>,
+    ) -> Result<TeamSyncReport> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let plan = self.plan(client, &merchant_code).await?;
+        if self.dry_run {
+            return Ok(TeamSyncReport {
+                dry_run: true,
+                planned: plan.changes,
+                ..Default::default()
+            });
+        }
+        Ok(self.apply(client, &merchant_code, &plan).await)
+    }
+
+    /// Applies a plan made by [`Self::plan`] or [`Self::plan_against`].
+    ///
+    /// Every change is attempted even if an earlier one fails; a member whose role
+    /// could not be created fails as well. Failures are listed in the report.
+    pub async fn apply(
+        &self,
+        client: &SumUpClient,
+        merchant_code: impl Into<MerchantCode>,
+        plan: &TeamPlan,
+    ) -> TeamSyncReport {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let mut role_ids = plan.role_ids.clone();
+        let mut report = TeamSyncReport {
+            planned: plan.changes.clone(),
+            ..Default::default()
+        };
+
+        for change in &plan.changes {
+            let outcome = match change {
+                TeamChange::CreateRole { name, permissions } => {
+                    let body = CreateRoleRequest {
+                        name: name.clone(),
+                        permissions: permissions.clone(),
+                    };
+                    client.create_role(&merchant_code, &body).await.map(|role| {
+                        role_ids.insert(role.name, role.id);
+                    })
+                }
+                TeamChange::UpdateRole {
+                    role_id,
+                    name,
+                    permissions,
+                } => {
+                    let body = UpdateRoleRequest {
+                        name: name.clone(),
+                        permissions: permissions.clone(),
+                    };
+                    client
+                        .update_role(&merchant_code, role_id, &body)
+                        .await
+                        .map(|_| ())
+                }
+                TeamChange::DeleteRole { role_id, .. } => {
+                    client.delete_role(&merchant_code, role_id).await
+                }
+                TeamChange::CreateMember { email, roles } => {
+                    match resolve_roles(&role_ids, roles) {
+                        Ok(ids) => client
+                            .create_member(
+                                &merchant_code,
+                                &CreateMemberRequest::new(email.as_str(), ids),
+                            )
+                            .await
+                            .map(|_| ()),
+                        Err(e) => Err(e),
+                    }
+                }
+                TeamChange::UpdateMember {
+                    member_id, roles, ..
+                } => match resolve_roles(&role_ids, roles) {
+                    Ok(ids) => client
+                        .update_member(
+                            &merchant_code,
+                            member_id,
+                            &UpdateMemberRequest::default().roles(ids),
+                        )
+                        .await
+                        .map(|_| ()),
+                    Err(e) => Err(e),
+                },
+                TeamChange::DeleteMember { member_id, .. } => {
+                    client.delete_member(&merchant_code, member_id).await
+                }
+            };
+            match outcome {
+                Ok(()) => report.applied.push(change.clone()),
+                Err(error) => report.failed.push(FailedTeamChange {
+                    change: change.clone(),
+                    error,
+                }),
+            }
+        }
+        report
+    }
+}
+
+/// Predefined owner and admin roles, which pruning leaves alone by default
+fn is_privileged_role(role: &Role) -> bool {
+    role.is_predefined && (is_privileged_name(&role.name) || is_privileged_name(role.id.as_str()))
+}
+
+fn is_privileged_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.contains("owner") || name.contains("admin")
+}
+
+fn resolve_roles(role_ids: &HashMap<String, RoleId>, names: &[String]) -> Result<Vec<RoleId>> {
+    names
+        .iter()
+        .map(|name| {
+            role_ids
+                .get(name)
+                .cloned()
+                .ok_or_else(|| Error::InvalidInput(format!("role {name:?} does not exist")))
+        })
+        .collect()
+}
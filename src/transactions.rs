@@ -1,5 +1,7 @@
-use crate::{Amount, Result, SumUpClient, Transaction, TransactionHistoryResponse};
+use crate::{Amount, ClientTransactionId, Error, MerchantCode, Money, MoneyError, Result, SumUpClient, Transaction, TransactionHistoryResponse, TransactionId};
 use serde::Serialize;
+use std::collections::HashSet;
+use url::Url;
 
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct TransactionHistoryQuery {
@@ -19,16 +21,28 @@ pub struct TransactionHistoryQuery {
 
 impl SumUpClient {
     /// Lists transaction history for a merchant.
-    pub async fn list_transactions_history(&self, merchant_code: impl AsRef<str>, query: &TransactionHistoryQuery) -> Result<TransactionHistoryResponse> {
-        let url = self.build_url(&format!("/v2.1/merchants/{}/transactions/history", merchant_code.as_ref()))?;
+    pub async fn list_transactions_history(&self, merchant_code: impl Into<MerchantCode>, query: &TransactionHistoryQuery) -> Result<TransactionHistoryResponse> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let url = self.build_url(&format!("/v2.1/merchants/{}/transactions/history", merchant_code.as_str()))?;
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).query(query).send().await?;
         self.handle_response(response).await
     }
 
     /// Retrieves a transaction by ID.
-    pub async fn retrieve_transaction_by_id(&self, merchant_code: impl AsRef<str>, transaction_id: impl AsRef<str>) -> Result<Transaction> {
-        let mut url = self.build_url(&format!("/v2.1/merchants/{}/transactions", merchant_code.as_ref()))?;
-        url.query_pairs_mut().append_pair("id", transaction_id.as_ref());
+    pub async fn retrieve_transaction_by_id(&self, merchant_code: impl Into<MerchantCode>, transaction_id: impl Into<TransactionId>) -> Result<Transaction> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let transaction_id: TransactionId = transaction_id.into();
+        let mut url = self.build_url(&format!("/v2.1/merchants/{}/transactions", merchant_code.as_str()))?;
+        url.query_pairs_mut().append_pair("id", transaction_id.as_str());
+        let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Retrieves a transaction by its transaction code (e.g. from a payout entry).
+    pub async fn retrieve_transaction_by_code(&self, merchant_code: impl Into<MerchantCode>, transaction_code: impl AsRef<str>) -> Result<Transaction> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let mut url = self.build_url(&format!("/v2.1/merchants/{}/transactions", merchant_code.as_str()))?;
+        url.query_pairs_mut().append_pair("transaction_code", transaction_code.as_ref());
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
@@ -36,11 +50,13 @@ impl SumUpClient {
     /// Retrieves transaction by client transaction id (e.g. from CreateReaderCheckout).
     pub async fn retrieve_transaction_by_client_transaction_id(
         &self,
-        merchant_code: impl AsRef<str>,
-        client_transaction_id: &str,
+        merchant_code: impl Into<MerchantCode>,
+        client_transaction_id: impl Into<ClientTransactionId>,
     ) -> Result<Transaction> {
-        let mut url = self.build_url(&format!("/v2.1/merchants/{}/transactions", merchant_code.as_ref()))?;
-        url.query_pairs_mut().append_pair("client_transaction_id", client_transaction_id);
+        let merchant_code: MerchantCode = merchant_code.into();
+        let client_transaction_id: ClientTransactionId = client_transaction_id.into();
+        let mut url = self.build_url(&format!("/v2.1/merchants/{}/transactions", merchant_code.as_str()))?;
+        url.query_pairs_mut().append_pair("client_transaction_id", client_transaction_id.as_str());
         let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
         self.handle_response(response).await
     }
@@ -48,23 +64,35 @@ impl SumUpClient {
     /// Refunds a transaction.
     ///
     /// Note: Uses `/v0.1/me/refund/{txn_id}` per OpenAPI spec.
-    pub async fn refund_transaction(&self, _merchant_code: impl AsRef<str>, transaction_id: impl AsRef<str>, amount: Option<Amount>, reason: &str) -> Result<Transaction> {
-        let url = self.build_url(&format!("/v0.1/me/refund/{}", transaction_id.as_ref()))?;
+    pub async fn refund_transaction(&self, _merchant_code: impl AsRef<str>, transaction_id: impl Into<TransactionId>, amount: Option<Amount>, reason: &str) -> Result<Transaction> {
+        let transaction_id: TransactionId = transaction_id.into();
+        let url = self.build_url(&format!("/v0.1/me/refund/{}", transaction_id.as_str()))?;
 
         let mut body = serde_json::Map::new();
         body.insert("reason".to_string(), serde_json::Value::String(reason.to_string()));
         if let Some(amt) = amount {
-            use rust_decimal::prelude::ToPrimitive;
-            let num = serde_json::Number::from_f64(amt.0.to_f64().unwrap_or(0.0)).ok_or_else(|| {
-                crate::Error::InvalidInput(format!("Invalid amount: {} (must be a finite number)", amt))
-            })?;
-            body.insert("amount".to_string(), serde_json::Value::Number(num));
+            body.insert("amount".to_string(), serde_json::to_value(amt)?);
         }
 
         let response = self.http_client.post(url).bearer_auth(self.api_key_str()).json(&body).send().await?;
         self.handle_response(response).await
     }
 
+    /// Partially refunds a transaction by a validated money value.
+    ///
+    /// Fails without calling the API if the currency differs from the transaction's
+    /// or the amount is not positive.
+    pub async fn refund_transaction_money(&self, merchant_code: impl Into<MerchantCode>, transaction: &Transaction, money: &Money, reason: &str) -> Result<Transaction> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        if money.currency() != &transaction.currency {
+            return Err(MoneyError::CurrencyMismatch { left: transaction.currency.clone(), right: money.currency().clone() }.into());
+        }
+        if !money.is_positive() {
+            return Err(crate::Error::InvalidInput(format!("refund amount must be positive, got {}", money)));
+        }
+        self.refund_transaction(merchant_code, &transaction.id, Some(money.amount()), reason).await
+    }
+
     /// Extracts the next page URL from a transaction history response.
     pub fn get_next_page_url_from_history(history: &TransactionHistoryResponse) -> Option<String> {
         history.links.iter().find(|link| link.rel == "next").map(|link| link.href.clone())
@@ -80,9 +108,62 @@ impl SumUpClient {
         history.links.iter().any(|link| link.rel == "next")
     }
 
+    /// Fetches every page of transaction history matching `query`, following the `next`
+    /// links for at most `max_pages` pages. Transactions repeated across page boundaries
+    /// are returned once, and paging stops when a page brings no new transactions.
+    pub async fn list_transactions_history_all(&self, merchant_code: impl Into<MerchantCode>, query: &TransactionHistoryQuery, max_pages: Option<usize>) -> Result<Vec<Transaction>> {
+        let merchant_code: MerchantCode = merchant_code.into();
+        let endpoint = self.build_url(&format!("/v2.1/merchants/{}/transactions/history", merchant_code.as_str()))?;
+        let mut seen = HashSet::new();
+        let mut transactions = Vec::new();
+        let mut page = self.list_transactions_history(merchant_code.as_str(), query).await?;
+        let mut page_count = 1;
+        loop {
+            let next = Self::get_next_page_url_from_history(&page);
+            let before = transactions.len();
+            transactions.extend(page.items.into_iter().filter(|t| seen.insert(t.id.clone())));
+            let href = match next {
+                Some(href) => href,
+                None => break,
+            };
+            if transactions.len() == before || max_pages.is_some_and(|max| page_count >= max) {
+                break;
+            }
+            let url = self.history_next_page_url(&endpoint, &href, query)?;
+            let response = self.http_client.get(url).bearer_auth(self.api_key_str()).send().await?;
+            page = self.handle_response(response).await?;
+            page_count += 1;
+        }
+        Ok(transactions)
+    }
+
+    /// Resolves a `next` link against the history endpoint, keeping the filters of `query`
+    /// the link does not repeat. Links to another host are refused so the API key is not leaked.
+    fn history_next_page_url(&self, endpoint: &Url, href: &str, query: &TransactionHistoryQuery) -> Result<Url> {
+        let mut url = endpoint.join(href)?;
+        if url.origin() != self.base_url.origin() {
+            return Err(Error::InvalidInput(format!("refusing to follow pagination link to another host: {}", href)));
+        }
+        let present: HashSet<String> = url.query_pairs().map(|(key, _)| key.into_owned()).collect();
+        if let serde_json::Value::Object(params) = serde_json::to_value(query)? {
+            let missing: Vec<(String, String)> = params
+                .into_iter()
+                .filter(|(key, _)| !present.contains(key))
+                .map(|(key, value)| match value {
+                    serde_json::Value::String(value) => (key, value),
+                    other => (key, other.to_string()),
+                })
+                .collect();
+            if !missing.is_empty() {
+                url.query_pairs_mut().extend_pairs(missing);
+            }
+        }
+        Ok(url)
+    }
+
     /// Fetches all transactions by automatically handling pagination.
-    pub async fn list_all_transactions_history(&self, merchant_code: impl AsRef<str>, order: Option<&str>, max_pages: Option<usize>) -> Result<Vec<Transaction>> {
-        let merchant_code = merchant_code.as_ref();
+    pub async fn list_all_transactions_history(&self, merchant_code: impl Into<MerchantCode>, order: Option<&str>, max_pages: Option<usize>) -> Result<Vec<Transaction>> {
+        let merchant_code: MerchantCode = merchant_code.into();
         let mut all_transactions = Vec::new();
         let mut page_count = 0;
         let mut newest_time: Option<String> = None;
@@ -92,7 +173,7 @@ impl SumUpClient {
                 if page_count >= max { break; }
             }
 
-            let history = self.list_transactions_history(merchant_code, &TransactionHistoryQuery {
+            let history = self.list_transactions_history(&merchant_code, &TransactionHistoryQuery {
                 limit: Some(100),
                 order: order.map(|s| s.to_string()),
                 newest_time: newest_time.clone(),
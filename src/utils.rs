@@ -124,6 +124,79 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Nullable<T> {
     }
 }
 
+/// Fields of an API response that the model does not define.
+///
+/// Response models carry these in their `extra` field so new API fields can be used before
+/// the crate models them. Most models only capture them with the `unknown-fields` cargo
+/// feature (capturing makes decoding buffer each object); otherwise the map stays empty.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UnknownFields(serde_json::Map<String, serde_json::Value>);
+
+impl UnknownFields {
+    /// Raw value of an unmodelled field
+    pub fn get(&self, name: &str) -> Option<&serde_json::Value> {
+        self.0.get(name)
+    }
+
+    /// Decode an unmodelled field into `T`, `None` if the field is absent
+    pub fn get_as<T: serde::de::DeserializeOwned>(&self, name: &str) -> crate::Result<Option<T>> {
+        self.0
+            .get(name)
+            .map(|value| serde_json::from_value(value.clone()))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &serde_json::Value)> {
+        self.0.iter()
+    }
+
+    pub fn into_inner(self) -> serde_json::Map<String, serde_json::Value> {
+        self.0
+    }
+}
+
+impl From<serde_json::Map<String, serde_json::Value>> for UnknownFields {
+    fn from(map: serde_json::Map<String, serde_json::Value>) -> Self {
+        Self(map)
+    }
+}
+
+/// Access to the response fields a model does not define
+pub trait ExtraFields {
+    /// Unmodelled fields of this response (see [`UnknownFields`])
+    fn extra_fields(&self) -> &UnknownFields;
+
+    /// Raw value of an unmodelled field
+    fn extra_field(&self, name: &str) -> Option<&serde_json::Value> {
+        self.extra_fields().get(name)
+    }
+}
+
+/// Implement [`ExtraFields`] for models with an `extra: UnknownFields` field
+macro_rules! impl_extra_fields {
+    ($($model:ty),+ $(,)?) => {
+        $(
+            impl $crate::utils::ExtraFields for $model {
+                fn extra_fields(&self) -> &$crate::utils::UnknownFields {
+                    &self.extra
+                }
+            }
+        )+
+    };
+}
+
+pub(crate) use impl_extra_fields;
+
 #[cfg(test)]
 mod tests {
     use super::*;
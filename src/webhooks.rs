@@ -1,17 +1,17 @@
+use crate::decode::api_enum;
+use crate::utils::{impl_extra_fields, UnknownFields};
 use crate::{Result, SumUpClient};
 use serde::{Deserialize, Serialize};
 
-/// Webhook event types from SumUp.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum WebhookEventType {
-    CheckoutCompleted,
-    CheckoutFailed,
-    CheckoutExpired,
-    PayoutCompleted,
-    PayoutFailed,
-    #[serde(other)]
-    Unknown,
+api_enum! {
+    /// Webhook event types from SumUp.
+    pub enum WebhookEventType {
+        CheckoutCompleted = "CHECKOUT_COMPLETED",
+        CheckoutFailed = "CHECKOUT_FAILED",
+        CheckoutExpired = "CHECKOUT_EXPIRED",
+        PayoutCompleted = "PAYOUT_COMPLETED",
+        PayoutFailed = "PAYOUT_FAILED",
+    }
 }
 
 /// A webhook event from SumUp.
@@ -34,9 +34,11 @@ pub struct WebhookEvent {
     pub timestamp: Option<String>,
     /// Raw payload for fields not explicitly defined.
     #[serde(flatten)]
-    pub extra: std::collections::HashMap<String, serde_json::Value>,
+    pub extra: UnknownFields,
 }
 
+impl_extra_fields!(WebhookEvent);
+
 impl WebhookEvent {
     /// Parse a webhook event from JSON bytes.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
@@ -67,7 +69,10 @@ impl SumUpClient {
     /// rather than using signature verification.
     ///
     /// Returns the verified checkout if valid.
-    pub async fn verify_checkout_webhook(&self, checkout_id: &str) -> Result<crate::Checkout> {
+    pub async fn verify_checkout_webhook(
+        &self,
+        checkout_id: impl Into<crate::CheckoutId>,
+    ) -> Result<crate::Checkout> {
         self.retrieve_checkout(checkout_id).await
     }
 }
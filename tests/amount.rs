@@ -0,0 +1,233 @@
+use proptest::prelude::*;
+use rust_decimal::Decimal;
+use sumup_rs::{
+    Amount, CreateCheckoutRequest, Error, Money, MoneyError, SumUpClient, TotalAmount, Transaction,
+};
+use wiremock::matchers::{body_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Any decimal representable by `Decimal` (96-bit mantissa, scale 0..=28)
+fn any_decimal() -> impl Strategy<Value = Decimal> {
+    (
+        any::<u32>(),
+        any::<u32>(),
+        any::<u32>(),
+        any::<bool>(),
+        0u32..=28,
+    )
+        .prop_map(|(lo, mid, hi, negative, scale)| {
+            Decimal::from_parts(lo, mid, hi, negative, scale)
+        })
+}
+
+/// Amounts as the API carries them: up to 4 decimals, large enough for CLP/COP totals
+fn api_amount() -> impl Strategy<Value = Decimal> {
+    (
+        -1_000_000_000_000_000i64..=1_000_000_000_000_000i64,
+        0u32..=4,
+    )
+        .prop_map(|(mantissa, scale)| Decimal::new(mantissa, scale))
+}
+
+proptest! {
+    #[test]
+    fn amount_round_trips_exactly_through_json(value in any_decimal()) {
+        let json = serde_json::to_string(&Amount(value)).unwrap();
+        prop_assert_eq!(&json, &value.to_string());
+
+        let back: Amount = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(back.0, value);
+        prop_assert_eq!(back.0.scale(), value.scale());
+    }
+
+    #[test]
+    fn amount_round_trips_through_json_value(value in api_amount()) {
+        let as_value = serde_json::to_value(Amount(value)).unwrap();
+        let back: Amount = serde_json::from_value(as_value).unwrap();
+        prop_assert_eq!(back.0, value);
+    }
+
+    #[test]
+    fn amount_round_trips_inside_request_bodies(value in api_amount()) {
+        let request = CreateCheckoutRequest::new("ref", Amount(value), "EUR", "M123");
+        let json = serde_json::to_string(&request).unwrap();
+        let back: CreateCheckoutRequest = serde_json::from_str(&json).unwrap();
+        prop_assert_eq!(back.amount.0.to_string(), value.to_string());
+    }
+}
+
+#[test]
+fn test_amount_serializes_without_float_artifacts() {
+    let sum = Amount(Decimal::new(1, 1)) + Amount(Decimal::new(2, 1));
+    assert_eq!(serde_json::to_string(&sum).unwrap(), "0.3");
+    assert_eq!(
+        serde_json::to_string(&Amount::from_cents(1050)).unwrap(),
+        "10.50"
+    );
+}
+
+#[test]
+fn test_amount_parses_digits_beyond_f64_precision() {
+    // 17 significant digits: an f64 round trip would turn this into 12345678901234568
+    let amount: Amount = serde_json::from_str("1234567890123456.78").unwrap();
+    assert_eq!(amount.0.to_string(), "1234567890123456.78");
+
+    let amount: Amount = serde_json::from_str("\"99999999999.99\"").unwrap();
+    assert_eq!(amount, Amount::from_cents(9_999_999_999_999));
+}
+
+#[test]
+fn test_amount_rejects_non_finite_input() {
+    assert!(serde_json::from_str::<Amount>("\"NaN\"").is_err());
+    assert!(serde_json::from_str::<Amount>("\"inf\"").is_err());
+    assert!(Amount::try_from(f64::NAN).is_err());
+    assert!(Amount::try_from(f64::INFINITY).is_err());
+    assert!(Amount::try_from(f64::NEG_INFINITY).is_err());
+    assert_eq!(Amount::try_from(0.1).unwrap().0.to_string(), "0.1");
+}
+
+#[test]
+fn test_total_amount_value_is_exact() {
+    let total: TotalAmount = serde_json::from_str(
+        r#"{"currency": "CLP", "minor_unit": 0, "value": 123456789012345678}"#,
+    )
+    .unwrap();
+    assert_eq!(total.value, 123_456_789_012_345_678);
+}
+
+#[test]
+fn test_total_amount_uses_currency_minor_unit() {
+    let clp = TotalAmount::from_money(&Money::from_minor_units(15_000, "CLP").unwrap()).unwrap();
+    assert_eq!((clp.value, clp.minor_unit), (15_000, 0));
+    assert_eq!(clp.to_amount(), Amount::from_major(15_000));
+
+    let eur = TotalAmount::try_from_amount(Amount::from_cents(1050), "EUR").unwrap();
+    assert_eq!((eur.value, eur.minor_unit), (1050, 2));
+    assert_eq!(
+        eur.to_money().unwrap(),
+        Money::from_minor_units(1050, "EUR").unwrap()
+    );
+
+    let cop = TotalAmount::try_from_amount(Amount::from_major(2500), "COP").unwrap();
+    assert_eq!((cop.value, cop.minor_unit), (250_000, 2));
+
+    assert!(TotalAmount::from_money(&Money::from_minor_units(-1, "EUR").unwrap()).is_err());
+}
+
+#[test]
+fn test_total_amount_try_from_amount_rounds_and_rejects_out_of_range() {
+    let half = TotalAmount::try_from_amount("10.125".parse().unwrap(), "EUR").unwrap();
+    assert_eq!(half.value, 1013);
+    let rounded = Money::rounded("10.125".parse::<Amount>().unwrap(), "EUR").unwrap();
+    assert_eq!(half.to_money().unwrap(), rounded);
+
+    assert!(TotalAmount::try_from_amount(Amount::from_cents(-500), "EUR").is_err());
+    assert!(TotalAmount::try_from_amount(Amount::from_major(i64::MAX), "EUR").is_err());
+
+    let total: TotalAmount =
+        serde_json::from_str(r#"{"currency": "EUR", "minor_unit": 40, "value": 1}"#).unwrap();
+    assert!(total.try_to_amount().is_err());
+    assert!(total.to_money().is_err());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_total_amount_infallible_conversions_saturate() {
+    let eur = TotalAmount::from_amount("10.125".parse().unwrap(), "EUR");
+    assert_eq!((eur.value, eur.minor_unit), (1013, 2));
+    assert_eq!(
+        TotalAmount::from_amount(Amount::from_cents(-500), "EUR").value,
+        0
+    );
+    assert_eq!(
+        TotalAmount::from_amount(Amount::from_major(i64::MAX), "EUR").value,
+        u64::MAX
+    );
+
+    let total: TotalAmount =
+        serde_json::from_str(r#"{"currency": "EUR", "minor_unit": 40, "value": 1}"#).unwrap();
+    assert_eq!(
+        total.to_amount(),
+        "0.0000000000000000000000000001".parse().unwrap()
+    );
+}
+
+#[test]
+fn test_checkout_request_from_money() {
+    let money = Money::from_minor_units(4990, "BRL").unwrap();
+    let request = CreateCheckoutRequest::from_money("ref", money.clone(), "M123");
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["amount"].to_string(), "49.90");
+    assert_eq!(json["currency"], "BRL");
+    assert_eq!(request.money().unwrap(), money);
+}
+
+fn transaction(currency: &str) -> Transaction {
+    serde_json::from_value(serde_json::json!({
+        "id": "txn_1",
+        "transaction_code": "TXN1",
+        "amount": 20000,
+        "currency": currency,
+        "timestamp": "2024-01-15T10:30:00Z",
+        "status": "SUCCESSFUL"
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_refund_transaction_money() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v0.1/me/refund/txn_1"))
+        .and(body_json(
+            serde_json::json!({"amount": 5000, "reason": "damaged"}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "txn_1",
+            "transaction_code": "TXN1",
+            "amount": 20000,
+            "currency": "CLP",
+            "timestamp": "2024-01-15T10:30:00Z",
+            "status": "REFUNDED"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let client = SumUpClient::builder()
+        .api_key("test-api-key")
+        .base_url(server.uri())
+        .build()
+        .unwrap();
+
+    let refund = Money::from_minor_units(5000, "CLP").unwrap();
+    client
+        .refund_transaction_money("M123", &transaction("CLP"), &refund, "damaged")
+        .await
+        .unwrap();
+
+    let err = client
+        .refund_transaction_money("M123", &transaction("EUR"), &refund, "damaged")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::Money(MoneyError::CurrencyMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_checkout_request_from_vat_breakdown() {
+    let products =
+        vec![
+            sumup_rs::Product::new("mug", 2, Amount::from_cents(1000), Decimal::new(19, 2))
+                .unwrap(),
+        ];
+    let breakdown = sumup_rs::VatCalculator::new("EUR")
+        .unwrap()
+        .calculate(&products)
+        .unwrap();
+    let request = CreateCheckoutRequest::from_vat_breakdown("ref", &breakdown, "M123");
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["amount"].to_string(), "23.80");
+    assert!(json.get("products").is_none());
+}
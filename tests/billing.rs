@@ -0,0 +1,289 @@
+#![cfg(feature = "billing")]
+
+use chrono::NaiveDate;
+use sumup_rs::billing::{
+    ChargeOutcome, DunningPolicy, InMemoryStore, ManualClock, Schedule, Subscription,
+    SubscriptionRunner, SubscriptionStatus, SubscriptionStore,
+};
+use sumup_rs::Amount;
+use wiremock::matchers::{body_partial_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::client;
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn checkout_json(id: &str, status: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "status": status,
+        "amount": 9.99,
+        "currency": "EUR",
+        "merchant_code": "M123",
+        "date": "2024-01-15T10:30:00+00:00",
+        "transactions": []
+    })
+}
+
+async fn mount_create_checkout(server: &MockServer) {
+    Mock::given(method("POST"))
+        .and(path("/v0.1/checkouts"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(checkout_json("chk_1", "PENDING")))
+        .mount(server)
+        .await;
+}
+
+fn subscription(schedule: Schedule) -> Subscription {
+    Subscription::new("sub_1", "M123", "cust_1", "tok_1", schedule).unwrap()
+}
+
+#[tokio::test]
+async fn test_runner_charges_due_subscription_and_advances_period() {
+    let server = MockServer::start().await;
+    mount_create_checkout(&server).await;
+    Mock::given(method("PUT"))
+        .and(path("/v0.1/checkouts/chk_1"))
+        .and(body_partial_json(serde_json::json!({
+            "payment_type": "card",
+            "token": "tok_1",
+            "customer_id": "cust_1"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(checkout_json("chk_1", "PAID")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let store = InMemoryStore::new();
+    store
+        .save(&subscription(Schedule::monthly(
+            date(2024, 1, 15),
+            Amount::from_cents(999),
+            "EUR",
+        )))
+        .unwrap();
+    let clock = ManualClock::at_date(date(2024, 1, 15));
+    let runner = SubscriptionRunner::with_clock(&client, &store, &clock);
+
+    let report = runner.run_due().await.unwrap();
+    assert_eq!(report.paid().count(), 1);
+
+    let saved = store.get("sub_1").unwrap().unwrap();
+    assert_eq!(saved.status, SubscriptionStatus::Active);
+    assert_eq!(saved.periods_billed, 1);
+    assert_eq!(saved.next_charge_date, date(2024, 2, 15));
+
+    // Nothing is due again until next month
+    let report = runner.run_due().await.unwrap();
+    assert!(report.results.is_empty());
+}
+
+#[tokio::test]
+async fn test_runner_skips_trial_period() {
+    let server = MockServer::start().await;
+    let client = client(&server);
+    let store = InMemoryStore::new();
+    let schedule =
+        Schedule::monthly(date(2024, 1, 1), Amount::from_cents(999), "EUR").trial_days(14);
+    store.save(&subscription(schedule)).unwrap();
+
+    let clock = ManualClock::at_date(date(2024, 1, 10));
+    let runner = SubscriptionRunner::with_clock(&client, &store, &clock);
+    let report = runner.run_due().await.unwrap();
+
+    assert!(report.results.is_empty());
+    assert_eq!(
+        store.get("sub_1").unwrap().unwrap().status,
+        SubscriptionStatus::Trialing
+    );
+}
+
+#[tokio::test]
+async fn test_runner_applies_dunning_until_unpaid() {
+    let server = MockServer::start().await;
+    mount_create_checkout(&server).await;
+    Mock::given(method("PUT"))
+        .and(path("/v0.1/checkouts/chk_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(checkout_json("chk_1", "FAILED")))
+        .expect(3)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let store = InMemoryStore::new();
+    store
+        .save(&subscription(Schedule::monthly(
+            date(2024, 1, 15),
+            Amount::from_cents(999),
+            "EUR",
+        )))
+        .unwrap();
+    let clock = ManualClock::at_date(date(2024, 1, 15));
+    let runner = SubscriptionRunner::with_clock(&client, &store, &clock)
+        .dunning(DunningPolicy::new(vec![1, 3]));
+
+    let report = runner.run_due().await.unwrap();
+    match &report.results[0].outcome {
+        ChargeOutcome::Failed { retry_on, .. } => assert_eq!(*retry_on, Some(date(2024, 1, 16))),
+        other => panic!("expected failure, got {:?}", other),
+    }
+    assert_eq!(
+        store.get("sub_1").unwrap().unwrap().status,
+        SubscriptionStatus::PastDue
+    );
+
+    clock.advance_days(1);
+    runner.run_due().await.unwrap();
+    assert_eq!(
+        store.get("sub_1").unwrap().unwrap().next_charge_date,
+        date(2024, 1, 19)
+    );
+
+    clock.advance_days(3);
+    let report = runner.run_due().await.unwrap();
+    assert!(matches!(
+        report.results[0].outcome,
+        ChargeOutcome::Failed { retry_on: None, .. }
+    ));
+
+    let saved = store.get("sub_1").unwrap().unwrap();
+    assert_eq!(saved.status, SubscriptionStatus::Unpaid);
+    assert_eq!(saved.failed_attempts, 3);
+
+    // Unpaid subscriptions are no longer charged
+    clock.advance_days(30);
+    assert!(runner.run_due().await.unwrap().results.is_empty());
+}
+
+#[tokio::test]
+async fn test_runner_records_api_decline_as_failure() {
+    let server = MockServer::start().await;
+    mount_create_checkout(&server).await;
+    Mock::given(method("PUT"))
+        .and(path("/v0.1/checkouts/chk_1"))
+        .respond_with(ResponseTemplate::new(402).set_body_json(serde_json::json!({
+            "error_code": "CARD_DECLINED",
+            "message": "Card declined"
+        })))
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let store = InMemoryStore::new();
+    store
+        .save(&subscription(Schedule::monthly(
+            date(2024, 1, 15),
+            Amount::from_cents(999),
+            "EUR",
+        )))
+        .unwrap();
+    let clock = ManualClock::at_date(date(2024, 1, 15));
+    let runner = SubscriptionRunner::with_clock(&client, &store, &clock);
+
+    let report = runner.run_due().await.unwrap();
+    assert_eq!(report.failed().count(), 1);
+
+    let saved = store.get("sub_1").unwrap().unwrap();
+    assert_eq!(saved.status, SubscriptionStatus::PastDue);
+    assert!(saved.last_error.unwrap().contains("Card declined"));
+    assert_eq!(saved.last_checkout_id.unwrap().as_str(), "chk_1");
+}
+
+#[tokio::test]
+async fn test_interrupted_run_resumes_saved_checkout() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v0.1/checkouts"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(checkout_json("chk_1", "PENDING")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/v0.1/checkouts/chk_1"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "30"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    // The charge went through before the run was interrupted
+    Mock::given(method("GET"))
+        .and(path("/v0.1/checkouts/chk_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(checkout_json("chk_1", "PAID")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let store = InMemoryStore::new();
+    store
+        .save(&subscription(Schedule::monthly(
+            date(2024, 1, 15),
+            Amount::from_cents(999),
+            "EUR",
+        )))
+        .unwrap();
+    let clock = ManualClock::at_date(date(2024, 1, 15));
+    let runner = SubscriptionRunner::with_clock(&client, &store, &clock);
+
+    assert!(runner.run_due().await.is_err());
+    let saved = store.get("sub_1").unwrap().unwrap();
+    assert_eq!(saved.pending_checkout_id.unwrap().as_str(), "chk_1");
+    assert_eq!(saved.periods_billed, 0);
+
+    let report = runner.run_due().await.unwrap();
+    assert!(matches!(
+        report.results[0].outcome,
+        ChargeOutcome::Paid { ref checkout_id } if checkout_id.as_str() == "chk_1"
+    ));
+    let saved = store.get("sub_1").unwrap().unwrap();
+    assert_eq!(saved.periods_billed, 1);
+    assert!(saved.pending_checkout_id.is_none());
+}
+
+#[tokio::test]
+async fn test_duplicate_reference_reuses_existing_checkout() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v0.1/checkouts"))
+        .respond_with(ResponseTemplate::new(409).set_body_json(serde_json::json!({
+            "error_code": "DUPLICATED_CHECKOUT",
+            "message": "Checkout with this checkout reference already exists"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    let mut existing = checkout_json("chk_7", "PENDING");
+    existing["checkout_reference"] = "sub-sub_1-0-0".into();
+    Mock::given(method("GET"))
+        .and(path("/v0.1/checkouts"))
+        .and(query_param("checkout_reference", "sub-sub_1-0-0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([existing])))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/v0.1/checkouts/chk_7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(checkout_json("chk_7", "PAID")))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let store = InMemoryStore::new();
+    store
+        .save(&subscription(Schedule::monthly(
+            date(2024, 1, 15),
+            Amount::from_cents(999),
+            "EUR",
+        )))
+        .unwrap();
+    let clock = ManualClock::at_date(date(2024, 1, 15));
+    let runner = SubscriptionRunner::with_clock(&client, &store, &clock);
+
+    let report = runner.run_due().await.unwrap();
+    assert_eq!(report.paid().count(), 1);
+    let saved = store.get("sub_1").unwrap().unwrap();
+    assert_eq!(saved.last_checkout_id.unwrap().as_str(), "chk_7");
+}
@@ -0,0 +1,13 @@
+//! Helpers shared by the mock-server integration tests
+
+use sumup_rs::SumUpClient;
+use wiremock::MockServer;
+
+/// Client pointed at `server` with a dummy API key
+pub fn client(server: &MockServer) -> SumUpClient {
+    SumUpClient::builder()
+        .api_key("test-api-key")
+        .base_url(server.uri())
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "csv")]
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sumup_rs::csv_io::{read_payouts_csv, write_journal_csv, write_transactions_csv};
+use sumup_rs::payouts::PayoutListQuery;
+use sumup_rs::{AccountMapping, LedgerExporter, PayoutType, Transaction};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::client;
+
+#[tokio::test]
+async fn test_list_merchant_payouts_csv() {
+    let server = MockServer::start().await;
+    let body = "id,date,type,status,amount,fee,currency,transaction_code,reference\n\
+        101,2024-03-04,PAYOUT,SUCCESSFUL,98.10,1.90,EUR,TX1,PO-1\n\
+        101,2024-03-04,REFUND_DEDUCTION,SUCCESSFUL,-20.00,0.00,EUR,TX0,PO-1\n";
+    Mock::given(method("GET"))
+        .and(path("/v1.0/merchants/M123/payouts"))
+        .and(query_param("format", "csv"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "text/csv"))
+        .mount(&server)
+        .await;
+
+    let day = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+    let payouts = client(&server)
+        .list_merchant_payouts_csv("M123", &PayoutListQuery::on(day))
+        .await
+        .unwrap();
+    assert_eq!(payouts.len(), 2);
+    assert_eq!(payouts[0].id.as_ref().unwrap(), "101");
+    assert_eq!(payouts[0].amount.0, Decimal::new(9810, 2));
+    assert_eq!(payouts[1].payout_type, PayoutType::RefundDeduction);
+    assert_eq!(read_payouts_csv(body.as_bytes()).unwrap().len(), 2);
+}
+
+#[test]
+fn test_write_transactions_csv_keeps_exact_amounts() {
+    // parsed from text so the amount keeps its scale (`json!` would go through f64)
+    let transaction: Transaction = serde_json::from_str(
+        r#"{"id": "txn_1", "transaction_code": "TX1", "amount": 10.10, "vat_amount": 1.61,
+            "currency": "EUR", "timestamp": "2024-03-01T10:00:00Z", "status": "SUCCESSFUL",
+            "payment_type": "POS", "auth_code": "A1,B2"}"#,
+    )
+    .unwrap();
+
+    let mut out = Vec::new();
+    write_transactions_csv(&mut out, [&transaction]).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let mut lines = text.lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        "id,transaction_code,timestamp,status,payment_type,amount,tip_amount,vat_amount,\
+         currency,merchant_code,entry_mode,auth_code,installments_count,internal_id"
+    );
+    assert_eq!(
+        lines.next().unwrap(),
+        "txn_1,TX1,2024-03-01T10:00:00+00:00,SUCCESSFUL,POS,10.10,,1.61,EUR,,,\"A1,B2\",,"
+    );
+}
+
+#[test]
+fn test_write_journal_csv() {
+    let transaction: Transaction = serde_json::from_str(
+        r#"{"id": "txn_1", "transaction_code": "TX1", "amount": 11.90, "vat_amount": 1.90,
+            "currency": "EUR", "timestamp": "2024-03-01T10:00:00Z", "status": "SUCCESSFUL"}"#,
+    )
+    .unwrap();
+    let journal = LedgerExporter::new(AccountMapping::default().sales("8400").clearing("1361"))
+        .export([&transaction], []);
+
+    let mut out = Vec::new();
+    write_journal_csv(&mut out, &journal).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "entry_id,date,account,debit,credit,currency,description,reference\n\
+         sale-TX1,2024-03-01,1361,11.90,,EUR,Sale TX1,TX1\n\
+         sale-TX1,2024-03-01,8400,,10.00,EUR,Sale TX1,TX1\n\
+         sale-TX1,2024-03-01,VAT payable,,1.90,EUR,Sale TX1,TX1\n"
+    );
+}
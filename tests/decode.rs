@@ -0,0 +1,138 @@
+use std::sync::{Arc, Mutex};
+use sumup_rs::{
+    CardType, Checkout, CheckoutStatus, DecodePolicy, Error, ExtraFields, Merchant, ReaderStatus,
+    SumUpClient, TransactionStatus, UnknownValue,
+};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn checkout_server(status: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/checkouts/chk_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "chk_1",
+            "status": status,
+            "amount": 10.00,
+            "currency": "EUR",
+            "date": "2024-01-01T00:00:00Z",
+            "transactions": [{
+                "id": "txn_1",
+                "transaction_code": "TX1",
+                "amount": 10.00,
+                "currency": "EUR",
+                "timestamp": "2024-01-01T00:00:00Z",
+                "status": "PARTIALLY_REFUNDED"
+            }]
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+#[test]
+fn test_unknown_values_round_trip() {
+    let status: CheckoutStatus = serde_json::from_str("\"ON_HOLD\"").unwrap();
+    assert_eq!(status, CheckoutStatus::Unknown("ON_HOLD".into()));
+    assert!(status.is_unknown());
+    assert_eq!(status.to_string(), "ON_HOLD");
+    assert_eq!(serde_json::to_string(&status).unwrap(), "\"ON_HOLD\"");
+
+    let card: CardType = serde_json::from_str("\"VISA_ELECTRON\"").unwrap();
+    assert_eq!(card, CardType::VisaElectron);
+    assert_eq!("CUP".parse::<CardType>().unwrap(), CardType::Cup);
+
+    let reader: ReaderStatus = serde_json::from_str("\"unknown\"").unwrap();
+    assert_eq!(reader, ReaderStatus::Unknown("unknown".into()));
+}
+
+#[tokio::test]
+async fn test_lenient_policy_keeps_unknown_values_and_calls_hook() {
+    let server = checkout_server("ON_HOLD").await;
+    let seen: Arc<Mutex<Vec<UnknownValue>>> = Arc::default();
+    let hook_seen = seen.clone();
+    let client = SumUpClient::builder()
+        .api_key("test-api-key")
+        .base_url(server.uri())
+        .on_unknown_value(move |value| hook_seen.lock().unwrap().push(value.clone()))
+        .build()
+        .unwrap();
+
+    let checkout = client.retrieve_checkout("chk_1").await.unwrap();
+    assert_eq!(checkout.status, CheckoutStatus::Unknown("ON_HOLD".into()));
+    assert_eq!(
+        checkout.transactions[0].status,
+        Some(TransactionStatus::Unknown("PARTIALLY_REFUNDED".into()))
+    );
+
+    let seen = seen.lock().unwrap();
+    let values: Vec<_> = seen
+        .iter()
+        .map(|v| (v.type_name, v.value.as_str()))
+        .collect();
+    assert_eq!(
+        values,
+        vec![
+            ("CheckoutStatus", "ON_HOLD"),
+            ("TransactionStatus", "PARTIALLY_REFUNDED")
+        ]
+    );
+    assert_eq!(seen[0].path, "/v0.1/checkouts/chk_1");
+}
+
+#[tokio::test]
+async fn test_strict_policy_rejects_unknown_values() {
+    let server = checkout_server("ON_HOLD").await;
+    let client = SumUpClient::builder()
+        .api_key("test-api-key")
+        .base_url(server.uri())
+        .decode_policy(DecodePolicy::Strict)
+        .build()
+        .unwrap();
+
+    match client.retrieve_checkout("chk_1").await {
+        Err(Error::UnknownValue(value)) => {
+            assert_eq!(value.type_name, "CheckoutStatus");
+            assert_eq!(value.value, "ON_HOLD");
+        }
+        other => panic!("expected unknown value error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_unknown_fields_are_captured() {
+    let checkout: Checkout = serde_json::from_value(serde_json::json!({
+        "id": "chk_1",
+        "status": "PAID",
+        "amount": 10.00,
+        "currency": "EUR",
+        "date": "2024-01-01T00:00:00Z",
+        "surcharge": {"amount": 0.5, "currency": "EUR"}
+    }))
+    .unwrap();
+
+    if cfg!(feature = "unknown-fields") {
+        assert_eq!(
+            checkout.extra_field("surcharge"),
+            Some(&serde_json::json!({"amount": 0.5, "currency": "EUR"}))
+        );
+        let round_trip = serde_json::to_value(&checkout).unwrap();
+        assert_eq!(round_trip["surcharge"]["currency"], "EUR");
+    } else {
+        assert!(checkout.extra_fields().is_empty());
+    }
+
+    let merchant: Merchant = serde_json::from_value(serde_json::json!({
+        "merchant_code": "M123",
+        "mcc": 5812
+    }))
+    .unwrap();
+    assert_eq!(
+        merchant.extra_fields().get_as::<u32>("mcc").unwrap(),
+        Some(5812)
+    );
+    assert_eq!(
+        merchant.extra_fields().get_as::<u32>("missing").unwrap(),
+        None
+    );
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "disputes")]
+
+use chrono::NaiveDate;
+use sumup_rs::clock::ManualClock;
+use sumup_rs::disputes::{DisputeNotificationKind, DisputeTracker, InMemoryDisputeStore};
+use sumup_rs::TransactionHistoryQuery;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::client;
+
+fn transaction(code: &str, status: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": format!("id-{code}"),
+        "transaction_code": code,
+        "amount": 40.00,
+        "currency": "EUR",
+        "timestamp": "2024-03-01T10:00:00Z",
+        "status": status
+    })
+}
+
+#[tokio::test]
+async fn test_sync_opens_disputes_with_details() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v2.1/merchants/M123/transactions/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [transaction("TX1", "SUCCESSFUL"), transaction("TX2", "CHARGE_BACK")],
+            "links": []
+        })))
+        .mount(&server)
+        .await;
+    let mut detailed = transaction("TX2", "CHARGE_BACK");
+    detailed["events"] = serde_json::json!([
+        {"type": "CHARGE_BACK", "status": "PENDING", "amount": 40.00,
+         "timestamp": "2024-03-09T12:00:00Z"}
+    ]);
+    Mock::given(method("GET"))
+        .and(path("/v2.1/merchants/M123/transactions"))
+        .and(query_param("id", "id-TX2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(detailed))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let clock = ManualClock::at_date(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap());
+    let tracker = DisputeTracker::with_clock(InMemoryDisputeStore::new(), &clock);
+    let client = client(&server);
+    let query = TransactionHistoryQuery::default();
+
+    // opened from the chargeback event on the 9th, so due on the 16th
+    let kinds: Vec<_> = tracker
+        .sync(&client, "M123", &query)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|n| n.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            DisputeNotificationKind::Opened,
+            DisputeNotificationKind::DeadlineApproaching
+        ]
+    );
+
+    // already tracked: no new notifications and no second details request
+    assert!(tracker
+        .sync(&client, "M123", &query)
+        .await
+        .unwrap()
+        .is_empty());
+    let dispute = tracker.store().all().pop().unwrap();
+    assert_eq!(dispute.events.len(), 1);
+    assert!(dispute.reminder_sent);
+}
@@ -1,5 +1,8 @@
-use sumup_rs::{CountryCode, Currency, DoingBusinessAs, MerchantCode, MerchantProfile, MerchantProfileDetails, SumUpClient};
-use wiremock::matchers::{method, path};
+use sumup_rs::{
+    CountryCode, Currency, DoingBusinessAs, MerchantCode, MerchantProfile,
+    MerchantProfileDetails, SumUpClient, UpdateMerchantProfileRequest, UpdateMerchantSettingsRequest,
+};
+use wiremock::matchers::{body_json, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -205,9 +208,9 @@ async fn test_list_merchants_success() {
     assert!(result.is_ok());
     let memberships = result.unwrap();
     assert_eq!(memberships.len(), 2);
-    assert_eq!(memberships[0].resource_id, Some("merchant_1".to_string()));
+    assert_eq!(memberships[0].resource_id.as_ref().unwrap(), "merchant_1");
     assert_eq!(memberships[0].resource.as_ref().map(|r| r.name.clone()), Some("First Merchant".to_string()));
-    assert_eq!(memberships[1].resource_id, Some("merchant_2".to_string()));
+    assert_eq!(memberships[1].resource_id.as_ref().unwrap(), "merchant_2");
     assert_eq!(memberships[1].resource.as_ref().map(|r| r.name.clone()), Some("Second Merchant".to_string()));
 }
 
@@ -275,3 +278,100 @@ async fn test_get_merchant_not_found() {
         _ => panic!("Expected ApiError, got {:?}", error),
     }
 }
+
+#[tokio::test]
+async fn test_update_business_profile_sends_only_set_fields() {
+    // Arrange
+    let mock_server = MockServer::start().await;
+    let client = SumUpClient::builder()
+        .api_key("test-api-key")
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    Mock::given(method("PUT"))
+        .and(path("/v0.1/me/merchant-profile"))
+        .and(body_json(serde_json::json!({
+            "company_name": "New Name GmbH",
+            "website": null
+        })))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/v0.1/me/merchant-profile/settings"))
+        .and(body_json(serde_json::json!({
+            "tax_enabled": true,
+            "moto_payment": null
+        })))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let profile = UpdateMerchantProfileRequest::default()
+        .company_name("New Name GmbH")
+        .clear_website();
+    let settings = UpdateMerchantSettingsRequest::default()
+        .tax_enabled(true)
+        .clear_moto_payment();
+
+    // Assert
+    client.update_business_profile(&profile).await.unwrap();
+    client.update_merchant_settings(&settings).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_read_bank_accounts_personal_profile_and_settings() {
+    // Arrange
+    let mock_server = MockServer::start().await;
+    let client = SumUpClient::builder()
+        .api_key("test-api-key")
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/v0.1/me/merchant-profile/bank-accounts"))
+        .and(query_param("primary", "true"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"iban": "DE89370400440532013000", "bank_name": "Commerzbank",
+             "account_holder_name": "Test Merchant", "primary": true, "status": "ACTIVE"}
+        ])))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/me/personal-profile"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "first_name": "Ada", "last_name": "Lovelace", "complete": true
+        })))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/me/merchant-profile/settings"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "tax_enabled": false, "moto_payment": "OFF", "payout_period": "DAILY"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    // Act
+    let accounts = client.list_bank_accounts(true).await.unwrap();
+    let person = client.get_personal_profile().await.unwrap();
+    let settings = client.get_merchant_settings().await.unwrap();
+
+    // Assert
+    assert_eq!(accounts.len(), 1);
+    assert!(accounts[0].primary);
+    assert_eq!(accounts[0].iban.as_deref(), Some("DE89370400440532013000"));
+    let debug = format!("{:?}", accounts[0]);
+    assert!(!debug.contains("DE89370400440532013000"));
+    assert!(debug.contains("******************3000"));
+    assert_eq!(person.first_name.as_deref(), Some("Ada"));
+    assert!(person.complete);
+    assert_eq!(settings.tax_enabled, Some(false));
+    assert_eq!(settings.moto_payment.as_deref(), Some("OFF"));
+    assert_eq!(settings.payout_period.as_deref(), Some("DAILY"));
+}
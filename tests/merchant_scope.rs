@@ -0,0 +1,158 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use sumup_rs::{Error, Membership};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::client;
+
+fn membership(id: &str, kind: &str, resource_id: &str) -> Membership {
+    membership_with_status(id, kind, resource_id, "active")
+}
+
+fn membership_with_status(id: &str, kind: &str, resource_id: &str, status: &str) -> Membership {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "type": kind,
+        "resource_id": resource_id,
+        "roles": ["role_owner"],
+        "status": status,
+        "resource": {
+            "id": resource_id,
+            "type": kind,
+            "name": resource_id,
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z"
+        }
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_merchant_scope_fills_in_merchant_code() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/merchants/M123/readers"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"items": []})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/merchants/M123/roles"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"roles": []})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let merchant = client.merchant("M123");
+    assert_eq!(merchant.merchant_code(), "M123");
+    assert!(merchant.list_readers().await.unwrap().items.is_empty());
+    assert!(merchant.list_roles().await.unwrap().roles.is_empty());
+}
+
+#[test]
+fn test_membership_merchant_code_skips_organizations() {
+    assert_eq!(
+        membership("mem_1", "merchant", "M1")
+            .merchant_code()
+            .unwrap(),
+        "M1"
+    );
+    assert!(membership("mem_2", "organization", "O1")
+        .merchant_code()
+        .is_none());
+
+    let mut bare = membership("mem_3", "merchant", "M3");
+    bare.resource = None;
+    assert_eq!(bare.merchant_code().unwrap(), "M3");
+}
+
+#[tokio::test]
+async fn test_for_each_merchant_fans_out_with_bounded_concurrency() {
+    let server = MockServer::start().await;
+    for code in ["M1", "M2", "M3", "M4"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/v0.1/merchants/{code}/readers")))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"items": []}))
+                    .set_delay(Duration::from_millis(50)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/v0.1/merchants/M5/readers"))
+        .respond_with(
+            ResponseTemplate::new(404).set_body_json(serde_json::json!({"message": "Not found"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let memberships = vec![
+        membership("mem_1", "merchant", "M1"),
+        membership("mem_2", "organization", "O1"),
+        membership("mem_3", "merchant", "M2"),
+        membership("mem_4", "merchant", "M3"),
+        membership("mem_5", "merchant", "M1"),
+        membership("mem_6", "merchant", "M4"),
+        membership("mem_7", "merchant", "M5"),
+    ];
+
+    let client = client(&server);
+    let in_flight = AtomicUsize::new(0);
+    let peak = AtomicUsize::new(0);
+    let results = client
+        .for_each_merchant(&memberships, 2, |merchant| {
+            let (in_flight, peak) = (&in_flight, &peak);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                let readers = merchant.list_readers().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                readers.map(|r| r.items.len())
+            }
+        })
+        .await;
+
+    let codes: Vec<&str> = results.iter().map(|(code, _)| code.as_str()).collect();
+    assert_eq!(codes, vec!["M1", "M2", "M3", "M4", "M5"]);
+    assert!(results[..4].iter().all(|(_, r)| matches!(r, Ok(0))));
+    assert!(matches!(
+        results[4].1,
+        Err(Error::ApiError { status: 404, .. })
+    ));
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_for_each_merchant_only_visits_active_memberships() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/merchants/M1/readers"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"items": []})))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let memberships = vec![
+        membership_with_status("mem_1", "merchant", "M2", "pending"),
+        membership_with_status("mem_2", "merchant", "M1", "active"),
+        membership_with_status("mem_3", "merchant", "M3", "declined"),
+        membership_with_status("mem_4", "merchant", "M4", "revoked"),
+        membership_with_status("mem_5", "merchant", "M5", "suspended"),
+    ];
+    let results = client(&server)
+        .for_each_merchant(&memberships, 4, |merchant| async move {
+            merchant.list_readers().await.map(|r| r.items.len())
+        })
+        .await;
+
+    let codes: Vec<&str> = results.iter().map(|(code, _)| code.as_str()).collect();
+    assert_eq!(codes, vec!["M1"]);
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
@@ -0,0 +1,160 @@
+use sumup_rs::{MembershipListQuery, MembershipStatus, ResourceType};
+use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::client;
+
+fn membership_json(id: &str, status: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "type": "merchant",
+        "resource_id": "M123",
+        "roles": ["role_employee"],
+        "status": status
+    })
+}
+
+#[tokio::test]
+async fn test_list_organizations_and_their_merchants() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/organizations"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [{"id": "org_1", "name": "Franchise Group"}],
+            "total_count": 1
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/organizations/org_1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "org_1",
+            "name": "Franchise Group",
+            "created_at": "2024-01-01T00:00:00Z"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/organizations/org_1/merchants"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [
+                {"merchant_code": "M1", "name": "Berlin", "country": "DE"},
+                {"merchant_code": "M2", "name": "Paris", "country": "FR"}
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let organizations = client.list_organizations().await.unwrap();
+    assert_eq!(organizations.len(), 1);
+    assert_eq!(organizations[0].id, "org_1");
+
+    let organization = client.retrieve_organization("org_1").await.unwrap();
+    assert_eq!(organization.name, "Franchise Group");
+    assert!(organization.created_at.is_some());
+
+    let merchants = client.list_organization_merchants("org_1").await.unwrap();
+    let codes: Vec<&str> = merchants.iter().map(|m| m.merchant_code.as_str()).collect();
+    assert_eq!(codes, vec!["M1", "M2"]);
+}
+
+#[tokio::test]
+async fn test_list_organization_merchants_follows_total_count() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/organizations/org_1/merchants"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [{"merchant_code": "M3"}],
+            "total_count": 3
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/organizations/org_1/merchants"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [{"merchant_code": "M1"}, {"merchant_code": "M2"}],
+            "total_count": 3
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let merchants = client(&server)
+        .list_organization_merchants("org_1")
+        .await
+        .unwrap();
+    let codes: Vec<&str> = merchants.iter().map(|m| m.merchant_code.as_str()).collect();
+    assert_eq!(codes, vec!["M1", "M2", "M3"]);
+}
+
+#[tokio::test]
+async fn test_list_all_memberships_sends_filters_and_pages() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/memberships"))
+        .and(query_param("status", "pending"))
+        .and(query_param("resource.type", "merchant"))
+        .and(query_param("roles", "role_employee"))
+        .and(query_param("limit", "2"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [membership_json("mem_1", "pending"), membership_json("mem_2", "pending")],
+            "total_count": 3
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/memberships"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [membership_json("mem_3", "pending")],
+            "total_count": 3
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let query = MembershipListQuery::new()
+        .status(MembershipStatus::Pending)
+        .resource_type(ResourceType::Merchant)
+        .role("role_employee")
+        .limit(2)
+        .offset(0);
+    let memberships = client(&server).list_all_memberships(&query).await.unwrap();
+
+    let ids: Vec<&str> = memberships.iter().map(|m| m.id.as_str()).collect();
+    assert_eq!(ids, vec!["mem_1", "mem_2", "mem_3"]);
+    assert!(memberships.iter().all(|m| m.is_pending_invitation()));
+}
+
+#[tokio::test]
+async fn test_accept_and_decline_membership_invitations() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .and(path("/v0.1/memberships/mem_1"))
+        .and(body_json(serde_json::json!({"status": "active"})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(membership_json("mem_1", "active")))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/v0.1/memberships/mem_2"))
+        .and(body_json(serde_json::json!({"status": "declined"})))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(membership_json("mem_2", "declined")),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = client(&server);
+    let accepted = client.accept_membership_invitation("mem_1").await.unwrap();
+    assert_eq!(accepted.status, MembershipStatus::Active);
+    let declined = client.decline_membership_invitation("mem_2").await.unwrap();
+    assert_eq!(declined.status, MembershipStatus::Declined);
+}
@@ -1,8 +1,11 @@
 use sumup_rs::{
-    Address, Amount, CardDetails, CheckoutStatus, CreateCheckoutRequest, CustomerId, PersonalDetails,
-    ProcessCheckoutRequest, ProcessCheckoutResponse, SumUpClient, TransactionId, TransactionStatus,
+    Address, Amount, ApplePayPaymentRequest, ApplePaySessionRequest, ApplePayToken,
+    AvailablePaymentMethodsResponse, CardDetails, Checkout, GooglePayPaymentDataRequest,
+    GooglePayToken, CheckoutStatus, CreateCheckoutRequest, CustomerId,
+    PaymentAction, PaymentType, PersonalDetails, ProcessCheckoutRequest, ProcessCheckoutResponse,
+    SumUpClient, TransactionId, TransactionStatus,
 };
-use wiremock::matchers::{header, method, path};
+use wiremock::matchers::{body_partial_json, header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 #[tokio::test]
@@ -496,3 +499,269 @@ async fn test_different_mock_card_types() {
         }
     }
 }
+
+#[test]
+fn test_alternative_payment_method_validation() {
+    assert!(ProcessCheckoutRequest::ideal().validate().is_ok());
+    let ideal = ProcessCheckoutRequest::ideal().issuer("INGBNL2A");
+    assert_eq!(
+        serde_json::to_value(&ideal).unwrap(),
+        serde_json::json!({"payment_type": "ideal", "issuer": "INGBNL2A"})
+    );
+    assert!(ProcessCheckoutRequest::bancontact().validate().is_ok());
+    assert!(ProcessCheckoutRequest::blik("123456").validate().is_ok());
+    assert!(ProcessCheckoutRequest::blik("12345").validate().is_err());
+    assert!(ProcessCheckoutRequest::blik("12345a").validate().is_err());
+
+    let incomplete = ProcessCheckoutRequest::boleto(PersonalDetails {
+        first_name: Some("Maria".to_string()),
+        ..Default::default()
+    });
+    let err = incomplete.validate().unwrap_err().to_string();
+    assert!(err.contains("personal_details.tax_id"));
+    assert!(err.contains("personal_details.address"));
+
+    let complete = ProcessCheckoutRequest::boleto(PersonalDetails {
+        first_name: Some("Maria".to_string()),
+        last_name: Some("Silva".to_string()),
+        email: Some("maria@example.com".to_string()),
+        tax_id: Some("12345678909".to_string()),
+        address: Some(Address {
+            city: Some("São Paulo".to_string()),
+            country: Some("BR".to_string()),
+            postal_code: Some("01310-100".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    assert!(complete.validate().is_ok());
+    let json = serde_json::to_value(&complete).unwrap();
+    assert_eq!(json["payment_type"], "boleto");
+    assert_eq!(json["personal_details"]["tax_id"], "12345678909");
+}
+
+#[test]
+fn test_payment_action_for_alternative_methods() {
+    let boleto: ProcessCheckoutResponse = serde_json::from_value(serde_json::json!({
+        "id": "checkout-boleto",
+        "status": "PENDING",
+        "amount": 100.00,
+        "currency": "BRL",
+        "date": "2024-01-15T10:30:00+00:00",
+        "boleto": {
+            "barcode": "34191790010104351004791020150008291070026000",
+            "url": "https://example.com/boleto.pdf"
+        }
+    }))
+    .unwrap();
+    match boleto.action() {
+        PaymentAction::Boleto(details) => assert_eq!(details.url, "https://example.com/boleto.pdf"),
+        other => panic!("expected boleto action, got {:?}", other),
+    }
+
+    let ideal = ProcessCheckoutResponse::Accepted(
+        serde_json::from_value(serde_json::json!({
+            "next_step": {
+                "url": "https://bank.example.com/ideal",
+                "method": "GET",
+                "redirect_url": "https://example.com/return"
+            }
+        }))
+        .unwrap(),
+    );
+    match ideal.action() {
+        PaymentAction::Redirect(step) => assert_eq!(step.url, "https://bank.example.com/ideal"),
+        other => panic!("expected redirect action, got {:?}", other),
+    }
+
+    let blik: ProcessCheckoutResponse = serde_json::from_value(serde_json::json!({
+        "id": "checkout-blik",
+        "status": "PENDING",
+        "amount": 50.00,
+        "currency": "PLN",
+        "date": "2024-01-15T10:30:00+00:00"
+    }))
+    .unwrap();
+    assert!(matches!(blik.action(), PaymentAction::AwaitingConfirmation));
+}
+
+#[tokio::test]
+async fn test_process_checkout_checked_rejects_unavailable_method() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v0.1/merchants/M123/payment-methods"))
+        .and(query_param("currency", "EUR"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "available_payment_methods": [{ "id": "card" }, { "id": "ideal" }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("PUT"))
+        .and(path("/v0.1/checkouts/checkout-nl"))
+        .and(body_partial_json(serde_json::json!({ "payment_type": "ideal" })))
+        .respond_with(ResponseTemplate::new(202).set_body_json(serde_json::json!({
+            "next_step": {
+                "url": "https://bank.example.com/ideal",
+                "method": "GET"
+            }
+        })))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = SumUpClient::builder()
+        .api_key("test-api-key")
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+    let checkout: Checkout = serde_json::from_value(serde_json::json!({
+        "id": "checkout-nl",
+        "status": "PENDING",
+        "amount": 25.00,
+        "currency": "EUR",
+        "merchant_code": "M123",
+        "date": "2024-01-15T10:30:00+00:00"
+    }))
+    .unwrap();
+
+    let result = client
+        .process_checkout_checked(&checkout, &ProcessCheckoutRequest::bancontact())
+        .await;
+    assert!(matches!(result, Err(sumup_rs::Error::InvalidInput(_))));
+
+    let result = client
+        .process_checkout_checked(&checkout, &ProcessCheckoutRequest::ideal())
+        .await
+        .unwrap();
+    assert!(matches!(result.action(), PaymentAction::Redirect(_)));
+
+    let methods = client
+        .get_available_payment_methods("M123", None, Some(&"EUR".into()))
+        .await
+        .unwrap();
+    assert!(methods.supports(PaymentType::Card));
+    assert!(!methods.supports(PaymentType::Blik));
+}
+
+#[test]
+fn test_wallet_payment_requests() {
+    let apple = ProcessCheckoutRequest::apple_pay(ApplePayToken::new(serde_json::json!({
+        "paymentData": { "version": "EC_v1", "data": "abc" },
+        "paymentMethod": { "network": "Visa", "type": "debit" },
+        "transactionIdentifier": "123"
+    })));
+    assert!(apple.validate().is_ok());
+    let json = serde_json::to_value(&apple).unwrap();
+    assert_eq!(json["payment_type"], "apple_pay");
+    assert_eq!(json["apple_pay"]["paymentData"]["version"], "EC_v1");
+
+    let google = ProcessCheckoutRequest::google_pay(
+        GooglePayToken::from_json(r#"{"apiVersion":2,"paymentMethodData":{"type":"CARD"}}"#)
+            .unwrap(),
+    );
+    assert!(google.validate().is_ok());
+    assert_eq!(serde_json::to_value(&google).unwrap()["payment_type"], "google_pay");
+
+    let empty = ProcessCheckoutRequest::apple_pay(ApplePayToken::new(serde_json::json!({})));
+    assert!(empty.validate().is_err());
+}
+
+#[test]
+fn test_wallet_configuration_from_available_methods() {
+    let checkout: Checkout = serde_json::from_value(serde_json::json!({
+        "id": "checkout-wallet",
+        "status": "PENDING",
+        "amount": 12.5,
+        "currency": "EUR",
+        "merchant_code": "M123",
+        "date": "2024-01-15T10:30:00+00:00"
+    }))
+    .unwrap();
+    let methods: AvailablePaymentMethodsResponse = serde_json::from_value(serde_json::json!({
+        "available_payment_methods": [{ "id": "card" }, { "id": "google_pay" }]
+    }))
+    .unwrap();
+
+    assert!(ApplePayPaymentRequest::for_checkout(&methods, &checkout, "DE", "Coffee").is_err());
+
+    let request = GooglePayPaymentDataRequest::for_checkout(&methods, &checkout, "DE", "Café")
+        .unwrap()
+        .merchant_id("BCR2DN4T");
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["apiVersion"], 2);
+    assert_eq!(json["transactionInfo"]["totalPrice"], "12.50");
+    assert_eq!(json["transactionInfo"]["currencyCode"], "EUR");
+    assert_eq!(json["merchantInfo"]["merchantId"], "BCR2DN4T");
+    let tokenization = &json["allowedPaymentMethods"][0]["tokenizationSpecification"];
+    assert_eq!(tokenization["parameters"]["gateway"], "sumup");
+    assert_eq!(tokenization["parameters"]["gatewayMerchantId"], "M123");
+
+    // Prices use the currency's own minor units, not a fixed two decimals
+    for (amount, currency, expected) in [("1500", "CLP", "1500"), ("1.25", "KWD", "1.250")] {
+        let checkout: Checkout = serde_json::from_str(&format!(
+            r#"{{"id": "checkout-wallet", "status": "PENDING", "amount": {amount},
+                "currency": "{currency}", "merchant_code": "M123",
+                "date": "2024-01-15T10:30:00+00:00"}}"#
+        ))
+        .unwrap();
+        let request =
+            GooglePayPaymentDataRequest::for_checkout(&methods, &checkout, "DE", "Café").unwrap();
+        assert_eq!(request.transaction_info.total_price, expected);
+    }
+}
+
+#[tokio::test]
+async fn test_create_apple_pay_session() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/v0.2/checkouts/checkout-wallet/apple-pay-session"))
+        .and(body_partial_json(serde_json::json!({
+            "context": "shop.example.com",
+            "target": "https://apple-pay-gateway.apple.com/paymentservices/startSession"
+        })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "merchantSessionIdentifier": "SSH123",
+            "displayName": "Coffee Shop"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let client = SumUpClient::builder()
+        .api_key("test-api-key")
+        .base_url(mock_server.uri())
+        .build()
+        .unwrap();
+    let session = client
+        .create_apple_pay_session(
+            "checkout-wallet",
+            &ApplePaySessionRequest::new(
+                "shop.example.com",
+                "https://apple-pay-gateway.apple.com/paymentservices/startSession",
+            ),
+        )
+        .await
+        .unwrap();
+    assert_eq!(session["merchantSessionIdentifier"], "SSH123");
+}
+
+#[test]
+fn test_card_details_are_redacted_in_debug_output() {
+    let card = CardDetails::new("4242 4242 4242 4242", "12", "2027", "987").name("John Doe");
+    assert_eq!(card.number.to_string(), "424242******4242");
+    assert_eq!(card.number.last_4_digits(), "4242");
+
+    let request = ProcessCheckoutRequest::card(card);
+    let debug = format!("{:?}", request);
+    assert!(!debug.contains("4242 4242 4242 4242"));
+    assert!(!debug.contains("987"));
+    assert!(debug.contains("424242******4242"));
+    assert!(debug.contains("[REDACTED]"));
+
+    // The request body sent to the API still carries the full values
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["card"]["number"], "4242 4242 4242 4242");
+    assert_eq!(json["card"]["cvv"], "987");
+}
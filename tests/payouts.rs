@@ -0,0 +1,248 @@
+use chrono::NaiveDate;
+use sumup_rs::payouts::{PayoutListQuery, SortOrder};
+use sumup_rs::{PayoutStatus, PayoutType, TransactionHistoryQuery};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::client;
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+fn payout_entries() -> serde_json::Value {
+    serde_json::json!([
+        {"id": 101, "amount": 98.10, "fee": 1.90, "currency": "EUR", "date": "2024-03-04",
+         "status": "SUCCESSFUL", "type": "PAYOUT", "transaction_code": "TX1", "reference": "PO-1"},
+        {"id": 101, "amount": 49.05, "fee": 0.95, "currency": "EUR", "date": "2024-03-04",
+         "status": "SUCCESSFUL", "type": "PAYOUT", "transaction_code": "TX2", "reference": "PO-1"},
+        {"id": 101, "amount": -20.00, "fee": 0, "currency": "EUR", "date": "2024-03-04",
+         "status": "SUCCESSFUL", "type": "REFUND_DEDUCTION", "transaction_code": "TX0", "reference": "PO-1"},
+        {"id": 102, "amount": 10.00, "fee": 0.20, "currency": "EUR", "date": "2024-03-04",
+         "status": "FAILED", "type": "PAYOUT", "transaction_code": "TX3", "reference": "PO-2"}
+    ])
+}
+
+fn transaction(code: &str) -> serde_json::Value {
+    serde_json::json!({
+        "id": format!("id-{code}"),
+        "transaction_code": code,
+        "amount": 100.00,
+        "currency": "EUR",
+        "timestamp": "2024-03-01T10:00:00Z",
+        "status": "SUCCESSFUL"
+    })
+}
+
+#[tokio::test]
+async fn test_list_payouts_is_typed() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1.0/merchants/M123/payouts"))
+        .and(query_param("start_date", "2024-03-01"))
+        .and(query_param("end_date", "2024-03-31"))
+        .and(query_param("order", "asc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(payout_entries()))
+        .mount(&server)
+        .await;
+
+    let query = PayoutListQuery::new(date(2024, 3, 1), date(2024, 3, 31)).order(SortOrder::Asc);
+    let payouts = client(&server)
+        .list_merchant_payouts("M123", &query)
+        .await
+        .unwrap();
+
+    assert_eq!(payouts.items.len(), 4);
+    let first = &payouts.items[0];
+    assert_eq!(first.id.as_ref().unwrap(), "101");
+    assert_eq!(first.status, PayoutStatus::Successful);
+    assert_eq!(first.payout_type, PayoutType::Payout);
+    assert_eq!(first.date, date(2024, 3, 4));
+    assert_eq!(first.fee_money().unwrap().to_minor_units().unwrap(), 190);
+    assert!(payouts.items[2].payout_type.is_deduction());
+    assert!(!payouts.items[3].status.is_paid_out());
+
+    assert_eq!(payouts.references(), vec!["PO-1", "PO-2"]);
+    assert_eq!(
+        payouts.transaction_codes_for("PO-1"),
+        vec!["TX1", "TX2", "TX0"]
+    );
+}
+
+#[tokio::test]
+async fn test_reversed_date_range_is_rejected_locally() {
+    let server = MockServer::start().await;
+    let query = PayoutListQuery::new(date(2024, 3, 31), date(2024, 3, 1));
+    assert!(client(&server)
+        .list_merchant_payouts("M123", &query)
+        .await
+        .is_err());
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_list_payout_transactions() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1.0/merchants/M123/payouts"))
+        .and(query_param("start_date", "2024-03-04"))
+        .and(query_param("end_date", "2024-03-04"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(payout_entries()))
+        .mount(&server)
+        .await;
+    for code in ["TX0", "TX1", "TX2"] {
+        Mock::given(method("GET"))
+            .and(path("/v2.1/merchants/M123/transactions"))
+            .and(query_param("transaction_code", code))
+            .respond_with(ResponseTemplate::new(200).set_body_json(transaction(code)))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let client = client(&server);
+    let entries = client
+        .list_merchant_payouts("M123", &PayoutListQuery::on(date(2024, 3, 4)))
+        .await
+        .unwrap();
+    let transactions = client
+        .list_payout_transactions("M123", &entries.items[0])
+        .await
+        .unwrap();
+
+    let codes: Vec<_> = transactions
+        .iter()
+        .map(|t| t.transaction_code.as_str())
+        .collect();
+    assert_eq!(codes, vec!["TX1", "TX2", "TX0"]);
+}
+
+#[tokio::test]
+async fn test_reconcile_payouts_follows_history_pages() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1.0/merchants/M123/payouts"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(payout_entries()))
+        .mount(&server)
+        .await;
+    let mut tx1 = transaction("TX1");
+    tx1["timestamp"] = "2024-03-01T09:00:00Z".into();
+    Mock::given(method("GET"))
+        .and(path("/v2.1/merchants/M123/transactions/history"))
+        .and(query_param("newest_time", "2024-03-01T09:00:00Z"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [tx1.clone(), transaction("TX0")],
+            "links": []
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v2.1/merchants/M123/transactions/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [transaction("TX2"), tx1],
+            "links": [{"rel": "next", "href": "?newest_time=2024-03-01T09:00:00Z", "type": "application/json"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let report = client(&server)
+        .reconcile_payouts(
+            "M123",
+            &PayoutListQuery::on(date(2024, 3, 4)),
+            &TransactionHistoryQuery::default(),
+        )
+        .await
+        .unwrap();
+
+    // TX1 repeats across pages but is matched once
+    let matched: Vec<_> = report
+        .matched
+        .iter()
+        .map(|m| m.transaction.transaction_code.as_str())
+        .collect();
+    assert_eq!(matched, vec!["TX1", "TX0"]);
+    assert_eq!(report.discrepancies.len(), 1);
+    assert_eq!(report.unmatched_payouts.len(), 1);
+}
+
+#[tokio::test]
+async fn test_history_paging_stops_when_a_page_brings_nothing_new() {
+    let server = MockServer::start().await;
+    // Ascending order: the next link always points back at the same page
+    Mock::given(method("GET"))
+        .and(path("/v2.1/merchants/M123/transactions/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [transaction("TX1"), transaction("TX2")],
+            "links": [{"rel": "next", "href": "?order=asc&oldest_time=2024-03-01T10:00:00Z", "type": "application/json"}]
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let query = TransactionHistoryQuery {
+        order: Some("asc".into()),
+        ..Default::default()
+    };
+    let transactions = client(&server)
+        .list_transactions_history_all("M123", &query, None)
+        .await
+        .unwrap();
+    assert_eq!(transactions.len(), 2);
+}
+
+#[tokio::test]
+async fn test_history_paging_follows_next_link_and_respects_max_pages() {
+    let server = MockServer::start().await;
+    for page in 1..=3 {
+        Mock::given(method("GET"))
+            .and(path("/v2.1/merchants/M123/transactions/history"))
+            .and(query_param("cursor", page.to_string()))
+            .and(query_param("limit", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "items": [transaction(&format!("TX{}", page + 1))],
+                "links": [{"rel": "next", "href": format!("?cursor={}", page + 1), "type": "application/json"}]
+            })))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/v2.1/merchants/M123/transactions/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [transaction("TX1")],
+            "links": [{"rel": "next", "href": "?cursor=1", "type": "application/json"}]
+        })))
+        .mount(&server)
+        .await;
+
+    let query = TransactionHistoryQuery {
+        limit: Some(1),
+        ..Default::default()
+    };
+    let transactions = client(&server)
+        .list_transactions_history_all("M123", &query, Some(3))
+        .await
+        .unwrap();
+    let codes: Vec<&str> = transactions
+        .iter()
+        .map(|t| t.transaction_code.as_str())
+        .collect();
+    assert_eq!(codes, vec!["TX1", "TX2", "TX3"]);
+}
+
+#[test]
+fn test_payout_bank_account_debug_masks_iban() {
+    let mut entry = payout_entries()[0].clone();
+    entry["bank_account"] = serde_json::json!({
+        "iban": "DE89370400440532013000",
+        "bic": "COBADEFFXXX",
+        "bank_name": "Commerzbank"
+    });
+    let payout: sumup_rs::Payout = serde_json::from_value(entry).unwrap();
+    let bank_account = payout.bank_account.as_ref().unwrap();
+    assert_eq!(bank_account.iban.as_deref(), Some("DE89370400440532013000"));
+
+    let debug = format!("{:?}", payout);
+    assert!(!debug.contains("DE89370400440532013000"));
+    assert!(debug.contains("******************3000"));
+}
@@ -0,0 +1,122 @@
+use sumup_rs::{CreateReaderCheckoutRequest, Money, ReaderId, TipRate, TotalAmount};
+use wiremock::matchers::{body_partial_json, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::client;
+
+#[tokio::test]
+async fn test_reader_checkout_sends_exact_tip_rates() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v0.1/merchants/M123/readers/rdr_1/checkout"))
+        .and(body_partial_json(serde_json::json!({
+            "total_amount": {"value": 1500, "currency": "EUR", "minor_unit": 2},
+            "tip_rates": [0.1, 0.15, 0.2],
+            "tip_timeout": 60
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "data": {"client_transaction_id": "ctx_1"}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let rates = [20, 10, 15].map(|p| TipRate::percent(p).unwrap());
+    let total = TotalAmount::from_money(&Money::from_minor_units(1500, "EUR").unwrap()).unwrap();
+    let request = CreateReaderCheckoutRequest::new(total)
+        .tip_rate_values(&rates)
+        .tip_timeout(60);
+
+    let response = client(&server)
+        .create_merchant_reader_checkout("M123", "rdr_1", &request)
+        .await
+        .unwrap();
+    assert_eq!(response.data.client_transaction_id, "ctx_1");
+
+    let tips: Vec<i64> = request
+        .tip_breakdowns()
+        .unwrap()
+        .iter()
+        .map(|b| b.tip.to_minor_units().unwrap())
+        .collect();
+    assert_eq!(tips, vec![150, 225, 300]);
+}
+
+#[tokio::test]
+async fn test_reader_checkout_rejects_invalid_tipping_locally() {
+    let server = MockServer::start().await;
+    let client = client(&server);
+    let total = TotalAmount::from_minor(1000, "EUR", 2);
+
+    let unsorted = CreateReaderCheckoutRequest::new(total.clone()).tip_rates(vec![0.2, 0.1]);
+    let out_of_range = CreateReaderCheckoutRequest::new(total.clone()).tip_rates(vec![1.5]);
+    let timeout = CreateReaderCheckoutRequest::new(total.clone()).tip_timeout(10);
+
+    for request in [unsorted, out_of_range, timeout] {
+        assert!(client
+            .create_merchant_reader_checkout("M123", "rdr_1", &request)
+            .await
+            .is_err());
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_reader_checkout_leaves_minor_unit_to_the_api() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v0.1/merchants/M123/readers/rdr_1/checkout"))
+        .and(body_partial_json(serde_json::json!({
+            "total_amount": {"value": 1000, "currency": "CLP", "minor_unit": 2}
+        })))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "data": {"client_transaction_id": "ctx_2"}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = CreateReaderCheckoutRequest::new(TotalAmount::from_minor(1000, "CLP", 2));
+    assert!(request.validate().is_ok());
+    assert!(request.check_minor_unit().is_err());
+    assert!(client(&server)
+        .create_merchant_reader_checkout("M123", "rdr_1", &request)
+        .await
+        .is_ok());
+}
+
+#[test]
+fn test_split_bill_sums_exactly() {
+    let total = Money::from_minor_units(10_000, "EUR").unwrap();
+    let checkouts = CreateReaderCheckoutRequest::split(&total, 3).unwrap();
+    let values: Vec<u64> = checkouts.iter().map(|c| c.total_amount.value).collect();
+    assert_eq!(values, vec![3334, 3333, 3333]);
+    assert_eq!(values.iter().sum::<u64>(), 10_000);
+}
+
+#[tokio::test]
+async fn test_invalid_reader_id_is_rejected_locally() {
+    let server = MockServer::start().await;
+    let client = client(&server);
+
+    for id in [
+        "",
+        "rdr_",
+        "reader_1",
+        "rdr_3MS-AFM",
+        "3MSAFM23CK82VSTT4BN6RWSQ65",
+    ] {
+        assert!(ReaderId::parse(id).is_err(), "{id:?} should be rejected");
+        assert!(client.retrieve_merchant_reader("M123", id).await.is_err());
+        assert!(client.delete_merchant_reader("M123", id).await.is_err());
+    }
+    assert!(server.received_requests().await.unwrap().is_empty());
+
+    let id = ReaderId::parse("rdr_3MSAFM23CK82VSTT4BN6RWSQ65").unwrap();
+    assert_eq!(id, "rdr_3MSAFM23CK82VSTT4BN6RWSQ65");
+    assert_eq!(
+        serde_json::to_string(&id).unwrap(),
+        "\"rdr_3MSAFM23CK82VSTT4BN6RWSQ65\""
+    );
+}
@@ -1,4 +1,4 @@
-use sumup_rs::{CreateMemberRequest, CreateRoleRequest, Role, SumUpClient};
+use sumup_rs::{CreateMemberRequest, CreateRoleRequest, Permission, Role, RoleTemplate, SumUpClient};
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -8,13 +8,14 @@ async fn test_create_role_success() {
     let client = SumUpClient::with_custom_url("test_api_key".to_string(), mock_server.uri()).unwrap();
 
     let expected_role = Role {
-        id: "role_123".to_string(),
+        id: "role_123".into(),
         name: "Admin".to_string(),
-        membership_id: "membership_123".to_string(),
-        permissions: vec!["read".to_string(), "write".to_string()],
+        membership_id: "membership_123".into(),
+        permissions: vec!["read".into(), "write".into()],
         is_predefined: false,
         created_at: chrono::Utc::now(),
         updated_at: None,
+        extra: Default::default(),
     };
 
     Mock::given(method("POST"))
@@ -33,7 +34,7 @@ async fn test_create_role_success() {
 
     let request = CreateRoleRequest {
         name: "Admin".to_string(),
-        permissions: vec!["read".to_string(), "write".to_string()],
+        permissions: vec!["read".into(), "write".into()],
     };
     let result = client.create_role("merchant_123", &request).await;
 
@@ -74,3 +75,46 @@ async fn test_create_member_success() {
     assert_eq!(member.id, "member_123");
     assert_eq!(member.email, "john@example.com");
 }
+
+#[tokio::test]
+async fn test_role_template_diff_against_existing_role() {
+    let mock_server = MockServer::start().await;
+    let client = SumUpClient::with_custom_url("test_api_key".to_string(), mock_server.uri()).unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/v0.1/merchants/merchant_123/roles/role_456"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": "role_456",
+            "name": "Accountant",
+            "membership_id": "membership_123",
+            "permissions": ["full_transaction_history_view", "view_payout"],
+            "is_predefined": false,
+            "created_at": "2024-01-01T00:00:00Z"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let role = client.retrieve_role("merchant_123", "role_456").await.unwrap();
+    assert!(role.has_permission(&Permission::FullTransactionHistoryView));
+    assert_eq!(role.permission_set().unknown(), vec!["view_payout"]);
+
+    let diff = RoleTemplate::accountant().diff(&role);
+    assert!(!diff.is_empty());
+    assert_eq!(diff.missing.to_strings(), vec!["view_payouts", "view_reports"]);
+    assert_eq!(diff.extra.to_strings(), vec!["view_payout"]);
+}
+
+#[test]
+fn test_membership_has_permission() {
+    let membership: sumup_rs::Membership = serde_json::from_value(serde_json::json!({
+        "id": "membership_123",
+        "roles": ["role_456"],
+        "permissions": ["create_moto_payments", "refund_transactions"],
+        "status": "active"
+    }))
+    .unwrap();
+
+    assert!(membership.has_permission(&Permission::RefundTransactions));
+    assert!(!membership.has_permission(&Permission::ManageMembers));
+    assert!(RoleTemplate::cashier().permissions.is_subset(&membership.permission_set()));
+}
@@ -0,0 +1,396 @@
+#![cfg(feature = "team-sync")]
+
+use sumup_rs::team_sync::{DesiredTeam, TeamChange, TeamSync};
+use sumup_rs::{Error, Member, Role};
+use wiremock::matchers::{body_json, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+mod common;
+use common::client;
+
+const DESIRED: &str = r#"
+roles:
+  - name: Shift Lead
+    permissions: [create_moto_payments, refund_transactions]
+  - name: Bookkeeper
+    permissions: [full_transaction_history_view, view_payouts]
+members:
+  - email: Anna@example.com
+    roles: [Shift Lead]
+  - email: ben@example.com
+    roles: [Bookkeeper]
+  - email: cleo@example.com
+    roles: [Employee]
+"#;
+
+fn role_json(id: &str, name: &str, permissions: &[&str], predefined: bool) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": name,
+        "membership_id": "membership_1",
+        "permissions": permissions,
+        "is_predefined": predefined,
+        "created_at": "2024-01-01T00:00:00Z"
+    })
+}
+
+fn member_json(id: &str, email: &str, roles: &[&str]) -> serde_json::Value {
+    serde_json::json!({"id": id, "email": email, "roles": roles, "status": "active"})
+}
+
+fn current_roles() -> Vec<serde_json::Value> {
+    vec![
+        role_json("role_employee", "Employee", &["create_moto_payments"], true),
+        role_json("role_lead", "Shift Lead", &["create_moto_payments"], false),
+        role_json("role_old", "Weekend", &["create_moto_payments"], false),
+    ]
+}
+
+fn current_members() -> Vec<serde_json::Value> {
+    vec![
+        member_json("mem_anna", "anna@example.com", &["role_lead"]),
+        member_json("mem_cleo", "cleo@example.com", &["role_old"]),
+        member_json("mem_dan", "dan@example.com", &["role_employee"]),
+    ]
+}
+
+fn parse<T: serde::de::DeserializeOwned>(values: Vec<serde_json::Value>) -> Vec<T> {
+    values
+        .into_iter()
+        .map(|v| serde_json::from_value(v).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_plan_from_yaml_diffs_members_and_roles() {
+    let desired = DesiredTeam::from_yaml(DESIRED).unwrap();
+    let members: Vec<Member> = parse(current_members());
+    let roles: Vec<Role> = parse(current_roles());
+
+    let plan = TeamSync::new(desired.clone())
+        .prune(true)
+        .plan_against(&members, &roles)
+        .unwrap();
+    let lines: Vec<String> = plan.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        lines,
+        vec![
+            "update role Shift Lead [create_moto_payments, refund_transactions]",
+            "create role Bookkeeper [full_transaction_history_view, view_payouts]",
+            "create member ben@example.com [Bookkeeper]",
+            "update member cleo@example.com [Employee]",
+            "delete member dan@example.com",
+            "delete role Weekend",
+        ]
+    );
+
+    let without_prune = TeamSync::new(desired)
+        .plan_against(&members, &roles)
+        .unwrap();
+    assert!(without_prune.iter().all(|c| !matches!(
+        c,
+        TeamChange::DeleteMember { .. } | TeamChange::DeleteRole { .. }
+    )));
+}
+
+#[test]
+fn test_plan_rejects_invalid_desired_state() {
+    let roles: Vec<Role> = parse(current_roles());
+
+    let unknown_role = DesiredTeam::from_json(
+        r#"{"members": [{"email": "anna@example.com", "roles": ["Supervisor"]}]}"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        TeamSync::new(unknown_role).plan_against(&[], &roles),
+        Err(Error::InvalidInput(_))
+    ));
+
+    let predefined = DesiredTeam::from_json(r#"{"roles": [{"name": "Employee"}]}"#).unwrap();
+    assert!(matches!(
+        TeamSync::new(predefined).plan_against(&[], &roles),
+        Err(Error::InvalidInput(_))
+    ));
+
+    let duplicate = DesiredTeam::from_json(
+        r#"{"members": [{"email": "a@example.com"}, {"email": "A@example.com"}]}"#,
+    )
+    .unwrap();
+    assert!(duplicate.validate().is_err());
+
+    let typo = DesiredTeam::from_json(
+        r#"{"roles": [{"name": "Till", "permissions": ["create_moto_payments", "refund_transaction"]}]}"#,
+    )
+    .unwrap();
+    match typo.validate() {
+        Err(Error::InvalidInput(message)) => assert!(message.contains("refund_transaction")),
+        other => panic!("expected an unknown permission error, got {other:?}"),
+    }
+}
+
+async fn mount_current_state(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/v0.1/merchants/M123/members"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(serde_json::json!({"items": current_members()})),
+        )
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/merchants/M123/roles"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"roles": current_roles()})),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_dry_run_sends_no_changes() {
+    let server = MockServer::start().await;
+    mount_current_state(&server).await;
+
+    let report = TeamSync::new(DesiredTeam::from_yaml(DESIRED).unwrap())
+        .dry_run(true)
+        .sync(&client(&server), "M123")
+        .await
+        .unwrap();
+
+    assert!(report.dry_run);
+    assert_eq!(report.planned.len(), 4);
+    assert!(report.applied.is_empty());
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests.iter().all(|r| r.method.as_str() == "GET"));
+}
+
+#[tokio::test]
+async fn test_apply_resolves_created_roles_and_reports_failures() {
+    let server = MockServer::start().await;
+    mount_current_state(&server).await;
+    Mock::given(method("PATCH"))
+        .and(path("/v0.1/merchants/M123/roles/role_lead"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(role_json(
+            "role_lead",
+            "Shift Lead",
+            &["create_moto_payments", "refund_transactions"],
+            false,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v0.1/merchants/M123/roles"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(role_json(
+            "role_books",
+            "Bookkeeper",
+            &["full_transaction_history_view", "view_payouts"],
+            false,
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v0.1/merchants/M123/members"))
+        .and(body_json(
+            serde_json::json!({"email": "ben@example.com", "roles": ["role_books"]}),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(member_json(
+            "mem_ben",
+            "ben@example.com",
+            &["role_books"],
+        )))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/v0.1/merchants/M123/members/mem_cleo"))
+        .respond_with(
+            ResponseTemplate::new(403).set_body_json(serde_json::json!({"message": "Forbidden"})),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let report = TeamSync::new(DesiredTeam::from_yaml(DESIRED).unwrap())
+        .sync(&client(&server), "M123")
+        .await
+        .unwrap();
+
+    assert!(!report.is_success());
+    assert_eq!(report.applied.len(), 3);
+    assert_eq!(report.failed.len(), 1);
+    assert!(matches!(
+        report.failed[0].change,
+        TeamChange::UpdateMember { ref email, .. } if email == "cleo@example.com"
+    ));
+}
+
+#[test]
+fn test_plan_is_empty_once_applied() {
+    let desired = DesiredTeam::from_yaml(DESIRED).unwrap();
+    let roles: Vec<Role> = parse(vec![
+        role_json("role_employee", "Employee", &["create_moto_payments"], true),
+        role_json(
+            "role_lead",
+            "Shift Lead",
+            &["refund_transactions", "create_moto_payments"],
+            false,
+        ),
+        role_json(
+            "role_books",
+            "Bookkeeper",
+            &["view_payouts", "full_transaction_history_view"],
+            false,
+        ),
+    ]);
+    let members: Vec<Member> = parse(vec![
+        member_json("mem_anna", "anna@example.com", &["role_lead"]),
+        member_json("mem_ben", "ben@example.com", &["role_books"]),
+        member_json("mem_cleo", "cleo@example.com", &["role_employee"]),
+    ]);
+
+    let plan = TeamSync::new(desired)
+        .prune(true)
+        .plan_against(&members, &roles)
+        .unwrap();
+    assert!(plan.is_empty(), "unexpected changes:\n{plan}");
+}
+
+#[tokio::test]
+async fn test_plan_reads_every_page_of_members() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/merchants/M123/members"))
+        .and(query_param("offset", "0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [
+                member_json("mem_anna", "anna@example.com", &["role_employee"]),
+                member_json("mem_ben", "ben@example.com", &["role_employee"])
+            ],
+            "total_count": 3
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/merchants/M123/members"))
+        .and(query_param("offset", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [member_json("mem_cleo", "cleo@example.com", &["role_employee"])],
+            "total_count": 3
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v0.1/merchants/M123/roles"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!({"roles": current_roles()})),
+        )
+        .mount(&server)
+        .await;
+
+    let desired = DesiredTeam::from_json(
+        r#"{"members": [
+            {"email": "anna@example.com", "roles": ["Employee"]},
+            {"email": "ben@example.com", "roles": ["Employee"]},
+            {"email": "cleo@example.com", "roles": ["Employee"]}
+        ]}"#,
+    )
+    .unwrap();
+    let plan = TeamSync::new(desired)
+        .plan(&client(&server), "M123")
+        .await
+        .unwrap();
+    assert!(plan.is_empty(), "unexpected changes:\n{plan}");
+}
+
+#[test]
+fn test_prune_keeps_owner_and_admin_members_unless_opted_in() {
+    let desired = DesiredTeam::from_json(
+        r#"{"members": [{"email": "anna@example.com", "roles": ["Employee"]}]}"#,
+    )
+    .unwrap();
+    let mut roles: Vec<Role> = parse(current_roles());
+    roles.extend(parse::<Role>(vec![
+        role_json("role_owner", "Owner", &[], true),
+        role_json("role_admin", "Administrator", &[], true),
+    ]));
+    let members: Vec<Member> = parse(vec![
+        member_json("mem_anna", "anna@example.com", &["role_employee"]),
+        member_json("mem_owner", "owner@example.com", &["role_owner"]),
+        member_json(
+            "mem_admin",
+            "admin@example.com",
+            &["role_admin", "role_old"],
+        ),
+        member_json("mem_dan", "dan@example.com", &["role_employee"]),
+    ]);
+
+    let plan = TeamSync::new(desired.clone())
+        .prune(true)
+        .plan_against(&members, &roles)
+        .unwrap();
+    let deleted: Vec<&str> = plan
+        .iter()
+        .filter_map(|c| match c {
+            TeamChange::DeleteMember { email, .. } => Some(email.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(deleted, vec!["dan@example.com"]);
+    assert_eq!(
+        plan.protected_members,
+        vec!["owner@example.com", "admin@example.com"]
+    );
+    // the protected admin still holds the custom role, so it is not deleted either
+    assert!(!plan
+        .iter()
+        .any(|c| matches!(c, TeamChange::DeleteRole { name, .. } if name == "Weekend")));
+
+    let plan = TeamSync::new(desired)
+        .prune(true)
+        .prune_privileged(true)
+        .plan_against(&members, &roles)
+        .unwrap();
+    let deleted = plan
+        .iter()
+        .filter(|c| matches!(c, TeamChange::DeleteMember { .. }))
+        .count();
+    assert_eq!(deleted, 3);
+    assert!(plan.protected_members.is_empty());
+}
+
+#[test]
+fn test_update_keeps_owner_role_of_listed_member_unless_opted_in() {
+    let desired = DesiredTeam::from_json(
+        r#"{"members": [{"email": "owner@example.com", "roles": ["Shift Lead"]}]}"#,
+    )
+    .unwrap();
+    let mut roles: Vec<Role> = parse(current_roles());
+    roles.push(parse::<Role>(vec![role_json("role_owner", "Owner", &[], true)]).remove(0));
+    let members: Vec<Member> = parse(vec![member_json(
+        "mem_owner",
+        "owner@example.com",
+        &["role_owner"],
+    )]);
+
+    let plan = TeamSync::new(desired.clone())
+        .plan_against(&members, &roles)
+        .unwrap();
+    let lines: Vec<String> = plan.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        lines,
+        vec!["update member owner@example.com [Owner, Shift Lead]"]
+    );
+    assert_eq!(plan.protected_members, vec!["owner@example.com"]);
+
+    let plan = TeamSync::new(desired)
+        .prune_privileged(true)
+        .plan_against(&members, &roles)
+        .unwrap();
+    let lines: Vec<String> = plan.iter().map(ToString::to_string).collect();
+    assert_eq!(lines, vec!["update member owner@example.com [Shift Lead]"]);
+    assert!(plan.protected_members.is_empty());
+}